@@ -1,4 +1,4 @@
-use crate::{DataPage, Paginate};
+use crate::{DataPage, Paginate, rate_limit::TokenBucket};
 use artifactsmmo_openapi::{
     apis::{
         Error,
@@ -17,11 +17,15 @@ use std::sync::Arc;
 #[derive(Default, Debug)]
 pub struct AccountApi {
     configuration: Arc<Configuration>,
+    limiter: Arc<TokenBucket>,
 }
 
 impl AccountApi {
-    pub(crate) fn new(configuration: Arc<Configuration>) -> Self {
-        Self { configuration }
+    pub(crate) fn new(configuration: Arc<Configuration>, limiter: Arc<TokenBucket>) -> Self {
+        Self {
+            configuration,
+            limiter,
+        }
     }
 
     pub fn characters(
@@ -29,6 +33,7 @@ impl AccountApi {
         account: &str,
     ) -> Result<CharactersListSchema, Error<GetAccountCharactersAccountsAccountCharactersGetError>>
     {
+        self.limiter.acquire();
         get_account_characters_accounts_account_characters_get(&self.configuration, account)
     }
 
@@ -41,6 +46,7 @@ impl AccountApi {
     > {
         AchievementsRequest {
             configuration: &self.configuration,
+            limiter: &self.limiter,
             account,
         }
         .send()
@@ -49,6 +55,7 @@ impl AccountApi {
 
 struct AchievementsRequest<'a> {
     configuration: &'a Configuration,
+    limiter: &'a TokenBucket,
     account: &'a str,
 }
 
@@ -58,6 +65,7 @@ impl<'a> Paginate for AchievementsRequest<'a> {
     type Error = GetAccountAchievementsAccountsAccountAchievementsGetError;
 
     fn request_page(&self, current_page: u32) -> Result<Self::Page, Error<Self::Error>> {
+        self.limiter.acquire();
         get_account_achievements_accounts_account_achievements_get(
             self.configuration,
             self.account,
@@ -1,4 +1,4 @@
-use crate::{DataPage, Paginate};
+use crate::{DataPage, Paginate, rate_limit::TokenBucket};
 use artifactsmmo_openapi::{
     apis::{
         Error,
@@ -15,11 +15,15 @@ use std::sync::Arc;
 #[derive(Default, Debug)]
 pub struct BankApi {
     configuration: Arc<Configuration>,
+    limiter: Arc<TokenBucket>,
 }
 
 impl BankApi {
-    pub fn new(configuration: Arc<Configuration>) -> Self {
-        BankApi { configuration }
+    pub fn new(configuration: Arc<Configuration>, limiter: Arc<TokenBucket>) -> Self {
+        BankApi {
+            configuration,
+            limiter,
+        }
     }
 
     pub fn get_items(
@@ -27,17 +31,20 @@ impl BankApi {
     ) -> Result<Vec<SimpleItemSchema>, Error<GetBankItemsMyBankItemsGetError>> {
         BankItemsRequest {
             configuration: &self.configuration,
+            limiter: &self.limiter,
         }
         .send()
     }
 
     pub fn get_details(&self) -> Result<BankResponseSchema, Error<GetBankDetailsMyBankGetError>> {
+        self.limiter.acquire();
         get_bank_details_my_bank_get(&self.configuration)
     }
 }
 
 struct BankItemsRequest<'a> {
     configuration: &'a Configuration,
+    limiter: &'a TokenBucket,
 }
 
 impl<'a> Paginate for BankItemsRequest<'a> {
@@ -46,6 +53,7 @@ impl<'a> Paginate for BankItemsRequest<'a> {
     type Error = GetBankItemsMyBankItemsGetError;
 
     fn request_page(&self, current_page: u32) -> Result<Self::Page, Error<Self::Error>> {
+        self.limiter.acquire();
         get_bank_items_my_bank_items_get(self.configuration, None, Some(current_page), Some(100))
     }
 }
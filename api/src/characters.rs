@@ -1,3 +1,4 @@
+use crate::rate_limit::TokenBucket;
 use artifactsmmo_openapi::{
     apis::{
         Error,
@@ -11,17 +12,22 @@ use std::sync::Arc;
 #[derive(Default, Debug)]
 pub struct CharactersApi {
     configuration: Arc<Configuration>,
+    limiter: Arc<TokenBucket>,
 }
 
 impl CharactersApi {
-    pub(crate) fn new(configuration: Arc<Configuration>) -> Self {
-        Self { configuration }
+    pub(crate) fn new(configuration: Arc<Configuration>, limiter: Arc<TokenBucket>) -> Self {
+        Self {
+            configuration,
+            limiter,
+        }
     }
 
     pub fn get(
         &self,
         name: &str,
     ) -> Result<CharacterResponseSchema, Error<GetCharacterCharactersNameGetError>> {
+        self.limiter.acquire();
         get_character_characters_name_get(&self.configuration, name)
     }
 }
@@ -1,4 +1,4 @@
-use crate::{DataPage, Paginate};
+use crate::{DataPage, Paginate, rate_limit::TokenBucket};
 use artifactsmmo_openapi::{
     apis::{
         Error,
@@ -15,16 +15,21 @@ use std::{option::Option, sync::Arc};
 #[derive(Default, Debug)]
 pub struct EventsApi {
     configuration: Arc<Configuration>,
+    limiter: Arc<TokenBucket>,
 }
 
 impl EventsApi {
-    pub(crate) fn new(configuration: Arc<Configuration>) -> Self {
-        Self { configuration }
+    pub(crate) fn new(configuration: Arc<Configuration>, limiter: Arc<TokenBucket>) -> Self {
+        Self {
+            configuration,
+            limiter,
+        }
     }
 
     pub fn get_all(&self) -> Result<Vec<EventSchema>, Error<GetAllEventsEventsGetError>> {
         EventsRequest {
             configuration: &self.configuration,
+            limiter: &self.limiter,
         }
         .send()
     }
@@ -34,6 +39,7 @@ impl EventsApi {
     ) -> Result<Vec<ActiveEventSchema>, Error<GetAllActiveEventsEventsActiveGetError>> {
         ActiveEventsRequest {
             configuration: &self.configuration,
+            limiter: &self.limiter,
         }
         .send()
     }
@@ -41,6 +47,7 @@ impl EventsApi {
 
 struct EventsRequest<'a> {
     configuration: &'a Configuration,
+    limiter: &'a TokenBucket,
 }
 
 impl<'a> Paginate for EventsRequest<'a> {
@@ -49,6 +56,7 @@ impl<'a> Paginate for EventsRequest<'a> {
     type Error = GetAllEventsEventsGetError;
 
     fn request_page(&self, page: u32) -> Result<Self::Page, Error<Self::Error>> {
+        self.limiter.acquire();
         get_all_events_events_get(self.configuration, None, Some(page), Some(100))
     }
 }
@@ -65,6 +73,7 @@ impl DataPage<EventSchema> for DataPageEventSchema {
 
 struct ActiveEventsRequest<'a> {
     configuration: &'a Configuration,
+    limiter: &'a TokenBucket,
 }
 
 impl<'a> Paginate for ActiveEventsRequest<'a> {
@@ -73,6 +82,7 @@ impl<'a> Paginate for ActiveEventsRequest<'a> {
     type Error = GetAllActiveEventsEventsActiveGetError;
 
     fn request_page(&self, current_page: u32) -> Result<Self::Page, Error<Self::Error>> {
+        self.limiter.acquire();
         get_all_active_events_events_active_get(self.configuration, Some(current_page), Some(100))
     }
 }
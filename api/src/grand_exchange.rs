@@ -1,6 +1,6 @@
 use std::{result::Result, sync::Arc, vec::Vec};
 
-use crate::{DataPage, Paginate};
+use crate::{DataPage, Paginate, rate_limit::TokenBucket};
 use artifactsmmo_openapi::{
     apis::{
         Error,
@@ -23,11 +23,15 @@ use artifactsmmo_openapi::{
 #[derive(Default, Debug)]
 pub struct GrandExchangeApi {
     configuration: Arc<Configuration>,
+    limiter: Arc<TokenBucket>,
 }
 
 impl GrandExchangeApi {
-    pub(crate) fn new(configuration: Arc<Configuration>) -> Self {
-        Self { configuration }
+    pub(crate) fn new(configuration: Arc<Configuration>, limiter: Arc<TokenBucket>) -> Self {
+        Self {
+            configuration,
+            limiter,
+        }
     }
 
     pub fn sell_history(
@@ -37,6 +41,7 @@ impl GrandExchangeApi {
     {
         SellHistoryRequest {
             configuration: &self.configuration,
+            limiter: &self.limiter,
             code: item_code,
         }
         .send()
@@ -47,6 +52,7 @@ impl GrandExchangeApi {
     ) -> Result<Vec<GeOrderSchema>, Error<GetGeSellOrdersGrandexchangeOrdersGetError>> {
         SellOrdersRequest {
             configuration: &self.configuration,
+            limiter: &self.limiter,
         }
         .send()
     }
@@ -55,17 +61,20 @@ impl GrandExchangeApi {
         &self,
         id: &str,
     ) -> Result<GeOrderResponseSchema, Error<GetGeSellOrderGrandexchangeOrdersIdGetError>> {
+        self.limiter.acquire();
         get_ge_sell_order_grandexchange_orders_id_get(&self.configuration, id)
     }
 }
 
 struct SellHistoryRequest<'a> {
     configuration: &'a Configuration,
+    limiter: &'a TokenBucket,
     code: &'a str,
 }
 
 struct SellOrdersRequest<'a> {
     configuration: &'a Configuration,
+    limiter: &'a TokenBucket,
 }
 impl<'a> Paginate for SellHistoryRequest<'a> {
     type Data = GeOrderHistorySchema;
@@ -73,6 +82,7 @@ impl<'a> Paginate for SellHistoryRequest<'a> {
     type Error = GetGeSellHistoryGrandexchangeHistoryCodeGetError;
 
     fn request_page(&self, current_page: u32) -> Result<Self::Page, Error<Self::Error>> {
+        self.limiter.acquire();
         get_ge_sell_history_grandexchange_history_code_get(
             self.configuration,
             self.code,
@@ -100,6 +110,7 @@ impl<'a> Paginate for SellOrdersRequest<'a> {
     type Error = GetGeSellOrdersGrandexchangeOrdersGetError;
 
     fn request_page(&self, page: u32) -> Result<Self::Page, Error<Self::Error>> {
+        self.limiter.acquire();
         get_ge_sell_orders_grandexchange_orders_get(
             self.configuration,
             None,
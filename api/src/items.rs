@@ -1,4 +1,4 @@
-use crate::{DataPage, Paginate};
+use crate::{DataPage, Paginate, rate_limit::TokenBucket};
 use artifactsmmo_openapi::{
     apis::{
         Error,
@@ -12,16 +12,21 @@ use std::sync::Arc;
 #[derive(Default, Debug)]
 pub struct ItemsApi {
     configuration: Arc<Configuration>,
+    limiter: Arc<TokenBucket>,
 }
 
 impl ItemsApi {
-    pub(crate) fn new(configuration: Arc<Configuration>) -> Self {
-        Self { configuration }
+    pub(crate) fn new(configuration: Arc<Configuration>, limiter: Arc<TokenBucket>) -> Self {
+        Self {
+            configuration,
+            limiter,
+        }
     }
 
     pub fn get_all(&self) -> Result<Vec<ItemSchema>, Error<GetAllItemsItemsGetError>> {
         ItemsRequest {
             configuration: &self.configuration,
+            limiter: &self.limiter,
         }
         .send()
     }
@@ -29,6 +34,7 @@ impl ItemsApi {
 
 struct ItemsRequest<'a> {
     configuration: &'a Configuration,
+    limiter: &'a TokenBucket,
 }
 
 impl<'a> Paginate for ItemsRequest<'a> {
@@ -37,6 +43,7 @@ impl<'a> Paginate for ItemsRequest<'a> {
     type Error = GetAllItemsItemsGetError;
 
     fn request_page(&self, current_page: u32) -> Result<Self::Page, Error<Self::Error>> {
+        self.limiter.acquire();
         get_all_items_items_get(
             self.configuration,
             None,
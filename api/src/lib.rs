@@ -10,6 +10,7 @@ pub use maps::MapsApi;
 pub use monsters::MonstersApi;
 pub use my_characters::MyCharacterApi;
 pub use npcs::NpcsApi;
+pub use rate_limit::{BucketUsage, RateLimits, RateLimitsUsage, TokenBucket};
 pub use resources::ResourcesApi;
 pub use server::ServerApi;
 pub use tasks::TasksApi;
@@ -26,6 +27,7 @@ pub mod maps;
 pub mod monsters;
 pub mod my_characters;
 pub mod npcs;
+pub mod rate_limit;
 pub mod resources;
 pub mod server;
 pub mod tasks;
@@ -45,34 +47,70 @@ pub struct ArtifactApi {
     pub server: ServerApi,
     pub tasks: TasksApi,
     pub grand_exchange: GrandExchangeApi,
+    pub rate_limits: RateLimits,
 }
 
 impl ArtifactApi {
     pub fn new(base_path: String, token: String) -> Self {
+        Self::with_rate_limits(base_path, token, RateLimits::default())
+    }
+
+    /// Builds the API with the given rate limits. Clones of the same `RateLimits`
+    /// can be passed to several instances to make them share a budget.
+    pub fn with_rate_limits(base_path: String, token: String, rate_limits: RateLimits) -> Self {
+        Self::with_http_client(
+            base_path,
+            Some(token),
+            reqwest::blocking::Client::new(),
+            rate_limits,
+        )
+    }
+
+    /// Builds the API without credentials. Only the endpoints serving public
+    /// game data can be used with it.
+    pub fn public(base_path: String) -> Self {
+        Self::with_http_client(
+            base_path,
+            None,
+            reqwest::blocking::Client::new(),
+            RateLimits::default(),
+        )
+    }
+
+    /// Builds the API sending its requests through the given HTTP `client`,
+    /// e.g. one configured with custom timeouts.
+    pub fn with_http_client(
+        base_path: String,
+        token: Option<String>,
+        client: reqwest::blocking::Client,
+        rate_limits: RateLimits,
+    ) -> Self {
         let conf = Arc::new({
             let mut c = Configuration::new();
             c.base_path = base_path;
+            c.client = client;
             c
         });
         let auth_conf = Arc::new({
             let mut c = (*conf.clone()).clone();
-            c.bearer_access_token = Some(token);
+            c.bearer_access_token = token;
             c
         });
         Self {
-            account: AccountApi::new(auth_conf.clone()),
-            bank: BankApi::new(auth_conf.clone()),
-            character: CharactersApi::new(conf.clone()),
-            events: EventsApi::new(conf.clone()),
-            items: ItemsApi::new(conf.clone()),
-            maps: MapsApi::new(conf.clone()),
-            monsters: MonstersApi::new(conf.clone()),
-            my_character: MyCharacterApi::new(auth_conf.clone()),
-            resources: ResourcesApi::new(conf.clone()),
-            tasks: TasksApi::new(conf.clone()),
-            server: ServerApi::new(conf.clone()),
-            npcs: NpcsApi::new(conf.clone()),
-            grand_exchange: GrandExchangeApi::new(conf.clone()),
+            account: AccountApi::new(auth_conf.clone(), rate_limits.data.clone()),
+            bank: BankApi::new(auth_conf.clone(), rate_limits.data.clone()),
+            character: CharactersApi::new(conf.clone(), rate_limits.data.clone()),
+            events: EventsApi::new(conf.clone(), rate_limits.data.clone()),
+            items: ItemsApi::new(conf.clone(), rate_limits.data.clone()),
+            maps: MapsApi::new(conf.clone(), rate_limits.data.clone()),
+            monsters: MonstersApi::new(conf.clone(), rate_limits.data.clone()),
+            my_character: MyCharacterApi::new(auth_conf.clone(), rate_limits.actions.clone()),
+            resources: ResourcesApi::new(conf.clone(), rate_limits.data.clone()),
+            tasks: TasksApi::new(conf.clone(), rate_limits.data.clone()),
+            server: ServerApi::new(conf.clone(), rate_limits.data.clone()),
+            npcs: NpcsApi::new(conf.clone(), rate_limits.data.clone()),
+            grand_exchange: GrandExchangeApi::new(conf.clone(), rate_limits.data.clone()),
+            rate_limits,
         }
     }
 }
@@ -1,4 +1,4 @@
-use crate::{DataPage, Paginate};
+use crate::{DataPage, Paginate, rate_limit::TokenBucket};
 use artifactsmmo_openapi::{
     apis::{
         Error,
@@ -15,16 +15,21 @@ use std::sync::Arc;
 #[derive(Default, Debug)]
 pub struct MapsApi {
     configuration: Arc<Configuration>,
+    limiter: Arc<TokenBucket>,
 }
 
 impl MapsApi {
-    pub(crate) fn new(configuration: Arc<Configuration>) -> Self {
-        Self { configuration }
+    pub(crate) fn new(configuration: Arc<Configuration>, limiter: Arc<TokenBucket>) -> Self {
+        Self {
+            configuration,
+            limiter,
+        }
     }
 
     pub fn get_all(&self) -> Result<Vec<MapSchema>, Error<GetAllMapsMapsGetError>> {
         MapsRequest {
             configuration: &self.configuration,
+            limiter: &self.limiter,
         }
         .send()
     }
@@ -33,12 +38,14 @@ impl MapsApi {
         &self,
         id: i32,
     ) -> Result<MapResponseSchema, Error<GetMapByIdMapsIdMapIdGetError>> {
+        self.limiter.acquire();
         get_map_by_id_maps_id_map_id_get(&self.configuration, id)
     }
 }
 
 struct MapsRequest<'a> {
     configuration: &'a Configuration,
+    limiter: &'a TokenBucket,
 }
 
 impl<'a> Paginate for MapsRequest<'a> {
@@ -47,6 +54,7 @@ impl<'a> Paginate for MapsRequest<'a> {
     type Error = GetAllMapsMapsGetError;
 
     fn request_page(&self, page: u32) -> Result<Self::Page, Error<Self::Error>> {
+        self.limiter.acquire();
         get_all_maps_maps_get(
             self.configuration,
             None,
@@ -1,4 +1,4 @@
-use crate::{DataPage, Paginate};
+use crate::{DataPage, Paginate, rate_limit::TokenBucket};
 use artifactsmmo_openapi::{
     apis::{
         Error,
@@ -12,16 +12,21 @@ use std::sync::Arc;
 #[derive(Default, Debug)]
 pub struct MonstersApi {
     configuration: Arc<Configuration>,
+    limiter: Arc<TokenBucket>,
 }
 
 impl MonstersApi {
-    pub(crate) fn new(configuration: Arc<Configuration>) -> Self {
-        Self { configuration }
+    pub(crate) fn new(configuration: Arc<Configuration>, limiter: Arc<TokenBucket>) -> Self {
+        Self {
+            configuration,
+            limiter,
+        }
     }
 
     pub fn get_all(&self) -> Result<Vec<MonsterSchema>, Error<GetAllMonstersMonstersGetError>> {
         MonstersRequest {
             configuration: &self.configuration,
+            limiter: &self.limiter,
         }
         .send()
     }
@@ -29,6 +34,7 @@ impl MonstersApi {
 
 struct MonstersRequest<'a> {
     configuration: &'a Configuration,
+    limiter: &'a TokenBucket,
 }
 
 impl<'a> Paginate for MonstersRequest<'a> {
@@ -37,6 +43,7 @@ impl<'a> Paginate for MonstersRequest<'a> {
     type Error = GetAllMonstersMonstersGetError;
 
     fn request_page(&self, page: u32) -> Result<Self::Page, Error<Self::Error>> {
+        self.limiter.acquire();
         get_all_monsters_monsters_get(
             self.configuration,
             None,
@@ -1,3 +1,4 @@
+use crate::rate_limit::TokenBucket;
 use artifactsmmo_openapi::{
     apis::{
         Error,
@@ -75,11 +76,15 @@ use std::sync::Arc;
 #[derive(Default, Debug)]
 pub struct MyCharacterApi {
     configuration: Arc<Configuration>,
+    limiter: Arc<TokenBucket>,
 }
 
 impl MyCharacterApi {
-    pub(crate) fn new(configuration: Arc<Configuration>) -> Self {
-        MyCharacterApi { configuration }
+    pub(crate) fn new(configuration: Arc<Configuration>, limiter: Arc<TokenBucket>) -> Self {
+        MyCharacterApi {
+            configuration,
+            limiter,
+        }
     }
 
     pub fn r#move(
@@ -93,6 +98,7 @@ impl MyCharacterApi {
             y: Some(y),
             map_id: None,
         };
+        self.limiter.acquire();
         action_move_my_name_action_move_post(&self.configuration, name, dest)
     }
 
@@ -103,6 +109,7 @@ impl MyCharacterApi {
         CharacterTransitionResponseSchema,
         Error<ActionTransitionMyNameActionTransitionPostError>,
     > {
+        self.limiter.acquire();
         action_transition_my_name_action_transition_post(&self.configuration, name)
     }
 
@@ -114,6 +121,7 @@ impl MyCharacterApi {
         let schema = FightRequestSchema {
             participants: participants.map(|p| p.to_vec()),
         };
+        self.limiter.acquire();
         action_fight_my_name_action_fight_post(&self.configuration, name, Some(schema))
     }
 
@@ -121,6 +129,7 @@ impl MyCharacterApi {
         &self,
         name: &str,
     ) -> Result<CharacterRestResponseSchema, Error<ActionRestMyNameActionRestPostError>> {
+        self.limiter.acquire();
         action_rest_my_name_action_rest_post(&self.configuration, name)
     }
 
@@ -128,6 +137,7 @@ impl MyCharacterApi {
         &self,
         name: &str,
     ) -> Result<SkillResponseSchema, Error<ActionGatheringMyNameActionGatheringPostError>> {
+        self.limiter.acquire();
         action_gathering_my_name_action_gathering_post(&self.configuration, name)
     }
 
@@ -141,6 +151,7 @@ impl MyCharacterApi {
             code: item_code.to_owned(),
             quantity: Some(quantity),
         };
+        self.limiter.acquire();
         action_crafting_my_name_action_crafting_post(&self.configuration, name, schema)
     }
 
@@ -154,6 +165,7 @@ impl MyCharacterApi {
             code: item_code.to_owned(),
             quantity: Some(quantity),
         };
+        self.limiter.acquire();
         action_recycling_my_name_action_recycling_post(&self.configuration, name, schema)
     }
 
@@ -167,6 +179,7 @@ impl MyCharacterApi {
             code: item_code.to_owned(),
             quantity,
         };
+        self.limiter.acquire();
         action_delete_item_my_name_action_delete_post(&self.configuration, name, schema)
     }
 
@@ -178,6 +191,7 @@ impl MyCharacterApi {
         BankItemTransactionResponseSchema,
         Error<ActionDepositBankItemMyNameActionBankDepositItemPostError>,
     > {
+        self.limiter.acquire();
         action_deposit_bank_item_my_name_action_bank_deposit_item_post(
             &self.configuration,
             name,
@@ -193,6 +207,7 @@ impl MyCharacterApi {
         BankItemTransactionResponseSchema,
         Error<ActionWithdrawBankItemMyNameActionBankWithdrawItemPostError>,
     > {
+        self.limiter.acquire();
         action_withdraw_bank_item_my_name_action_bank_withdraw_item_post(
             &self.configuration,
             name,
@@ -209,6 +224,7 @@ impl MyCharacterApi {
         Error<ActionDepositBankGoldMyNameActionBankDepositGoldPostError>,
     > {
         let s = DepositWithdrawGoldSchema { quantity };
+        self.limiter.acquire();
         action_deposit_bank_gold_my_name_action_bank_deposit_gold_post(&self.configuration, name, s)
     }
 
@@ -221,6 +237,7 @@ impl MyCharacterApi {
         Error<ActionWithdrawBankGoldMyNameActionBankWithdrawGoldPostError>,
     > {
         let s = DepositWithdrawGoldSchema { quantity };
+        self.limiter.acquire();
         action_withdraw_bank_gold_my_name_action_bank_withdraw_gold_post(
             &self.configuration,
             name,
@@ -235,6 +252,7 @@ impl MyCharacterApi {
         BankExtensionTransactionResponseSchema,
         Error<ActionBuyBankExpansionMyNameActionBankBuyExpansionPostError>,
     > {
+        self.limiter.acquire();
         action_buy_bank_expansion_my_name_action_bank_buy_expansion_post(&self.configuration, name)
     }
 
@@ -247,6 +265,7 @@ impl MyCharacterApi {
     ) -> Result<EquipmentResponseSchema, Error<ActionEquipItemMyNameActionEquipPostError>> {
         let mut schema = EquipSchema::new(item_code.to_string(), slot);
         schema.quantity = quantity;
+        self.limiter.acquire();
         action_equip_item_my_name_action_equip_post(&self.configuration, name, schema)
     }
 
@@ -258,6 +277,7 @@ impl MyCharacterApi {
     ) -> Result<EquipmentResponseSchema, Error<ActionUnequipItemMyNameActionUnequipPostError>> {
         let mut schema = UnequipSchema::new(slot);
         schema.quantity = quantity;
+        self.limiter.acquire();
         action_unequip_item_my_name_action_unequip_post(&self.configuration, name, schema)
     }
 
@@ -271,6 +291,7 @@ impl MyCharacterApi {
             code: item_code.to_owned(),
             quantity,
         };
+        self.limiter.acquire();
         action_use_item_my_name_action_use_post(&self.configuration, name, schema)
     }
 
@@ -278,6 +299,7 @@ impl MyCharacterApi {
         &self,
         name: &str,
     ) -> Result<TaskResponseSchema, Error<ActionAcceptNewTaskMyNameActionTaskNewPostError>> {
+        self.limiter.acquire();
         action_accept_new_task_my_name_action_task_new_post(&self.configuration, name)
     }
 
@@ -286,6 +308,7 @@ impl MyCharacterApi {
         name: &str,
     ) -> Result<TaskCancelledResponseSchema, Error<ActionTaskCancelMyNameActionTaskCancelPostError>>
     {
+        self.limiter.acquire();
         action_task_cancel_my_name_action_task_cancel_post(&self.configuration, name)
     }
 
@@ -295,6 +318,7 @@ impl MyCharacterApi {
         item_code: &str,
         quantity: u32,
     ) -> Result<TaskTradeResponseSchema, Error<ActionTaskTradeMyNameActionTaskTradePostError>> {
+        self.limiter.acquire();
         action_task_trade_my_name_action_task_trade_post(
             &self.configuration,
             name,
@@ -307,6 +331,7 @@ impl MyCharacterApi {
         name: &str,
     ) -> Result<RewardDataResponseSchema, Error<ActionCompleteTaskMyNameActionTaskCompletePostError>>
     {
+        self.limiter.acquire();
         action_complete_task_my_name_action_task_complete_post(&self.configuration, name)
     }
 
@@ -315,6 +340,7 @@ impl MyCharacterApi {
         name: &str,
     ) -> Result<RewardDataResponseSchema, Error<ActionTaskExchangeMyNameActionTaskExchangePostError>>
     {
+        self.limiter.acquire();
         action_task_exchange_my_name_action_task_exchange_post(&self.configuration, name)
     }
 
@@ -328,6 +354,7 @@ impl MyCharacterApi {
         Error<ActionNpcBuyItemMyNameActionNpcBuyPostError>,
     > {
         let schema = NpcMerchantBuySchema::new(code, quantity);
+        self.limiter.acquire();
         action_npc_buy_item_my_name_action_npc_buy_post(&self.configuration, name, schema)
     }
 
@@ -341,6 +368,7 @@ impl MyCharacterApi {
         Error<ActionNpcSellItemMyNameActionNpcSellPostError>,
     > {
         let schema = NpcMerchantBuySchema::new(code, quantity);
+        self.limiter.acquire();
         action_npc_sell_item_my_name_action_npc_sell_post(&self.configuration, name, schema)
     }
 
@@ -354,6 +382,7 @@ impl MyCharacterApi {
             items: items.to_vec(),
             character: character.to_string(),
         };
+        self.limiter.acquire();
         action_give_items_my_name_action_give_item_post(&self.configuration, name, schema)
     }
 
@@ -367,6 +396,7 @@ impl MyCharacterApi {
             quantity,
             character: character.to_string(),
         };
+        self.limiter.acquire();
         action_give_gold_my_name_action_give_gold_post(&self.configuration, name, schema)
     }
 
@@ -380,6 +410,7 @@ impl MyCharacterApi {
         Error<ActionGeBuyItemMyNameActionGrandexchangeBuyPostError>,
     > {
         let schema = GeBuyOrderSchema::new(id.to_owned(), quantity);
+        self.limiter.acquire();
         action_ge_buy_item_my_name_action_grandexchange_buy_post(&self.configuration, name, schema)
     }
 
@@ -394,6 +425,7 @@ impl MyCharacterApi {
         Error<ActionGeCreateSellOrderMyNameActionGrandexchangeSellPostError>,
     > {
         let schema = GeOrderCreationrSchema::new(item_code.to_owned(), quantity, price);
+        self.limiter.acquire();
         action_ge_create_sell_order_my_name_action_grandexchange_sell_post(
             &self.configuration,
             name,
@@ -409,6 +441,7 @@ impl MyCharacterApi {
         GeTransactionResponseSchema,
         Error<ActionGeCancelSellOrderMyNameActionGrandexchangeCancelPostError>,
     > {
+        self.limiter.acquire();
         action_ge_cancel_sell_order_my_name_action_grandexchange_cancel_post(
             &self.configuration,
             name,
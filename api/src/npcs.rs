@@ -1,4 +1,4 @@
-use crate::{DataPage, Paginate};
+use crate::{DataPage, Paginate, rate_limit::TokenBucket};
 use artifactsmmo_openapi::{
     apis::{
         Error,
@@ -15,16 +15,21 @@ use std::sync::Arc;
 #[derive(Default, Debug)]
 pub struct NpcsApi {
     configuration: Arc<Configuration>,
+    limiter: Arc<TokenBucket>,
 }
 
 impl NpcsApi {
-    pub(crate) fn new(configuration: Arc<Configuration>) -> Self {
-        Self { configuration }
+    pub(crate) fn new(configuration: Arc<Configuration>, limiter: Arc<TokenBucket>) -> Self {
+        Self {
+            configuration,
+            limiter,
+        }
     }
 
     pub fn get_all(&self) -> Result<Vec<NpcSchema>, Error<GetAllNpcsNpcsDetailsGetError>> {
         NpcsRequest {
             configuration: &self.configuration,
+            limiter: &self.limiter,
         }
         .send()
     }
@@ -32,6 +37,7 @@ impl NpcsApi {
     pub fn get_items(&self) -> Result<Vec<NpcItem>, Error<GetAllNpcsItemsNpcsItemsGetError>> {
         NpcsItemsRequest {
             configuration: &self.configuration,
+            limiter: &self.limiter,
         }
         .send()
     }
@@ -39,6 +45,7 @@ impl NpcsApi {
 
 struct NpcsRequest<'a> {
     configuration: &'a Configuration,
+    limiter: &'a TokenBucket,
 }
 
 impl<'a> Paginate for NpcsRequest<'a> {
@@ -47,6 +54,7 @@ impl<'a> Paginate for NpcsRequest<'a> {
     type Error = GetAllNpcsNpcsDetailsGetError;
 
     fn request_page(&self, page: u32) -> Result<Self::Page, Error<Self::Error>> {
+        self.limiter.acquire();
         get_all_npcs_npcs_details_get(self.configuration, None, None, Some(page), Some(100))
     }
 }
@@ -63,6 +71,7 @@ impl DataPage<NpcSchema> for DataPageNpcSchema {
 
 struct NpcsItemsRequest<'a> {
     configuration: &'a Configuration,
+    limiter: &'a TokenBucket,
 }
 
 impl<'a> Paginate for NpcsItemsRequest<'a> {
@@ -71,6 +80,7 @@ impl<'a> Paginate for NpcsItemsRequest<'a> {
     type Error = GetAllNpcsItemsNpcsItemsGetError;
 
     fn request_page(&self, page: u32) -> Result<Self::Page, Error<Self::Error>> {
+        self.limiter.acquire();
         get_all_npcs_items_npcs_items_get(
             self.configuration,
             None,
@@ -0,0 +1,193 @@
+use std::{
+    sync::{Arc, Mutex},
+    thread::sleep,
+    time::{Duration, Instant},
+};
+
+/// Budget of the server for character actions (7 requests every 2 seconds).
+pub const ACTIONS_CAPACITY: u32 = 7;
+pub const ACTIONS_REFILL_PER_SEC: f64 = 3.5;
+
+/// Budget of the server for data endpoints (20 requests per second).
+pub const DATA_CAPACITY: u32 = 20;
+pub const DATA_REFILL_PER_SEC: f64 = 20.0;
+
+/// Token buckets shared by every request sent through an `ArtifactApi`.
+#[derive(Debug, Clone)]
+pub struct RateLimits {
+    pub actions: Arc<TokenBucket>,
+    pub data: Arc<TokenBucket>,
+}
+
+impl RateLimits {
+    pub fn new(actions: TokenBucket, data: TokenBucket) -> Self {
+        Self {
+            actions: actions.into(),
+            data: data.into(),
+        }
+    }
+
+    pub fn usage(&self) -> RateLimitsUsage {
+        RateLimitsUsage {
+            actions: self.actions.usage(),
+            data: self.data.usage(),
+        }
+    }
+}
+
+impl Default for RateLimits {
+    fn default() -> Self {
+        Self::new(
+            TokenBucket::new(ACTIONS_CAPACITY, ACTIONS_REFILL_PER_SEC),
+            TokenBucket::new(DATA_CAPACITY, DATA_REFILL_PER_SEC),
+        )
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RateLimitsUsage {
+    pub actions: BucketUsage,
+    pub data: BucketUsage,
+}
+
+/// Snapshot of a `TokenBucket` at a given instant.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BucketUsage {
+    pub capacity: u32,
+    /// Tokens that can be taken right now without waiting.
+    pub available: f64,
+    /// Requests currently waiting for a token.
+    pub queued: u32,
+    /// Total number of tokens handed out since the bucket was created.
+    pub acquired: u64,
+}
+
+/// Token bucket that queues callers until a token is available instead of
+/// rejecting them.
+///
+/// Each call to `acquire` reserves a token immediately, letting the token count
+/// go negative, and then sleeps until its reservation is covered by the refill.
+/// Callers are therefore served in the order they reserved.
+#[derive(Debug)]
+pub struct TokenBucket {
+    capacity: u32,
+    refill_per_sec: f64,
+    state: Mutex<BucketState>,
+}
+
+#[derive(Debug)]
+struct BucketState {
+    tokens: f64,
+    last_refill: Instant,
+    queued: u32,
+    acquired: u64,
+}
+
+impl TokenBucket {
+    pub fn new(capacity: u32, refill_per_sec: f64) -> Self {
+        assert!(capacity > 0, "token bucket capacity must be positive");
+        assert!(
+            refill_per_sec > 0.0,
+            "token bucket refill rate must be positive"
+        );
+        Self {
+            capacity,
+            refill_per_sec,
+            state: Mutex::new(BucketState {
+                tokens: capacity as f64,
+                last_refill: Instant::now(),
+                queued: 0,
+                acquired: 0,
+            }),
+        }
+    }
+
+    /// Blocks until a token is available and consumes it.
+    pub fn acquire(&self) {
+        let wait = self.reserve(Instant::now());
+        if wait.is_zero() {
+            return;
+        }
+        sleep(wait);
+        self.state.lock().unwrap().queued -= 1;
+    }
+
+    /// Consumes a token and returns how long the caller has to wait before
+    /// using it, without blocking. Used by async callers sleeping on their own
+    /// timer; they are not counted as queued while waiting.
+    pub fn reserve_delay(&self) -> Duration {
+        let wait = self.reserve(Instant::now());
+        if !wait.is_zero() {
+            self.state.lock().unwrap().queued -= 1;
+        }
+        wait
+    }
+
+    /// Consumes a token if one is available without waiting.
+    pub fn try_acquire(&self) -> bool {
+        let mut state = self.state.lock().unwrap();
+        self.refill(&mut state, Instant::now());
+        if state.tokens < 1.0 {
+            return false;
+        }
+        state.tokens -= 1.0;
+        state.acquired += 1;
+        true
+    }
+
+    pub fn usage(&self) -> BucketUsage {
+        let mut state = self.state.lock().unwrap();
+        self.refill(&mut state, Instant::now());
+        BucketUsage {
+            capacity: self.capacity,
+            available: state.tokens.max(0.0),
+            queued: state.queued,
+            acquired: state.acquired,
+        }
+    }
+
+    /// Reserves a token and returns how long the caller has to wait for it.
+    fn reserve(&self, now: Instant) -> Duration {
+        let mut state = self.state.lock().unwrap();
+        self.refill(&mut state, now);
+        state.tokens -= 1.0;
+        state.acquired += 1;
+        if state.tokens >= 0.0 {
+            return Duration::ZERO;
+        }
+        state.queued += 1;
+        Duration::from_secs_f64(-state.tokens / self.refill_per_sec)
+    }
+
+    fn refill(&self, state: &mut BucketState, now: Instant) {
+        let elapsed = now.saturating_duration_since(state.last_refill);
+        state.tokens =
+            (state.tokens + elapsed.as_secs_f64() * self.refill_per_sec).min(self.capacity as f64);
+        state.last_refill = now;
+    }
+}
+
+impl Default for TokenBucket {
+    fn default() -> Self {
+        Self::new(DATA_CAPACITY, DATA_REFILL_PER_SEC)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reservations_queue_behind_the_refill() {
+        let bucket = TokenBucket::new(2, 10.0);
+        let now = Instant::now();
+        bucket.state.lock().unwrap().last_refill = now;
+
+        assert_eq!(bucket.reserve(now), Duration::ZERO);
+        assert_eq!(bucket.reserve(now), Duration::ZERO);
+        assert_eq!(bucket.reserve(now), Duration::from_millis(100));
+        assert_eq!(bucket.reserve(now), Duration::from_millis(200));
+        assert_eq!(bucket.state.lock().unwrap().queued, 2);
+        assert_eq!(bucket.reserve(now + Duration::from_secs(1)), Duration::ZERO);
+    }
+}
@@ -1,3 +1,4 @@
+use crate::rate_limit::TokenBucket;
 use artifactsmmo_openapi::{
     apis::{configuration::Configuration, server_details_api::get_server_details_get},
     models::StatusResponseSchema,
@@ -7,15 +8,20 @@ use std::sync::Arc;
 #[derive(Default, Debug)]
 pub struct ServerApi {
     configuration: Arc<Configuration>,
+    limiter: Arc<TokenBucket>,
 }
 
 impl ServerApi {
-    pub(crate) fn new(configuration: Arc<Configuration>) -> Self {
-        Self { configuration }
+    pub(crate) fn new(configuration: Arc<Configuration>, limiter: Arc<TokenBucket>) -> Self {
+        Self {
+            configuration,
+            limiter,
+        }
     }
 
     //TODO: return result
     pub fn status(&self) -> Option<StatusResponseSchema> {
+        self.limiter.acquire();
         get_server_details_get(&self.configuration).ok()
     }
 }
@@ -1,4 +1,4 @@
-use crate::{DataPage, Paginate};
+use crate::{DataPage, Paginate, rate_limit::TokenBucket};
 use artifactsmmo_openapi::{
     apis::{
         Error,
@@ -15,16 +15,21 @@ use std::sync::Arc;
 #[derive(Default, Debug)]
 pub struct TasksApi {
     configuration: Arc<Configuration>,
+    limiter: Arc<TokenBucket>,
 }
 
 impl TasksApi {
-    pub(crate) fn new(configuration: Arc<Configuration>) -> Self {
-        Self { configuration }
+    pub(crate) fn new(configuration: Arc<Configuration>, limiter: Arc<TokenBucket>) -> Self {
+        Self {
+            configuration,
+            limiter,
+        }
     }
 
     pub fn get_all(&self) -> Result<Vec<TaskFullSchema>, Error<GetAllTasksTasksListGetError>> {
         TasksRequest {
             configuration: &self.configuration,
+            limiter: &self.limiter,
         }
         .send()
     }
@@ -34,6 +39,7 @@ impl TasksApi {
     ) -> Result<Vec<DropRateSchema>, Error<GetAllTasksRewardsTasksRewardsGetError>> {
         TasksRewardsRequest {
             configuration: &self.configuration,
+            limiter: &self.limiter,
         }
         .send()
     }
@@ -41,6 +47,7 @@ impl TasksApi {
 
 struct TasksRequest<'a> {
     configuration: &'a Configuration,
+    limiter: &'a TokenBucket,
 }
 
 impl<'a> Paginate for TasksRequest<'a> {
@@ -49,6 +56,7 @@ impl<'a> Paginate for TasksRequest<'a> {
     type Error = GetAllTasksTasksListGetError;
 
     fn request_page(&self, current_page: u32) -> Result<Self::Page, Error<Self::Error>> {
+        self.limiter.acquire();
         get_all_tasks_tasks_list_get(
             self.configuration,
             None,
@@ -73,6 +81,7 @@ impl DataPage<TaskFullSchema> for DataPageTaskFullSchema {
 
 struct TasksRewardsRequest<'a> {
     configuration: &'a Configuration,
+    limiter: &'a TokenBucket,
 }
 
 impl<'a> Paginate for TasksRewardsRequest<'a> {
@@ -81,6 +90,7 @@ impl<'a> Paginate for TasksRewardsRequest<'a> {
     type Error = GetAllTasksRewardsTasksRewardsGetError;
 
     fn request_page(&self, current_page: u32) -> Result<Self::Page, Error<Self::Error>> {
+        self.limiter.acquire();
         get_all_tasks_rewards_tasks_rewards_get(self.configuration, Some(current_page), Some(100))
     }
 }
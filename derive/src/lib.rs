@@ -23,27 +23,44 @@ pub fn collection_client_derive(input: TokenStream) -> TokenStream {
     }
 }
 
-// #[proc_macro_derive(FromRequestError)]
-// pub fn from_request_error_derive(input: TokenStream) -> TokenStream {
-//     let ast = syn::parse_macro_input!(input as DeriveInput);
-//
-//     match ast.data {
-//         Data::Enum(ref _data) => {
-//             let name = &ast.ident;
-//             let (impl_generics, ty_generics, where_clause) = ast.generics.split_for_impl();
-//             let expanded = quote! {
-//                 impl #impl_generics From<RequestError> for #name #ty_generics #where_clause {
-//                     fn from(value: RequestError) -> Self {
-//                         if let RequestError::ResponseError(ref schema) = value {
-//                             return Self::try_from(schema.error.code as isize)
-//                                 .unwrap_or(Self::UnhandledError(value));
-//                             }
-//                         Self::UnhandledError(value)
-//                     }
-//                 }
-//             };
-//             expanded.into()
-//         }
-//         _ => panic!("FromRequestError derive can only be used on enums"),
-//     }
-// }
+/// Implements `From<RequestError>` for an error enum whose variants carry the
+/// server error codes as `#[repr(isize)]` discriminants. Response errors are
+/// mapped back to the variant with the matching code through the `TryFrom`
+/// repr conversion, including the last error of an action whose retries were
+/// exhausted; anything else ends up in `UnhandledError`.
+#[proc_macro_derive(FromRequestError)]
+pub fn from_request_error_derive(input: TokenStream) -> TokenStream {
+    let ast = syn::parse_macro_input!(input as DeriveInput);
+
+    match ast.data {
+        Data::Enum(ref data) => {
+            if !data.variants.iter().any(|v| v.ident == "UnhandledError") {
+                panic!(
+                    "FromRequestError derive requires an `UnhandledError(RequestError)` variant"
+                );
+            }
+            let name = &ast.ident;
+            let (impl_generics, ty_generics, where_clause) = ast.generics.split_for_impl();
+            let expanded = quote! {
+                impl #impl_generics From<crate::client::character::error::RequestError> for #name #ty_generics #where_clause {
+                    fn from(value: crate::client::character::error::RequestError) -> Self {
+                        use crate::client::character::error::RequestError;
+
+                        let response = match value {
+                            RequestError::RetriesExhausted { ref last, .. } => last.as_ref(),
+                            ref value => value,
+                        };
+                        if let RequestError::ResponseError(schema) = response {
+                            if let Ok(error) = Self::try_from(schema.error.code as isize) {
+                                return error;
+                            }
+                        }
+                        Self::UnhandledError(value)
+                    }
+                }
+            };
+            expanded.into()
+        }
+        _ => panic!("FromRequestError derive can only be used on enums"),
+    }
+}
@@ -0,0 +1,274 @@
+use crate::{
+    client::ClientError,
+    storage::{Encoding, FileStorage, Namespaced, Storage, StorageError},
+};
+use log::error;
+use serde::{Deserialize, Serialize, de::DeserializeOwned};
+use std::{
+    path::PathBuf,
+    sync::{
+        Arc, RwLock,
+        atomic::{AtomicBool, Ordering},
+    },
+};
+use thiserror::Error;
+
+pub const DEFAULT_CACHE_ROOT: &str = ".cache";
+/// Namespace of a cache storage holding the cache of each account.
+pub const ACCOUNTS_CACHE_DIR: &str = "accounts";
+
+/// Storage and version stamp of the static game data.
+///
+/// Every entry is stamped with the server version and season it was fetched
+/// under (see `ServerClient::cache_stamp`). When a version is known, entries
+/// stamped with another one are reported as stale so that the data is fetched
+/// again.
+///
+/// An offline cache is never bypassed: clients loading from it fail instead of
+/// falling back to the API.
+#[derive(Debug)]
+pub struct Cache {
+    storage: Arc<dyn Storage>,
+    version: RwLock<Option<String>>,
+    offline: AtomicBool,
+}
+
+#[derive(Debug, Error)]
+pub enum CacheError {
+    #[error("cache is stale: stamped with version `{found:?}`, expected `{expected}`")]
+    Stale {
+        found: Option<String>,
+        expected: String,
+    },
+    #[error(transparent)]
+    Storage(#[from] StorageError),
+    #[error(transparent)]
+    Json(#[from] serde_json::Error),
+    #[cfg(feature = "binary")]
+    #[error(transparent)]
+    BinaryEncode(#[from] rmp_serde::encode::Error),
+    #[cfg(feature = "binary")]
+    #[error(transparent)]
+    BinaryDecode(#[from] rmp_serde::decode::Error),
+}
+
+#[derive(Serialize, Deserialize)]
+struct CacheEntry<D> {
+    version: Option<String>,
+    data: D,
+}
+
+impl Cache {
+    /// Creates a cache storing JSON files under `root`.
+    pub fn new(root: impl Into<PathBuf>, version: Option<String>) -> Self {
+        Self::with_storage(FileStorage::json(root), version)
+    }
+
+    pub fn with_storage(storage: impl Storage + 'static, version: Option<String>) -> Self {
+        Self {
+            storage: Arc::new(storage),
+            version: RwLock::new(version),
+            offline: AtomicBool::new(false),
+        }
+    }
+
+    pub fn version(&self) -> Option<String> {
+        self.version.read().unwrap().clone()
+    }
+
+    pub fn set_version(&self, version: Option<String>) {
+        *self.version.write().unwrap() = version;
+    }
+
+    pub fn is_offline(&self) -> bool {
+        self.offline.load(Ordering::Relaxed)
+    }
+
+    pub fn set_offline(&self, offline: bool) {
+        self.offline.store(offline, Ordering::Relaxed);
+    }
+
+    /// Reads the data cached under `key`, failing if it is missing, invalid or stale.
+    pub fn read<D: DeserializeOwned>(&self, key: &str) -> Result<D, CacheError> {
+        let bytes = self.storage.load(key)?;
+        let cached: CacheEntry<D> = match self.storage.encoding() {
+            Encoding::Json => serde_json::from_slice(&bytes)?,
+            #[cfg(feature = "binary")]
+            Encoding::Binary => rmp_serde::from_slice(&bytes)?,
+        };
+        if let Some(expected) = self.version()
+            && cached.version.as_ref() != Some(&expected)
+        {
+            return Err(CacheError::Stale {
+                found: cached.version,
+                expected,
+            });
+        }
+        Ok(cached.data)
+    }
+
+    /// Writes `data` under `key`, stamped with the current version.
+    pub fn write<D: Serialize>(&self, key: &str, data: &D) -> Result<(), CacheError> {
+        let entry = CacheEntry {
+            version: self.version(),
+            data,
+        };
+        let bytes = match self.storage.encoding() {
+            Encoding::Json => serde_json::to_vec_pretty(&entry)?,
+            #[cfg(feature = "binary")]
+            Encoding::Binary => rmp_serde::to_vec_named(&entry)?,
+        };
+        Ok(self.storage.store(key, &bytes)?)
+    }
+}
+
+impl Cache {
+    /// Creates the default cache of the data specific to the account `name`,
+    /// kept apart from the static game data and from the other accounts.
+    pub fn for_account(name: &str) -> Self {
+        Self::default().account(name)
+    }
+
+    /// Creates a cache of the data specific to the account `name`, kept in the
+    /// storage of this cache under `accounts/<name>`, apart from the static
+    /// game data and from the other accounts.
+    pub fn account(&self, name: &str) -> Self {
+        Self {
+            storage: Arc::new(Namespaced::new(
+                self.storage.clone(),
+                format!("{ACCOUNTS_CACHE_DIR}/{name}"),
+            )),
+            version: RwLock::new(None),
+            offline: AtomicBool::new(false),
+        }
+    }
+
+    /// Reads the data cached under `key` if the cache is offline, otherwise
+    /// fetches it and caches it.
+    pub(crate) fn read_or_fetch<D: Serialize + DeserializeOwned>(
+        &self,
+        key: &'static str,
+        fetch: impl FnOnce() -> Result<D, ClientError>,
+    ) -> Result<D, ClientError> {
+        if self.is_offline() {
+            return self
+                .read(key)
+                .map_err(|source| ClientError::NotCached { key, source });
+        }
+        let data = fetch()?;
+        if let Err(e) = self.write(key, &data) {
+            error!("failed to persist `{}`: {}", key, e);
+        }
+        Ok(data)
+    }
+}
+
+impl Default for Cache {
+    fn default() -> Self {
+        Self::new(DEFAULT_CACHE_ROOT, None)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::MemoryStorage;
+
+    #[test]
+    fn stale_cache_is_rejected() {
+        let cache = Cache::with_storage(MemoryStorage::new(), Some("1.0".to_string()));
+        cache.write("data", &vec![1, 2, 3]).unwrap();
+        assert_eq!(cache.read::<Vec<i32>>("data").unwrap(), vec![1, 2, 3]);
+        cache.set_version(Some("2.0".to_string()));
+        assert!(matches!(
+            cache.read::<Vec<i32>>("data"),
+            Err(CacheError::Stale { .. })
+        ));
+    }
+
+    #[test]
+    fn offline_cache_reads_instead_of_fetching() {
+        let cache = Cache::with_storage(MemoryStorage::new(), None);
+        assert_eq!(
+            cache.read_or_fetch("data", || Ok(vec![1, 2])).unwrap(),
+            vec![1, 2]
+        );
+        cache.set_offline(true);
+        assert_eq!(
+            cache
+                .read_or_fetch::<Vec<i32>>("data", || Err(ClientError::Offline))
+                .unwrap(),
+            vec![1, 2]
+        );
+        assert!(matches!(
+            cache.read_or_fetch::<Vec<i32>>("other", || Ok(vec![])),
+            Err(ClientError::NotCached { key: "other", .. })
+        ));
+    }
+
+    /// Writes and reads back an entry through `storage`, with the encoding
+    /// of the backend.
+    fn assert_roundtrip(storage: impl Storage + 'static) {
+        let cache = Cache::with_storage(storage, Some("1.0".to_string()));
+        let data = vec![("copper_ore".to_string(), 3), ("ash_wood".to_string(), 7)];
+        cache.write("items", &data).unwrap();
+        assert_eq!(cache.read::<Vec<(String, i32)>>("items").unwrap(), data);
+        assert!(matches!(
+            cache.read::<Vec<(String, i32)>>("monsters"),
+            Err(CacheError::Storage(StorageError::NotFound(_)))
+        ));
+    }
+
+    fn temp_root(name: &str) -> PathBuf {
+        std::env::temp_dir().join(format!("artifactsmmo-sdk-{name}-{}", std::process::id()))
+    }
+
+    #[test]
+    fn json_files_roundtrip() {
+        let root = temp_root("json");
+        assert_roundtrip(FileStorage::json(&root));
+        assert!(FileStorage::json(&root).path("items").exists());
+        std::fs::remove_dir_all(root).unwrap();
+    }
+
+    #[test]
+    fn account_caches_are_kept_apart_under_the_cache_root() {
+        let root = temp_root("accounts");
+        let cache = Cache::new(&root, Some("1.0".to_string()));
+        let alice = cache.account("alice");
+        alice.write("bank", &vec![1, 2]).unwrap();
+        assert_eq!(alice.read::<Vec<i32>>("bank").unwrap(), vec![1, 2]);
+        assert!(
+            root.join(ACCOUNTS_CACHE_DIR)
+                .join("alice")
+                .join("bank.json")
+                .exists()
+        );
+        assert!(cache.account("bob").read::<Vec<i32>>("bank").is_err());
+        assert!(cache.read::<Vec<i32>>("bank").is_err());
+        std::fs::remove_dir_all(root).unwrap();
+    }
+
+    #[cfg(feature = "binary")]
+    #[test]
+    fn binary_files_roundtrip() {
+        let root = temp_root("binary");
+        assert_roundtrip(FileStorage::binary(&root));
+        assert!(FileStorage::binary(&root).path("items").exists());
+        std::fs::remove_dir_all(root).unwrap();
+    }
+
+    #[cfg(feature = "sqlite")]
+    #[test]
+    fn sqlite_roundtrips_with_every_encoding() {
+        use crate::storage::SqliteStorage;
+
+        assert_roundtrip(SqliteStorage::open_in_memory().unwrap());
+        #[cfg(feature = "binary")]
+        assert_roundtrip(
+            SqliteStorage::open_in_memory()
+                .unwrap()
+                .with_encoding(Encoding::Binary),
+        );
+    }
+}
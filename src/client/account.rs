@@ -1,15 +1,20 @@
 use crate::{
-    ClientError, ItemsClient, MapsClient, MonstersClient, NpcsClient, ResourcesClient,
+    Cache, ClientError, ItemsClient, MapsClient, MonstersClient, NpcsClient, ResourcesClient,
     ServerClient, TasksClient,
     character::HasCharacterData,
-    client::{bank::BankClient, character::CharacterClient},
+    client::{
+        bank::BankClient, character::CharacterClient, retry::RetryPolicy, transport::Transport,
+    },
     grand_exchange::GrandExchangeClient,
 };
 use artifactsmmo_api_wrapper::ArtifactApi;
-use artifactsmmo_openapi::models::AccountAchievementSchema;
+use artifactsmmo_openapi::models::{AccountAchievementSchema, CharacterSchema};
 use itertools::Itertools;
 use std::sync::{Arc, RwLock};
 
+const CHARACTERS_KEY: &str = "characters";
+const ACHIEVEMENTS_KEY: &str = "achievements";
+
 #[derive(Default, Debug)]
 pub struct AccountClient {
     pub name: String,
@@ -17,29 +22,59 @@ pub struct AccountClient {
     characters: RwLock<Vec<Arc<CharacterClient>>>,
     achievements: RwLock<Vec<Arc<AccountAchievementSchema>>>,
     api: Arc<ArtifactApi>,
+    cache: Arc<Cache>,
+    retry_policy: RwLock<RetryPolicy>,
 }
 
 impl AccountClient {
-    pub(crate) fn new(name: String, bank: Arc<BankClient>, api: Arc<ArtifactApi>) -> Self {
+    pub(crate) fn new(
+        name: String,
+        bank: Arc<BankClient>,
+        api: Arc<ArtifactApi>,
+        cache: Arc<Cache>,
+    ) -> Self {
         Self {
             bank,
             characters: Default::default(),
-            achievements: RwLock::new(
-                api.account
-                    .achievements(&name)
-                    .unwrap()
-                    .into_iter()
-                    .map(Arc::new)
-                    .collect_vec(),
-            ),
+            achievements: Default::default(),
             name,
             api,
+            cache,
+            retry_policy: Default::default(),
         }
     }
 
+    /// Cache of the data specific to this account.
+    pub fn cache(&self) -> &Cache {
+        &self.cache
+    }
+
+    /// Returns the policy used to retry the failed actions of the account characters.
+    pub fn retry_policy(&self) -> RetryPolicy {
+        self.retry_policy.read().unwrap().clone()
+    }
+
+    pub fn set_retry_policy(&self, policy: RetryPolicy) {
+        *self.retry_policy.write().unwrap() = policy;
+    }
+
+    /// Fetches the data of the account characters from the API, or reads it
+    /// from the account cache if it is offline.
+    pub(crate) fn fetch_characters(&self) -> Result<Vec<CharacterSchema>, ClientError> {
+        self.cache.read_or_fetch(CHARACTERS_KEY, || {
+            Ok(self
+                .api
+                .account
+                .characters(&self.name)
+                .map_err(ClientError::api)?
+                .data)
+        })
+    }
+
     #[allow(clippy::too_many_arguments)]
     pub(crate) fn load_characters(
         &self,
+        characters: Vec<CharacterSchema>,
         account: Arc<AccountClient>,
         items: Arc<ItemsClient>,
         resources: Arc<ResourcesClient>,
@@ -49,16 +84,13 @@ impl AccountClient {
         tasks: Arc<TasksClient>,
         server: Arc<ServerClient>,
         grand_exchange: Arc<GrandExchangeClient>,
-    ) -> Result<(), ClientError> {
-        *self.characters.write().unwrap() = self
-            .api
-            .account
-            .characters(&account.name)
-            .map_err(|e| ClientError::Api(Box::new(e)))?
-            .data
+        transport: Arc<dyn Transport>,
+    ) {
+        *self.characters.write().unwrap() = characters
             .into_iter()
             .enumerate()
             .map(|(id, data)| {
+                transport.register_character(&data);
                 CharacterClient::new(
                     id,
                     Arc::new(RwLock::new(Arc::new(data))),
@@ -72,19 +104,24 @@ impl AccountClient {
                     grand_exchange.clone(),
                     server.clone(),
                     self.api.clone(),
+                    transport.clone(),
                 )
             })
             .map(Arc::new)
             .collect_vec();
-        Ok(())
     }
 
+    /// Loads the achievements of the account from the API, or from the account
+    /// cache if it is offline.
     pub fn load_achievements(&self) -> Result<(), ClientError> {
         *self.achievements.write().unwrap() = self
-            .api
-            .account
-            .achievements(&self.name)
-            .map_err(|e| ClientError::Api(Box::new(e)))?
+            .cache
+            .read_or_fetch(ACHIEVEMENTS_KEY, || {
+                self.api
+                    .account
+                    .achievements(&self.name)
+                    .map_err(ClientError::api)
+            })?
             .into_iter()
             .map(Arc::new)
             .collect_vec();
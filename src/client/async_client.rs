@@ -0,0 +1,71 @@
+use crate::client::{
+    Client,
+    async_transport::{AsyncTransport, HttpTransport},
+    character::AsyncCharacterClient,
+    error::ClientError,
+};
+use itertools::Itertools;
+use std::{ops::Deref, sync::Arc};
+use tokio::task;
+
+/// Async counterpart of `Client`, enabled by the `async` feature.
+///
+/// The game and account data are loaded once by the blocking `Client`, on
+/// tokio's blocking pool. Characters are then exposed as
+/// `AsyncCharacterClient`s sending their actions through an `AsyncTransport`.
+#[derive(Debug, Clone)]
+pub struct AsyncClient {
+    inner: Arc<Client>,
+    transport: Arc<dyn AsyncTransport>,
+}
+
+impl AsyncClient {
+    /// Loads the client and sends the character actions to the API at `url`
+    /// with an `HttpTransport` sharing the actions budget of the client.
+    pub async fn new(
+        url: String,
+        account_name: String,
+        token: String,
+    ) -> Result<Self, ClientError> {
+        let (base_path, action_token) = (url.clone(), token.clone());
+        let client = task::spawn_blocking(move || Client::new(url, account_name, token))
+            .await
+            .map_err(|_| ClientError::LoaderPanicked("client"))??;
+        let transport =
+            HttpTransport::new(base_path, action_token, client.rate_limits.actions.clone());
+        Ok(Self::with_transport(client, Arc::new(transport)))
+    }
+
+    /// Wraps an already loaded `client`, sending the character actions through
+    /// `transport`.
+    pub fn with_transport(client: Client, transport: Arc<dyn AsyncTransport>) -> Self {
+        Self {
+            inner: Arc::new(client),
+            transport,
+        }
+    }
+
+    pub fn characters(&self) -> Vec<AsyncCharacterClient> {
+        self.inner
+            .account
+            .characters()
+            .into_iter()
+            .map(|c| AsyncCharacterClient::new(c, self.transport.clone()))
+            .collect_vec()
+    }
+
+    pub fn get_character_by_name(&self, name: &str) -> Option<AsyncCharacterClient> {
+        self.inner
+            .account
+            .get_character_by_name(name)
+            .map(|c| AsyncCharacterClient::new(c, self.transport.clone()))
+    }
+}
+
+impl Deref for AsyncClient {
+    type Target = Client;
+
+    fn deref(&self) -> &Self::Target {
+        &self.inner
+    }
+}
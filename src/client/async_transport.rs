@@ -0,0 +1,366 @@
+use crate::client::{
+    character::{
+        ResponseSchema,
+        action::Action,
+        error::{ApiErrorResponseSchema, RequestError},
+    },
+    transport::{MockTransport, OfflineTransport, Transport},
+};
+use artifactsmmo_api_wrapper::TokenBucket;
+use artifactsmmo_openapi::models::{
+    BankExtensionTransactionResponseSchema, BankGoldTransactionResponseSchema,
+    BankItemTransactionResponseSchema, CharacterFightResponseSchema,
+    CharacterMovementResponseSchema, CharacterRestResponseSchema,
+    CharacterTransitionResponseSchema, CraftingSchema, DeleteItemResponseSchema,
+    DepositWithdrawGoldSchema, DestinationSchema, EquipSchema, EquipmentResponseSchema,
+    FightRequestSchema, GeBuyOrderSchema, GeCancelOrderSchema,
+    GeCreateOrderTransactionResponseSchema, GeOrderCreationrSchema, GeTransactionResponseSchema,
+    GiveGoldResponseSchema, GiveGoldSchema, GiveItemResponseSchema, GiveItemsSchema,
+    NpcMerchantBuySchema, NpcMerchantTransactionResponseSchema, RecyclingResponseSchema,
+    RecyclingSchema, RewardDataResponseSchema, SimpleItemSchema, SkillResponseSchema,
+    TaskCancelledResponseSchema, TaskResponseSchema, TaskTradeResponseSchema, UnequipSchema,
+    UseItemResponseSchema,
+};
+use serde::{Serialize, de::DeserializeOwned};
+use serde_json::Value;
+use std::{fmt, future::Future, pin::Pin, sync::Arc};
+use tokio::time;
+
+pub type ResponseFuture<'a> =
+    Pin<Box<dyn Future<Output = Result<Box<dyn ResponseSchema>, RequestError>> + Send + 'a>>;
+
+/// Async backend used by `AsyncCharacterClient`s to send their action requests.
+/// `HttpTransport` is the async reqwest backend; `MockTransport` and
+/// `OfflineTransport` answer immediately from memory.
+pub trait AsyncTransport: Send + Sync + fmt::Debug {
+    fn request<'a>(&'a self, name: &'a str, action: &'a Action<'_>) -> ResponseFuture<'a>;
+}
+
+impl AsyncTransport for MockTransport {
+    fn request<'a>(&'a self, name: &'a str, action: &'a Action<'_>) -> ResponseFuture<'a> {
+        Box::pin(async move { Transport::request(self, name, action) })
+    }
+}
+
+impl AsyncTransport for OfflineTransport {
+    fn request<'a>(&'a self, _name: &'a str, _action: &'a Action<'_>) -> ResponseFuture<'a> {
+        Box::pin(async { Err(RequestError::Offline) })
+    }
+}
+
+/// Sends the character actions to the API with an async `reqwest::Client`,
+/// waiting for the actions rate limit on tokio's timer.
+#[derive(Debug)]
+pub struct HttpTransport {
+    client: reqwest::Client,
+    base_path: String,
+    token: String,
+    limiter: Arc<TokenBucket>,
+}
+
+impl HttpTransport {
+    /// `limiter` should be the `actions` bucket of the `RateLimits` used by
+    /// the blocking client of the same account, so that both share a budget.
+    pub fn new(base_path: String, token: String, limiter: Arc<TokenBucket>) -> Self {
+        Self::with_http_client(base_path, token, limiter, reqwest::Client::new())
+    }
+
+    /// Same as `new` but requests are sent through the given HTTP `client`,
+    /// e.g. one configured with custom timeouts.
+    pub fn with_http_client(
+        base_path: String,
+        token: String,
+        limiter: Arc<TokenBucket>,
+        client: reqwest::Client,
+    ) -> Self {
+        Self {
+            client,
+            base_path,
+            token,
+            limiter,
+        }
+    }
+
+    async fn send(
+        &self,
+        name: &str,
+        action: &Action<'_>,
+    ) -> Result<Box<dyn ResponseSchema>, RequestError> {
+        match action {
+            Action::Move { x, y } => {
+                let body = DestinationSchema {
+                    x: Some(*x),
+                    y: Some(*y),
+                    map_id: None,
+                };
+                self.post::<CharacterMovementResponseSchema>(name, "move", Some(json(&body)?))
+                    .await
+            }
+            Action::Transition => {
+                self.post::<CharacterTransitionResponseSchema>(name, "transition", None)
+                    .await
+            }
+            Action::Fight { participants } => {
+                let body = FightRequestSchema {
+                    participants: participants.map(|p| p.to_vec()),
+                };
+                self.post::<CharacterFightResponseSchema>(name, "fight", Some(json(&body)?))
+                    .await
+            }
+            Action::Rest => {
+                self.post::<CharacterRestResponseSchema>(name, "rest", None)
+                    .await
+            }
+            Action::Gather => {
+                self.post::<SkillResponseSchema>(name, "gathering", None)
+                    .await
+            }
+            Action::Craft {
+                item_code,
+                quantity,
+            } => {
+                let body = CraftingSchema {
+                    code: item_code.to_string(),
+                    quantity: Some(*quantity),
+                };
+                self.post::<SkillResponseSchema>(name, "crafting", Some(json(&body)?))
+                    .await
+            }
+            Action::Recycle {
+                item_code,
+                quantity,
+            } => {
+                let body = RecyclingSchema {
+                    code: item_code.to_string(),
+                    quantity: Some(*quantity),
+                };
+                self.post::<RecyclingResponseSchema>(name, "recycling", Some(json(&body)?))
+                    .await
+            }
+            Action::Delete {
+                item_code,
+                quantity,
+            } => {
+                let body = SimpleItemSchema::new(item_code.to_string(), *quantity);
+                self.post::<DeleteItemResponseSchema>(name, "delete", Some(json(&body)?))
+                    .await
+            }
+            Action::DepositItem { items } => {
+                self.post::<BankItemTransactionResponseSchema>(
+                    name,
+                    "bank/deposit/item",
+                    Some(json(items)?),
+                )
+                .await
+            }
+            Action::WithdrawItem { items } => {
+                self.post::<BankItemTransactionResponseSchema>(
+                    name,
+                    "bank/withdraw/item",
+                    Some(json(items)?),
+                )
+                .await
+            }
+            Action::DepositGold { quantity } => {
+                let body = DepositWithdrawGoldSchema {
+                    quantity: *quantity,
+                };
+                self.post::<BankGoldTransactionResponseSchema>(
+                    name,
+                    "bank/deposit/gold",
+                    Some(json(&body)?),
+                )
+                .await
+            }
+            Action::WithdrawGold { quantity } => {
+                let body = DepositWithdrawGoldSchema {
+                    quantity: *quantity,
+                };
+                self.post::<BankGoldTransactionResponseSchema>(
+                    name,
+                    "bank/withdraw/gold",
+                    Some(json(&body)?),
+                )
+                .await
+            }
+            Action::ExpandBank => {
+                self.post::<BankExtensionTransactionResponseSchema>(
+                    name,
+                    "bank/buy_expansion",
+                    None,
+                )
+                .await
+            }
+            Action::Equip {
+                item_code,
+                slot,
+                quantity,
+            } => {
+                let mut body = EquipSchema::new(item_code.to_string(), (*slot).into());
+                body.quantity = Some(*quantity);
+                self.post::<EquipmentResponseSchema>(name, "equip", Some(json(&body)?))
+                    .await
+            }
+            Action::Unequip { slot, quantity } => {
+                let mut body = UnequipSchema::new((*slot).into());
+                body.quantity = Some(*quantity);
+                self.post::<EquipmentResponseSchema>(name, "unequip", Some(json(&body)?))
+                    .await
+            }
+            Action::UseItem {
+                item_code,
+                quantity,
+            } => {
+                let body = SimpleItemSchema::new(item_code.to_string(), *quantity);
+                self.post::<UseItemResponseSchema>(name, "use", Some(json(&body)?))
+                    .await
+            }
+            Action::AcceptTask => {
+                self.post::<TaskResponseSchema>(name, "task/new", None)
+                    .await
+            }
+            Action::CancelTask => {
+                self.post::<TaskCancelledResponseSchema>(name, "task/cancel", None)
+                    .await
+            }
+            Action::TradeTaskItem {
+                item_code,
+                quantity,
+            } => {
+                let body = SimpleItemSchema::new(item_code.to_string(), *quantity);
+                self.post::<TaskTradeResponseSchema>(name, "task/trade", Some(json(&body)?))
+                    .await
+            }
+            Action::CompleteTask => {
+                self.post::<RewardDataResponseSchema>(name, "task/complete", None)
+                    .await
+            }
+            Action::ExchangeTasksCoins => {
+                self.post::<RewardDataResponseSchema>(name, "task/exchange", None)
+                    .await
+            }
+            Action::NpcBuy {
+                item_code,
+                quantity,
+            } => {
+                let body = NpcMerchantBuySchema::new(item_code.to_string(), *quantity);
+                self.post::<NpcMerchantTransactionResponseSchema>(
+                    name,
+                    "npc/buy",
+                    Some(json(&body)?),
+                )
+                .await
+            }
+            Action::NpcSell {
+                item_code,
+                quantity,
+            } => {
+                let body = NpcMerchantBuySchema::new(item_code.to_string(), *quantity);
+                self.post::<NpcMerchantTransactionResponseSchema>(
+                    name,
+                    "npc/sell",
+                    Some(json(&body)?),
+                )
+                .await
+            }
+            Action::GiveItem { items, character } => {
+                let body = GiveItemsSchema {
+                    items: items.to_vec(),
+                    character: character.to_string(),
+                };
+                self.post::<GiveItemResponseSchema>(name, "give/item", Some(json(&body)?))
+                    .await
+            }
+            Action::GiveGold {
+                quantity,
+                character,
+            } => {
+                let body = GiveGoldSchema {
+                    quantity: *quantity,
+                    character: character.to_string(),
+                };
+                self.post::<GiveGoldResponseSchema>(name, "give/gold", Some(json(&body)?))
+                    .await
+            }
+            Action::GeBuyOrder { id, quantity } => {
+                let body = GeBuyOrderSchema::new(id.to_string(), *quantity);
+                self.post::<GeTransactionResponseSchema>(
+                    name,
+                    "grandexchange/buy",
+                    Some(json(&body)?),
+                )
+                .await
+            }
+            Action::GeCreateOrder {
+                item_code,
+                quantity,
+                price,
+            } => {
+                let body = GeOrderCreationrSchema::new(item_code.to_string(), *quantity, *price);
+                self.post::<GeCreateOrderTransactionResponseSchema>(
+                    name,
+                    "grandexchange/sell",
+                    Some(json(&body)?),
+                )
+                .await
+            }
+            Action::GeCancelOrder { id } => {
+                let body = GeCancelOrderSchema::new(id.to_string());
+                self.post::<GeTransactionResponseSchema>(
+                    name,
+                    "grandexchange/cancel",
+                    Some(json(&body)?),
+                )
+                .await
+            }
+        }
+    }
+
+    /// POSTs `body` to the `path` action endpoint of character `name` and
+    /// decodes the response as `R`.
+    async fn post<R>(
+        &self,
+        name: &str,
+        path: &str,
+        body: Option<Value>,
+    ) -> Result<Box<dyn ResponseSchema>, RequestError>
+    where
+        R: ResponseSchema + DeserializeOwned,
+    {
+        let wait = self.limiter.reserve_delay();
+        if !wait.is_zero() {
+            time::sleep(wait).await;
+        }
+        let mut req = self
+            .client
+            .post(format!("{}/my/{name}/action/{path}", self.base_path))
+            .bearer_auth(&self.token);
+        if let Some(body) = body {
+            req = req.json(&body);
+        }
+        let res = req.send().await.map_err(RequestError::Reqwest)?;
+        let status = res.status();
+        let content = res.bytes().await.map_err(RequestError::Reqwest)?;
+        if !status.is_success() {
+            return Err(
+                match serde_json::from_slice::<ApiErrorResponseSchema>(&content) {
+                    Ok(e) => RequestError::ResponseError(e),
+                    Err(e) => RequestError::Serde(e),
+                },
+            );
+        }
+        serde_json::from_slice::<R>(&content)
+            .map(|r| Box::new(r) as Box<dyn ResponseSchema>)
+            .map_err(RequestError::Serde)
+    }
+}
+
+impl AsyncTransport for HttpTransport {
+    fn request<'a>(&'a self, name: &'a str, action: &'a Action<'_>) -> ResponseFuture<'a> {
+        Box::pin(self.send(name, action))
+    }
+}
+
+fn json<T: Serialize + ?Sized>(body: &T) -> Result<Value, RequestError> {
+    serde_json::to_value(body).map_err(RequestError::Serde)
+}
@@ -0,0 +1,336 @@
+use crate::{
+    cache::Cache,
+    client::{
+        Client,
+        account::AccountClient,
+        bank::BankClient,
+        error::ClientError,
+        game_data::GameData,
+        grand_exchange::GrandExchangeClient,
+        retry::RetryPolicy,
+        transport::{OfflineTransport, Transport},
+    },
+    clock::{Clock, RealClock},
+    storage::Storage,
+};
+use artifactsmmo_api_wrapper::{ArtifactApi, RateLimits};
+use std::{path::PathBuf, sync::Arc, time::Duration};
+
+const BANK_KEY: &str = "bank";
+
+/// Builder of a `Client`.
+///
+/// By default the static game data is cached under `.cache`, the bank, the
+/// characters and the achievements of the account are loaded and cached under
+/// `accounts/<account name>` in the same storage and character actions are sent
+/// through the HTTP API.
+///
+/// `load_bank`, `load_characters` and `load_achievements` only cover the
+/// account data: the events are part of the static game data and the Grand
+/// Exchange client is always available, fetching its orders on demand.
+#[derive(Debug)]
+pub struct ClientBuilder {
+    url: String,
+    account_name: String,
+    token: String,
+    cache: Option<Cache>,
+    account_cache: Option<Cache>,
+    game_data: Option<Arc<GameData>>,
+    clock: Option<Arc<dyn Clock>>,
+    timeout: Option<Duration>,
+    connect_timeout: Option<Duration>,
+    retry_policy: RetryPolicy,
+    rate_limits: RateLimits,
+    transport: Option<Arc<dyn Transport>>,
+    load_bank: bool,
+    load_characters: bool,
+    load_achievements: bool,
+    offline: bool,
+}
+
+impl ClientBuilder {
+    pub fn new(url: String, account_name: String, token: String) -> Self {
+        Self {
+            url,
+            account_name,
+            token,
+            cache: None,
+            account_cache: None,
+            game_data: None,
+            clock: None,
+            timeout: None,
+            connect_timeout: None,
+            retry_policy: RetryPolicy::default(),
+            rate_limits: RateLimits::default(),
+            transport: None,
+            load_bank: true,
+            load_characters: true,
+            load_achievements: true,
+            offline: false,
+        }
+    }
+
+    /// Caches the static game data as JSON files under `root`, and the account
+    /// data under `root/accounts/<account name>`.
+    pub fn cache_root(mut self, root: impl Into<PathBuf>) -> Self {
+        self.cache = Some(Cache::new(root, None));
+        self
+    }
+
+    /// Caches the static game data in the given `storage` backend, and the
+    /// account data under the `accounts/<account name>` keys of it.
+    pub fn storage(mut self, storage: impl Storage + 'static) -> Self {
+        self.cache = Some(Cache::with_storage(storage, None));
+        self
+    }
+
+    /// Caches the bank, the characters and the achievements of the account
+    /// in `cache` instead of under `accounts/<account name>` in the storage of
+    /// the static game data. It must not be shared with other accounts.
+    pub fn account_cache(mut self, cache: Cache) -> Self {
+        self.account_cache = Some(cache);
+        self
+    }
+
+    /// Uses already loaded static game data, e.g. shared with other clients,
+    /// instead of loading it. `cache_root` and `storage` are then ignored and
+    /// the account data is cached in the storage of `game_data`.
+    pub fn game_data(mut self, game_data: Arc<GameData>) -> Self {
+        self.game_data = Some(game_data);
+        self
+    }
+
+    /// Clock used by the cooldown and event expiry logic, `RealClock` by
+    /// default. It is also set on the shared `game_data` if one is given.
+    pub fn clock(mut self, clock: Arc<dyn Clock>) -> Self {
+        self.clock = Some(clock);
+        self
+    }
+
+    /// Total timeout of the HTTP requests.
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Timeout of the connection phase of the HTTP requests.
+    pub fn connect_timeout(mut self, timeout: Duration) -> Self {
+        self.connect_timeout = Some(timeout);
+        self
+    }
+
+    pub fn retry_policy(mut self, policy: RetryPolicy) -> Self {
+        self.retry_policy = policy;
+        self
+    }
+
+    /// Request budgets shared by the characters of the account. Clones of the
+    /// same `RateLimits` can be given to several builders to share them.
+    pub fn rate_limits(mut self, rate_limits: RateLimits) -> Self {
+        self.rate_limits = rate_limits;
+        self
+    }
+
+    /// Sends character actions through `transport` instead of the HTTP API.
+    pub fn transport(mut self, transport: Arc<dyn Transport>) -> Self {
+        self.transport = Some(transport);
+        self
+    }
+
+    pub fn load_bank(mut self, load: bool) -> Self {
+        self.load_bank = load;
+        self
+    }
+
+    pub fn load_characters(mut self, load: bool) -> Self {
+        self.load_characters = load;
+        self
+    }
+
+    pub fn load_achievements(mut self, load: bool) -> Self {
+        self.load_achievements = load;
+        self
+    }
+
+    /// Loads the static game data and the account data from the caches only,
+    /// failing if they are not cached. Character actions fail with
+    /// `RequestError::Offline`, unless a `transport` is given.
+    pub fn offline(mut self, offline: bool) -> Self {
+        self.offline = offline;
+        self
+    }
+
+    pub fn build(self) -> Result<Client, ClientError> {
+        let mut http = reqwest::blocking::Client::builder();
+        if let Some(timeout) = self.timeout {
+            http = http.timeout(timeout);
+        }
+        if let Some(timeout) = self.connect_timeout {
+            http = http.connect_timeout(timeout);
+        }
+        let api = Arc::new(ArtifactApi::with_http_client(
+            self.url,
+            Some(self.token),
+            http.build()?,
+            self.rate_limits.clone(),
+        ));
+        let transport = self.transport.unwrap_or_else(|| {
+            if self.offline {
+                Arc::new(OfflineTransport)
+            } else {
+                api.clone() as Arc<dyn Transport>
+            }
+        });
+        let game_data = match self.game_data {
+            Some(game_data) => game_data,
+            None => {
+                let cache = self.cache.unwrap_or_default();
+                cache.set_offline(self.offline);
+                let clock = self.clock.clone().unwrap_or_else(|| Arc::new(RealClock));
+                Arc::new(GameData::load(api.clone(), cache, clock)?)
+            }
+        };
+        if let Some(clock) = self.clock {
+            game_data.set_clock(clock);
+        }
+        let account_cache = self
+            .account_cache
+            .unwrap_or_else(|| game_data.cache.account(&self.account_name));
+        account_cache.set_offline(self.offline);
+        let bank = if self.load_bank {
+            let (details, items) = account_cache.read_or_fetch(BANK_KEY, || {
+                let details = api.bank.get_details().map_err(ClientError::api)?;
+                let items = api.bank.get_items().map_err(ClientError::api)?;
+                Ok((*details.data, items))
+            })?;
+            Arc::new(BankClient::new(details, items))
+        } else {
+            Arc::new(BankClient::default())
+        };
+
+        let account = Arc::new(AccountClient::new(
+            self.account_name,
+            bank,
+            api.clone(),
+            Arc::new(account_cache),
+        ));
+        account.set_retry_policy(self.retry_policy);
+        if self.load_achievements {
+            account.load_achievements()?;
+        }
+        let grand_exchange = Arc::new(GrandExchangeClient::new(api.clone()));
+        if self.load_characters {
+            account.load_characters(
+                account.fetch_characters()?,
+                account.clone(),
+                game_data.items.clone(),
+                game_data.resources.clone(),
+                game_data.monsters.clone(),
+                game_data.maps.clone(),
+                game_data.npcs.clone(),
+                game_data.tasks.clone(),
+                game_data.server.clone(),
+                grand_exchange.clone(),
+                transport,
+            );
+        }
+
+        Ok(Client {
+            account,
+            server: game_data.server.clone(),
+            events: game_data.events.clone(),
+            resources: game_data.resources.clone(),
+            monsters: game_data.monsters.clone(),
+            items: game_data.items.clone(),
+            tasks: game_data.tasks.clone(),
+            maps: game_data.maps.clone(),
+            npcs: game_data.npcs.clone(),
+            grand_exchange,
+            cache: game_data.cache.clone(),
+            game_data,
+            rate_limits: self.rate_limits,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{client::transport::MockTransport, storage::MemoryStorage};
+    use artifactsmmo_openapi::models::{
+        AccountAchievementSchema, BankSchema, CharacterRestResponseSchema, CharacterSchema,
+        SimpleItemSchema,
+    };
+    use std::collections::HashMap;
+
+    const URL: &str = "https://api.artifactsmmo.com";
+
+    fn builder() -> ClientBuilder {
+        ClientBuilder::new(URL.to_string(), "account".to_string(), "token".to_string())
+    }
+
+    #[test]
+    fn offline_build_fails_when_not_cached() {
+        assert!(matches!(
+            builder()
+                .storage(MemoryStorage::new())
+                .offline(true)
+                .build(),
+            Err(ClientError::NotCached { .. })
+        ));
+    }
+
+    #[test]
+    fn offline_build_sends_actions_through_the_transport() {
+        let root =
+            std::env::temp_dir().join(format!("artifactsmmo-sdk-builder-{}", std::process::id()));
+        let cache = Cache::new(&root, None);
+        cache.write("maps", &Vec::<()>::new()).unwrap();
+        for key in [
+            "events",
+            "tasks",
+            "tasks_rewards",
+            "npcs",
+            "npcs_items",
+            "resources",
+            "monsters",
+            "items",
+        ] {
+            cache.write(key, &HashMap::<String, ()>::new()).unwrap();
+        }
+        let account = cache.account("account");
+        account
+            .write(
+                BANK_KEY,
+                &(BankSchema::default(), Vec::<SimpleItemSchema>::new()),
+            )
+            .unwrap();
+        account
+            .write("achievements", &Vec::<AccountAchievementSchema>::new())
+            .unwrap();
+        let data = CharacterSchema {
+            name: "char".to_string(),
+            hp: 50,
+            max_hp: 100,
+            ..Default::default()
+        };
+        account.write("characters", &vec![data.clone()]).unwrap();
+
+        let transport = Arc::new(MockTransport::new());
+        let mut res = CharacterRestResponseSchema::default();
+        res.data.hp_restored = 50;
+        res.data.character = CharacterSchema { hp: 100, ..data }.into();
+        transport.push(res);
+        let client = builder()
+            .cache_root(&root)
+            .offline(true)
+            .transport(transport.clone())
+            .build()
+            .unwrap();
+        let char = client.account.get_character_by_name("char").unwrap();
+        assert_eq!(char.rest().unwrap(), 50);
+        assert_eq!(char.health(), 100);
+        assert_eq!(transport.requests(), vec!["char: Rest"]);
+        std::fs::remove_dir_all(root).unwrap();
+    }
+}
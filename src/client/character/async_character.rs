@@ -0,0 +1,555 @@
+use crate::{
+    client::{
+        async_transport::AsyncTransport,
+        character::{
+            CharacterClient, HasCharacterData, ResponseSchema,
+            action::Action,
+            error::{
+                BankExpansionError, BuyNpcError, CraftError, DeleteError, DepositError, EquipError,
+                FightError, GatherError, GeBuyOrderError, GeCancelOrderError, GeCreateOrderError,
+                GiveGoldError, GiveItemError, GoldDepositError, GoldWithdrawError, MoveError,
+                RecycleError, RequestError, RestError, SellNpcError, TaskAcceptationError,
+                TaskCancellationError, TaskCompletionError, TaskTradeError, TasksCoinExchangeError,
+                TransitionError, UnequipError, UseError, WithdrawError,
+            },
+            request_handler::Recovery,
+        },
+        retry::RetryRule,
+    },
+    entities::Map,
+    gear::Slot,
+};
+use artifactsmmo_openapi::models::{
+    BankExtensionTransactionResponseSchema, BankGoldTransactionResponseSchema,
+    BankItemTransactionResponseSchema, CharacterFightResponseSchema, CharacterFightSchema,
+    CharacterMovementResponseSchema, CharacterRestResponseSchema,
+    CharacterTransitionResponseSchema, DeleteItemResponseSchema, EquipmentResponseSchema,
+    GeCreateOrderTransactionResponseSchema, GeTransactionResponseSchema, GeTransactionSchema,
+    GiveGoldResponseSchema, GiveItemResponseSchema, MapSchema, NpcItemTransactionSchema,
+    NpcMerchantTransactionResponseSchema, RecyclingItemsSchema, RecyclingResponseSchema,
+    RewardDataResponseSchema, RewardsSchema, SimpleItemSchema, SkillDataSchema, SkillInfoSchema,
+    SkillResponseSchema, TaskCancelledResponseSchema, TaskResponseSchema, TaskSchema,
+    TaskTradeResponseSchema, TaskTradeSchema, UseItemResponseSchema,
+};
+use log::warn;
+use std::{ops::Deref, sync::Arc};
+use tokio::task;
+
+/// Async counterpart of `CharacterClient`, enabled by the `async` feature.
+///
+/// Actions are validated with the same `can_*` checks and fail with the same
+/// error enums as the blocking client, but are sent through an `AsyncTransport`.
+/// Cooldowns and retry delays are awaited on the character clock and rate limits
+/// on tokio's timer, so a character never holds an OS thread while waiting.
+#[derive(Debug, Clone)]
+pub struct AsyncCharacterClient {
+    inner: Arc<CharacterClient>,
+    transport: Arc<dyn AsyncTransport>,
+}
+
+impl AsyncCharacterClient {
+    pub fn new(inner: Arc<CharacterClient>, transport: Arc<dyn AsyncTransport>) -> Self {
+        Self { inner, transport }
+    }
+
+    pub async fn wait_for_cooldown(&self) {
+        let remaining = self.inner.remaining_cooldown();
+        if !remaining.is_zero() {
+            self.inner.clock().sleep_async(remaining).await;
+        }
+    }
+
+    /// Sends `action` once the cooldown is over, retrying it according to the
+    /// account `RetryPolicy`, and downcasts the response to `R`.
+    async fn request<R: ResponseSchema>(&self, action: Action<'_>) -> Result<Box<R>, RequestError> {
+        let policy = self.inner.account().retry_policy();
+        let mut attempt = 1;
+        let res = loop {
+            self.wait_for_cooldown().await;
+            let error = match self.transport.request(&self.name(), &action).await {
+                Ok(res) => break res,
+                Err(e) => e,
+            };
+            self.handle_request_error(&action, &error).await;
+            let rule = policy.rule_for(&error);
+            if rule == RetryRule::Never {
+                return Err(error);
+            }
+            if attempt >= policy.attempts() {
+                return Err(RequestError::RetriesExhausted {
+                    attempts: attempt,
+                    last: Box::new(error),
+                });
+            }
+            if rule == RetryRule::Backoff {
+                let delay = policy.jittered_delay_for(attempt);
+                warn!(
+                    "{}: retrying in {}.{}s (attempt {}/{})",
+                    self.name(),
+                    delay.as_secs(),
+                    delay.subsec_millis(),
+                    attempt + 1,
+                    policy.attempts()
+                );
+                self.inner.clock().sleep_async(delay).await;
+            }
+            attempt += 1;
+        };
+        // The bank is only locked once the response is received: the guards
+        // cannot be held across an await point.
+        let (bank_content, bank_details) = self.inner.inner.bank_guards(&action);
+        self.inner
+            .inner
+            .apply_response(res.as_ref(), bank_content, bank_details);
+        res.downcast::<R>().map_err(|_| RequestError::DowncastError)
+    }
+
+    async fn handle_request_error(&self, action: &Action<'_>, error: &RequestError) {
+        self.inner.inner.log_request_error(action, error);
+        if let Some(recovery) = Recovery::for_error(error) {
+            let inner = self.inner.clone();
+            let _ = task::spawn_blocking(move || inner.inner.recover(recovery)).await;
+        }
+    }
+
+    pub async fn r#move(&self, x: i32, y: i32) -> Result<Map, MoveError> {
+        self.inner.can_move(x, y)?;
+        let res = self
+            .request::<CharacterMovementResponseSchema>(Action::Move { x, y })
+            .await?;
+        Ok(Map::new(*res.data.destination))
+    }
+
+    pub async fn transition(&self) -> Result<Arc<MapSchema>, TransitionError> {
+        self.inner.can_transition()?;
+        let res = self
+            .request::<CharacterTransitionResponseSchema>(Action::Transition)
+            .await?;
+        Ok(Arc::new(*res.data.destination))
+    }
+
+    pub async fn fight(
+        &self,
+        participants: Option<&[String; 2]>,
+    ) -> Result<CharacterFightSchema, FightError> {
+        self.inner.can_fight(participants)?;
+        let res = self
+            .request::<CharacterFightResponseSchema>(Action::Fight { participants })
+            .await?;
+        Ok(*res.data.fight)
+    }
+
+    pub async fn gather(&self) -> Result<SkillDataSchema, GatherError> {
+        self.inner.can_gather()?;
+        let res = self.request::<SkillResponseSchema>(Action::Gather).await?;
+        Ok(*res.data)
+    }
+
+    pub async fn rest(&self) -> Result<u32, RestError> {
+        if self.health() >= self.max_health() {
+            return Ok(0);
+        }
+        let res = self
+            .request::<CharacterRestResponseSchema>(Action::Rest)
+            .await?;
+        Ok(res.data.hp_restored as u32)
+    }
+
+    pub async fn craft(
+        &self,
+        item_code: &str,
+        quantity: u32,
+    ) -> Result<SkillInfoSchema, CraftError> {
+        self.inner.can_craft(item_code, quantity)?;
+        let res = self
+            .request::<SkillResponseSchema>(Action::Craft {
+                item_code,
+                quantity,
+            })
+            .await?;
+        Ok(*res.data.details)
+    }
+
+    pub async fn recycle(
+        &self,
+        item_code: &str,
+        quantity: u32,
+    ) -> Result<RecyclingItemsSchema, RecycleError> {
+        self.inner.can_recycle(item_code, quantity)?;
+        let res = self
+            .request::<RecyclingResponseSchema>(Action::Recycle {
+                item_code,
+                quantity,
+            })
+            .await?;
+        Ok(*res.data.details)
+    }
+
+    pub async fn delete(
+        &self,
+        item_code: &str,
+        quantity: u32,
+    ) -> Result<SimpleItemSchema, DeleteError> {
+        self.inner.can_delete(item_code, quantity)?;
+        let res = self
+            .request::<DeleteItemResponseSchema>(Action::Delete {
+                item_code,
+                quantity,
+            })
+            .await?;
+        Ok(*res.data.item)
+    }
+
+    pub async fn deposit_item(&self, items: &[SimpleItemSchema]) -> Result<(), DepositError> {
+        self.inner.can_deposit_items(items)?;
+        self.request::<BankItemTransactionResponseSchema>(Action::DepositItem { items })
+            .await?;
+        Ok(())
+    }
+
+    pub async fn withdraw_item(&self, items: &[SimpleItemSchema]) -> Result<(), WithdrawError> {
+        self.inner.can_withdraw_items(items)?;
+        self.request::<BankItemTransactionResponseSchema>(Action::WithdrawItem { items })
+            .await?;
+        Ok(())
+    }
+
+    pub async fn deposit_gold(&self, quantity: u32) -> Result<u32, GoldDepositError> {
+        self.inner.can_deposit_gold(quantity)?;
+        let res = self
+            .request::<BankGoldTransactionResponseSchema>(Action::DepositGold { quantity })
+            .await?;
+        Ok(res.data.bank.quantity)
+    }
+
+    pub async fn withdraw_gold(&self, quantity: u32) -> Result<u32, GoldWithdrawError> {
+        self.inner.can_withdraw_gold(quantity)?;
+        let res = self
+            .request::<BankGoldTransactionResponseSchema>(Action::WithdrawGold { quantity })
+            .await?;
+        Ok(res.data.bank.quantity)
+    }
+
+    pub async fn expand_bank(&self) -> Result<u32, BankExpansionError> {
+        self.inner.can_expand_bank()?;
+        let res = self
+            .request::<BankExtensionTransactionResponseSchema>(Action::ExpandBank)
+            .await?;
+        Ok(res.data.transaction.price)
+    }
+
+    pub async fn equip(
+        &self,
+        item_code: &str,
+        slot: Slot,
+        quantity: u32,
+    ) -> Result<(), EquipError> {
+        self.inner.can_equip(item_code, slot, quantity)?;
+        self.request::<EquipmentResponseSchema>(Action::Equip {
+            item_code,
+            slot,
+            quantity,
+        })
+        .await?;
+        Ok(())
+    }
+
+    pub async fn unequip(&self, slot: Slot, quantity: u32) -> Result<(), UnequipError> {
+        self.inner.can_unequip(slot, quantity)?;
+        self.request::<EquipmentResponseSchema>(Action::Unequip { slot, quantity })
+            .await?;
+        Ok(())
+    }
+
+    pub async fn use_item(&self, item_code: &str, quantity: u32) -> Result<(), UseError> {
+        self.inner.can_use_item(item_code, quantity)?;
+        self.request::<UseItemResponseSchema>(Action::UseItem {
+            item_code,
+            quantity,
+        })
+        .await?;
+        Ok(())
+    }
+
+    pub async fn accept_task(&self) -> Result<TaskSchema, TaskAcceptationError> {
+        self.inner.can_accept_task()?;
+        let res = self
+            .request::<TaskResponseSchema>(Action::AcceptTask)
+            .await?;
+        Ok(*res.data.task)
+    }
+
+    pub async fn cancel_task(&self) -> Result<(), TaskCancellationError> {
+        self.inner.can_cancel_task()?;
+        self.request::<TaskCancelledResponseSchema>(Action::CancelTask)
+            .await?;
+        Ok(())
+    }
+
+    pub async fn trade_task_item(
+        &self,
+        item_code: &str,
+        quantity: u32,
+    ) -> Result<TaskTradeSchema, TaskTradeError> {
+        self.inner.can_trade_task_item(item_code, quantity)?;
+        let res = self
+            .request::<TaskTradeResponseSchema>(Action::TradeTaskItem {
+                item_code,
+                quantity,
+            })
+            .await?;
+        Ok(*res.data.trade)
+    }
+
+    pub async fn complete_task(&self) -> Result<RewardsSchema, TaskCompletionError> {
+        self.inner.can_complete_task()?;
+        let res = self
+            .request::<RewardDataResponseSchema>(Action::CompleteTask)
+            .await?;
+        Ok(*res.data.rewards)
+    }
+
+    pub async fn exchange_tasks_coins(&self) -> Result<RewardsSchema, TasksCoinExchangeError> {
+        self.inner.can_exchange_tasks_coins()?;
+        let res = self
+            .request::<RewardDataResponseSchema>(Action::ExchangeTasksCoins)
+            .await?;
+        Ok(*res.data.rewards)
+    }
+
+    pub async fn npc_buy(
+        &self,
+        item_code: &str,
+        quantity: u32,
+    ) -> Result<NpcItemTransactionSchema, BuyNpcError> {
+        self.inner.can_npc_buy(item_code, quantity)?;
+        let res = self
+            .request::<NpcMerchantTransactionResponseSchema>(Action::NpcBuy {
+                item_code,
+                quantity,
+            })
+            .await?;
+        Ok(*res.data.transaction)
+    }
+
+    pub async fn npc_sell(
+        &self,
+        item_code: &str,
+        quantity: u32,
+    ) -> Result<NpcItemTransactionSchema, SellNpcError> {
+        self.inner.can_npc_sell(item_code, quantity)?;
+        let res = self
+            .request::<NpcMerchantTransactionResponseSchema>(Action::NpcSell {
+                item_code,
+                quantity,
+            })
+            .await?;
+        Ok(*res.data.transaction)
+    }
+
+    pub async fn give_item(
+        &self,
+        items: &[SimpleItemSchema],
+        character: &str,
+    ) -> Result<(), GiveItemError> {
+        self.inner.can_give_item(items, character)?;
+        self.request::<GiveItemResponseSchema>(Action::GiveItem { items, character })
+            .await?;
+        Ok(())
+    }
+
+    pub async fn give_gold(&self, quantity: u32, character: &str) -> Result<(), GiveGoldError> {
+        self.inner.can_give_gold(quantity, character)?;
+        self.request::<GiveGoldResponseSchema>(Action::GiveGold {
+            quantity,
+            character,
+        })
+        .await?;
+        Ok(())
+    }
+
+    pub async fn ge_buy_order(
+        &self,
+        id: &str,
+        quantity: u32,
+    ) -> Result<GeTransactionSchema, GeBuyOrderError> {
+        self.inner.can_ge_buy_order(id, quantity)?;
+        let res = self
+            .request::<GeTransactionResponseSchema>(Action::GeBuyOrder { id, quantity })
+            .await?;
+        Ok(*res.data.order)
+    }
+
+    pub async fn ge_create_order(
+        &self,
+        item_code: &str,
+        quantity: u32,
+        price: u32,
+    ) -> Result<(), GeCreateOrderError> {
+        self.inner.can_ge_create_order(item_code, quantity, price)?;
+        self.request::<GeCreateOrderTransactionResponseSchema>(Action::GeCreateOrder {
+            item_code,
+            quantity,
+            price,
+        })
+        .await?;
+        Ok(())
+    }
+
+    pub async fn ge_cancel_order(
+        &self,
+        id: &str,
+    ) -> Result<GeTransactionSchema, GeCancelOrderError> {
+        self.inner.can_ge_cancel_order(id)?;
+        let res = self
+            .request::<GeTransactionResponseSchema>(Action::GeCancelOrder { id })
+            .await?;
+        Ok(*res.data.order)
+    }
+}
+
+impl Deref for AsyncCharacterClient {
+    type Target = CharacterClient;
+
+    fn deref(&self) -> &Self::Target {
+        &self.inner
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        AccountClient,
+        client::{
+            character::{
+                error::{ApiErrorResponseSchema, ApiErrorSchema},
+                request_handler::CharacterRequestHandler,
+            },
+            retry::RetryPolicy,
+            server::ServerClient,
+            transport::MockTransport,
+        },
+        clock::{Clock, VirtualClock},
+    };
+    use artifactsmmo_openapi::models::CharacterSchema;
+    use chrono::{DateTime, TimeDelta};
+    use std::sync::RwLock;
+
+    fn character(transport: Arc<MockTransport>, data: CharacterSchema) -> AsyncCharacterClient {
+        character_on(transport, Arc::default(), data)
+    }
+
+    fn character_on(
+        transport: Arc<MockTransport>,
+        clock: Arc<VirtualClock>,
+        data: CharacterSchema,
+    ) -> AsyncCharacterClient {
+        let account = Arc::new(AccountClient::default());
+        account.set_retry_policy(RetryPolicy::none());
+        let inner = CharacterClient {
+            inner: CharacterRequestHandler::new(
+                Arc::default(),
+                transport.clone(),
+                Arc::new(RwLock::new(Arc::new(data))),
+                account.clone(),
+                Arc::new(ServerClient::new(Arc::default(), clock)),
+            ),
+            account,
+            ..Default::default()
+        };
+        AsyncCharacterClient::new(Arc::new(inner), transport)
+    }
+
+    #[tokio::test]
+    async fn actions_go_through_the_async_transport() {
+        let transport = Arc::new(MockTransport::new());
+        let rested = CharacterSchema {
+            name: "char".to_string(),
+            hp: 100,
+            max_hp: 100,
+            ..Default::default()
+        };
+        let mut res = CharacterRestResponseSchema::default();
+        res.data.hp_restored = 40;
+        res.data.character = rested.into();
+        transport.push(res);
+        let char = character(
+            transport.clone(),
+            CharacterSchema {
+                name: "char".to_string(),
+                hp: 60,
+                max_hp: 100,
+                ..Default::default()
+            },
+        );
+
+        assert_eq!(char.rest().await.unwrap(), 40);
+        assert_eq!(char.health(), 100);
+        assert_eq!(char.rest().await.unwrap(), 0);
+        assert_eq!(transport.requests(), vec!["char: Rest"]);
+    }
+
+    #[tokio::test]
+    async fn response_errors_are_mapped_to_the_action_error() {
+        let transport = Arc::new(MockTransport::new());
+        transport.push_error(RequestError::ResponseError(ApiErrorResponseSchema {
+            error: ApiErrorSchema {
+                code: 483,
+                message: "insufficient health".to_string(),
+            },
+        }));
+        let char = character(
+            transport,
+            CharacterSchema {
+                name: "char".to_string(),
+                hp: 1,
+                max_hp: 100,
+                ..Default::default()
+            },
+        );
+
+        assert!(matches!(
+            char.rest().await,
+            Err(RestError::UnhandledError(RequestError::ResponseError(_)))
+        ));
+    }
+
+    #[tokio::test]
+    async fn cooldowns_and_backoffs_are_awaited_on_the_character_clock() {
+        let start = DateTime::from_timestamp(0, 0).unwrap();
+        let clock = Arc::new(VirtualClock::new(start));
+        let transport = Arc::new(MockTransport::new());
+        transport.push_error(RequestError::ResponseError(ApiErrorResponseSchema {
+            error: ApiErrorSchema {
+                code: 500,
+                message: String::new(),
+            },
+        }));
+        let mut res = CharacterRestResponseSchema::default();
+        res.data.character = CharacterSchema {
+            name: "char".to_string(),
+            ..Default::default()
+        }
+        .into();
+        transport.push(res);
+        let char = character_on(
+            transport.clone(),
+            clock.clone(),
+            CharacterSchema {
+                name: "char".to_string(),
+                hp: 60,
+                max_hp: 100,
+                cooldown_expiration: Some((start + TimeDelta::seconds(30)).to_rfc3339()),
+                ..Default::default()
+            },
+        );
+        char.account().set_retry_policy(RetryPolicy::default());
+
+        char.wait_for_cooldown().await;
+        assert_eq!(clock.now(), start + TimeDelta::seconds(30));
+        char.rest().await.unwrap();
+        assert!(clock.now() > start + TimeDelta::seconds(30));
+        assert_eq!(transport.requests().len(), 2);
+    }
+}
@@ -0,0 +1,1007 @@
+use crate::{
+    Code, CollectionClient, GOLD, Level, TASK_EXCHANGE_PRICE, TASKS_COIN,
+    client::{
+        bank::Bank,
+        character::{CharacterClient, HasCharacterData, inventory::Inventory},
+    },
+    conditions::{CharacterState, UnmetCondition},
+    container::{ItemContainer, LimitedContainer, SlotLimited, SpaceLimited},
+    entities::Map,
+    gear::Slot,
+    simulator::HasEffects,
+    skill::Skill,
+};
+use artifactsmmo_openapi::models::{MapContentType, SimpleItemSchema, TaskType};
+use thiserror::Error;
+
+/// A failed precondition of a character action.
+///
+/// Unlike the errors returned by the `can_*` methods, violations are reported
+/// all at once by the `explain_*` methods and carry the details needed to fix
+/// them.
+#[derive(Debug, Clone, PartialEq, Error)]
+pub enum Violation {
+    #[error("already on map")]
+    AlreadyOnMap,
+    #[error("map ({0},{1}) not found")]
+    MapNotFound(i32, i32),
+    #[error("map {map} is not accessible (nearest accessible: {})", display_nearest(.nearest))]
+    InaccessibleMap { map: Map, nearest: Option<Map> },
+    #[error("item `{0}` not found")]
+    ItemNotFound(String),
+    #[error("item `{0}` is not craftable")]
+    ItemNotCraftable(String),
+    #[error("item `{0}` is not recyclable")]
+    ItemNotRecyclable(String),
+    #[error("item `{0}` is not consumable")]
+    ItemNotConsumable(String),
+    #[error("item `{0}` cannot be bought")]
+    ItemNotBuyable(String),
+    #[error("item `{0}` cannot be sold")]
+    ItemNotSalable(String),
+    #[error("item `{0}` is not tradable")]
+    ItemNotTradable(String),
+    #[error("{skill} level is {current}, {required} required")]
+    InsufficientSkillLevel {
+        skill: Skill,
+        required: u32,
+        current: u32,
+    },
+    #[error("character level is {current}, {required} required")]
+    InsufficientCharacterLevel { required: u32, current: u32 },
+    #[error("missing {} `{code}` ({available}/{required})", .required - .available)]
+    MissingItem {
+        code: String,
+        required: u32,
+        available: u32,
+    },
+    #[error("missing {} gold ({available}/{required})", .required - .available)]
+    InsufficientGold { required: u32, available: u32 },
+    #[error("health is {current}, more than {required} required")]
+    InsufficientHealth { required: i32, current: i32 },
+    #[error("insufficient inventory space")]
+    InsufficientInventorySpace,
+    #[error("insufficient bank space")]
+    InsufficientBankSpace,
+    #[error("no {expected} on map (nearest: {})", display_nearest(.nearest))]
+    WrongMap {
+        expected: String,
+        nearest: Option<Map>,
+    },
+    #[error("no monster on map")]
+    NoMonsterOnMap,
+    #[error("no resource on map")]
+    NoResourceOnMap,
+    #[error("monster is not a boss")]
+    MonsterIsNotABoss,
+    #[error("participant `{0}` is not on the map")]
+    ParticipantNotOnMap(String),
+    #[error("participant `{0}` has insufficient inventory space")]
+    ParticipantInventoryFull(String),
+    #[error("slot {0:?} is not empty")]
+    SlotNotEmpty(Slot),
+    #[error("slot {0:?} is empty")]
+    SlotEmpty(Slot),
+    #[error("slot {slot:?} holds {available}, {required} required")]
+    InsufficientSlotQuantity {
+        slot: Slot,
+        required: u32,
+        available: u32,
+    },
+    #[error("item already equiped")]
+    ItemAlreadyEquiped,
+    #[error("quantity greater than the slot maximum")]
+    QuantityGreaterThanSlotMaximum,
+    #[error("task `{0}` already in progress")]
+    TaskAlreadyInProgress(String),
+    #[error("no current task")]
+    NoCurrentTask,
+    #[error("item `{code}` is not the task item `{task}`")]
+    NotTaskItem { code: String, task: String },
+    #[error("{quantity} items traded, only {missing} missing for the task")]
+    SuperfluousTaskQuantity { quantity: u32, missing: u32 },
+    #[error("task progress is {progress}/{total}")]
+    TaskNotFinished { progress: u32, total: u32 },
+    #[error("character `{0}` not found")]
+    CharacterNotFound(String),
+    #[error("recipient `{0}` is not on the map")]
+    RecipientNotOnMap(String),
+    #[error("recipient `{0}` has insufficient inventory space")]
+    RecipientInventoryFull(String),
+    #[error("order `{0}` not found")]
+    OrderNotFound(String),
+    #[error("order `{0}` is not owned by the account")]
+    OrderNotOwned(String),
+    #[error("cannot trade with self")]
+    CannotTradeWithSelf,
+    #[error("order holds {available}, {required} required")]
+    InsufficientOrderQuantity { required: u32, available: u32 },
+    #[error("conditions are not met: {}", .0.iter().map(|c| c.to_string()).collect::<Vec<_>>().join(", "))]
+    ConditionsNotMet(Vec<UnmetCondition>),
+}
+
+fn display_nearest(map: &Option<Map>) -> String {
+    map.as_ref()
+        .map_or_else(|| "none".to_owned(), |m| m.to_string())
+}
+
+impl CharacterClient {
+    /// Runs every check of `can_move` and returns all the failing ones.
+    ///
+    /// When the map cannot be accessed, the closest accessible map is reported.
+    pub fn explain_move(&self, x: i32, y: i32) -> Vec<Violation> {
+        let (layer, ..) = self.position();
+        if self.position() == (layer, x, y) {
+            return vec![Violation::AlreadyOnMap];
+        }
+        let Some(map) = self.maps.get(layer, x, y) else {
+            return vec![Violation::MapNotFound(x, y)];
+        };
+        let state = CharacterState::from(self);
+        let unmet = state.unmet(map.access());
+        if !map.is_blocked() && unmet.is_empty() {
+            return vec![];
+        }
+        let nearest = self
+            .maps
+            .closest_matching_from(map.clone(), |m| !m.is_blocked() && state.meets(m.access()));
+        let mut violations = vec![Violation::InaccessibleMap { map, nearest }];
+        if !unmet.is_empty() {
+            violations.push(Violation::ConditionsNotMet(unmet));
+        }
+        violations
+    }
+
+    /// Runs every check of `can_transition` and returns all the failing ones.
+    ///
+    /// When the current map has no transition, the closest map with a transition
+    /// the character can take is reported.
+    pub fn explain_transition(&self) -> Vec<Violation> {
+        let map = self.current_map();
+        let state = CharacterState::from(self);
+        let Some(transition) = map.transition() else {
+            return vec![Violation::WrongMap {
+                expected: "transition".to_owned(),
+                nearest: self
+                    .maps
+                    .closest_matching_from(map, |m| m.transition().is_some_and(|t| state.meets(t))),
+            }];
+        };
+        let unmet = state.unmet(transition);
+        if unmet.is_empty() {
+            return vec![];
+        }
+        vec![Violation::ConditionsNotMet(unmet)]
+    }
+
+    /// Runs every check of `can_craft` and returns all the failing ones.
+    pub fn explain_craft(&self, item_code: &str, quantity: u32) -> Vec<Violation> {
+        let Some(item) = self.items.get(item_code) else {
+            return vec![Violation::ItemNotFound(item_code.to_owned())];
+        };
+        let Some(skill) = item.skill_to_craft() else {
+            return vec![Violation::ItemNotCraftable(item_code.to_owned())];
+        };
+        let mut violations = vec![];
+        violations.extend(self.skill_level_violation(skill, item.level()));
+        violations.extend(self.missing_items(&item.mats_for(quantity)));
+        if !self.inventory().has_room_to_craft(&item) {
+            violations.push(Violation::InsufficientInventorySpace);
+        }
+        violations.extend(self.workshop_violation(skill));
+        violations
+    }
+
+    /// Runs every check of `can_recycle` and returns all the failing ones.
+    pub fn explain_recycle(&self, item_code: &str, quantity: u32) -> Vec<Violation> {
+        let Some(item) = self.items.get(item_code) else {
+            return vec![Violation::ItemNotFound(item_code.to_owned())];
+        };
+        let Some(skill) = item
+            .skill_to_craft()
+            .filter(|s| !s.is_cooking() && !s.is_alchemy())
+        else {
+            return vec![Violation::ItemNotRecyclable(item_code.to_owned())];
+        };
+        let mut violations = vec![];
+        violations.extend(self.skill_level_violation(skill, item.level()));
+        violations.extend(self.missing_items(&[SimpleItemSchema {
+            code: item_code.to_owned(),
+            quantity,
+        }]));
+        if self.inventory().free_space() + quantity < item.recycled_quantity() {
+            violations.push(Violation::InsufficientInventorySpace);
+        }
+        violations.extend(self.workshop_violation(skill));
+        violations
+    }
+
+    /// Runs every check of `can_delete` and returns all the failing ones.
+    pub fn explain_delete(&self, item_code: &str, quantity: u32) -> Vec<Violation> {
+        if self.items.get(item_code).is_none() {
+            return vec![Violation::ItemNotFound(item_code.to_owned())];
+        }
+        self.missing_items(&[SimpleItemSchema {
+            code: item_code.to_owned(),
+            quantity,
+        }])
+    }
+
+    /// Runs every check of `can_gather` and returns all the failing ones.
+    pub fn explain_gather(&self) -> Vec<Violation> {
+        let Some(resource) = self
+            .current_map()
+            .resource()
+            .and_then(|code| self.resources.get(code))
+        else {
+            return vec![Violation::NoResourceOnMap];
+        };
+        let mut violations = vec![];
+        violations.extend(self.skill_level_violation(resource.skill(), resource.level()));
+        if !self.inventory().has_room_for_drops_from(&resource) {
+            violations.push(Violation::InsufficientInventorySpace);
+        }
+        violations
+    }
+
+    /// Runs every check of `can_fight` and returns all the failing ones.
+    pub fn explain_fight(&self, participants: Option<&[String; 2]>) -> Vec<Violation> {
+        let Some(monster) = self
+            .current_map()
+            .monster()
+            .and_then(|code| self.monsters.get(code))
+        else {
+            return vec![Violation::NoMonsterOnMap];
+        };
+        let mut violations = vec![];
+        if !self.inventory().has_room_for_drops_from(&monster) {
+            violations.push(Violation::InsufficientInventorySpace);
+        }
+        let Some(participants) = participants else {
+            return violations;
+        };
+        if !participants.is_empty() && !monster.is_boss() {
+            violations.push(Violation::MonsterIsNotABoss);
+        }
+        for name in participants.iter() {
+            let Some(p) = self.account.get_character_by_name(name) else {
+                continue;
+            };
+            if p.position() != self.position() {
+                violations.push(Violation::ParticipantNotOnMap(name.clone()));
+            }
+            if !p.inventory().has_room_for_drops_from(&monster) {
+                violations.push(Violation::ParticipantInventoryFull(name.clone()));
+            }
+        }
+        violations
+    }
+
+    /// Runs every check of `can_equip` and returns all the failing ones.
+    pub fn explain_equip(&self, item_code: &str, slot: Slot, quantity: u32) -> Vec<Violation> {
+        let Some(item) = self.items.get(item_code) else {
+            return vec![Violation::ItemNotFound(item_code.to_owned())];
+        };
+        let mut violations = self.missing_items(&[SimpleItemSchema {
+            code: item_code.to_owned(),
+            quantity,
+        }]);
+        if let Some(equiped) = self.items.get(&self.equiped_in(slot)) {
+            if equiped.code() != item_code {
+                violations.push(Violation::SlotNotEmpty(slot));
+            } else if slot.max_quantity() <= 1 {
+                violations.push(Violation::ItemAlreadyEquiped);
+            } else if self.quantity_in_slot(slot) + quantity > slot.max_quantity() {
+                violations.push(Violation::QuantityGreaterThanSlotMaximum);
+            }
+        }
+        let unmet = self.unmet_conditions_for(&item);
+        if !unmet.is_empty() {
+            violations.push(Violation::ConditionsNotMet(unmet));
+        }
+        if self.inventory().free_space() as i32 + item.inventory_space() <= 0 {
+            violations.push(Violation::InsufficientInventorySpace);
+        }
+        violations
+    }
+
+    /// Runs every check of `can_unequip` and returns all the failing ones.
+    pub fn explain_unequip(&self, slot: Slot, quantity: u32) -> Vec<Violation> {
+        let Some(equiped) = self.items.get(&self.equiped_in(slot)) else {
+            return vec![Violation::SlotEmpty(slot)];
+        };
+        let mut violations = vec![];
+        if self.health() <= equiped.health() {
+            violations.push(Violation::InsufficientHealth {
+                required: equiped.health(),
+                current: self.health(),
+            });
+        }
+        if self.quantity_in_slot(slot) < quantity {
+            violations.push(Violation::InsufficientSlotQuantity {
+                slot,
+                required: quantity,
+                available: self.quantity_in_slot(slot),
+            });
+        }
+        if !self.inventory().has_room_for(equiped.code(), quantity) {
+            violations.push(Violation::InsufficientInventorySpace);
+        }
+        violations
+    }
+
+    /// Runs every check of `can_use_item` and returns all the failing ones.
+    pub fn explain_use_item(&self, item_code: &str, quantity: u32) -> Vec<Violation> {
+        let Some(item) = self.items.get(item_code) else {
+            return vec![Violation::ItemNotFound(item_code.to_owned())];
+        };
+        let mut violations = vec![];
+        if !item.is_consumable() {
+            violations.push(Violation::ItemNotConsumable(item_code.to_owned()));
+        }
+        violations.extend(self.missing_items(&[SimpleItemSchema {
+            code: item_code.to_owned(),
+            quantity,
+        }]));
+        if self.level() < item.level() {
+            violations.push(Violation::InsufficientCharacterLevel {
+                required: item.level(),
+                current: self.level(),
+            });
+        }
+        violations
+    }
+
+    /// Runs every check of `can_deposit_items` and returns all the failing ones.
+    pub fn explain_deposit_items(&self, items: &[SimpleItemSchema]) -> Vec<Violation> {
+        let mut violations = items
+            .iter()
+            .filter(|i| self.items.get(&i.code).is_none())
+            .map(|i| Violation::ItemNotFound(i.code.clone()))
+            .collect::<Vec<_>>();
+        violations.extend(self.missing_items(items));
+        if !self.bank.has_room_for_multiple(items) {
+            violations.push(Violation::InsufficientBankSpace);
+        }
+        violations.extend(self.bank_violation());
+        violations
+    }
+
+    /// Runs every check of `can_withdraw_items` and returns all the failing ones.
+    pub fn explain_withdraw_items(&self, items: &[SimpleItemSchema]) -> Vec<Violation> {
+        let mut violations = items
+            .iter()
+            .filter_map(|i| {
+                let available = self.bank.total_of(&i.code);
+                (available < i.quantity).then(|| Violation::MissingItem {
+                    code: i.code.clone(),
+                    required: i.quantity,
+                    available,
+                })
+            })
+            .collect::<Vec<_>>();
+        if !self.inventory().has_room_for_multiple(items) {
+            violations.push(Violation::InsufficientInventorySpace);
+        }
+        violations.extend(self.bank_violation());
+        violations
+    }
+
+    /// Runs every check of `can_deposit_gold` and returns all the failing ones.
+    pub fn explain_deposit_gold(&self, quantity: u32) -> Vec<Violation> {
+        let mut violations = vec![];
+        violations.extend(self.gold_violation(quantity));
+        violations.extend(self.bank_violation());
+        violations
+    }
+
+    /// Runs every check of `can_withdraw_gold` and returns all the failing ones.
+    pub fn explain_withdraw_gold(&self, quantity: u32) -> Vec<Violation> {
+        let mut violations = vec![];
+        if self.bank.gold() < quantity {
+            violations.push(Violation::InsufficientGold {
+                required: quantity,
+                available: self.bank.gold(),
+            });
+        }
+        violations.extend(self.bank_violation());
+        violations
+    }
+
+    /// Runs every check of `can_expand_bank` and returns all the failing ones.
+    pub fn explain_expand_bank(&self) -> Vec<Violation> {
+        let mut violations = vec![];
+        violations.extend(self.gold_violation(self.bank.next_expansion_cost()));
+        violations.extend(self.bank_violation());
+        violations
+    }
+
+    /// Runs every check of `can_accept_task` and returns all the failing ones.
+    pub fn explain_accept_task(&self) -> Vec<Violation> {
+        let mut violations = vec![];
+        if !self.task().is_empty() {
+            violations.push(Violation::TaskAlreadyInProgress(self.task()));
+        }
+        violations.extend(self.tasks_master_violation(None));
+        violations
+    }
+
+    /// Runs every check of `can_cancel_task` and returns all the failing ones.
+    pub fn explain_cancel_task(&self) -> Vec<Violation> {
+        let Some(task_type) = self.task_type() else {
+            return vec![Violation::NoCurrentTask];
+        };
+        let mut violations = self.missing_items(&[SimpleItemSchema {
+            code: TASKS_COIN.to_owned(),
+            quantity: 1,
+        }]);
+        violations.extend(self.tasks_master_violation(Some(task_type)));
+        violations
+    }
+
+    /// Runs every check of `can_trade_task_item` and returns all the failing ones.
+    pub fn explain_trade_task_item(&self, item_code: &str, quantity: u32) -> Vec<Violation> {
+        if self.items.get(item_code).is_none() {
+            return vec![Violation::ItemNotFound(item_code.to_owned())];
+        }
+        let mut violations = vec![];
+        if item_code != self.task() {
+            violations.push(Violation::NotTaskItem {
+                code: item_code.to_owned(),
+                task: self.task(),
+            });
+        }
+        violations.extend(self.missing_items(&[SimpleItemSchema {
+            code: item_code.to_owned(),
+            quantity,
+        }]));
+        if self.task_missing() < quantity {
+            violations.push(Violation::SuperfluousTaskQuantity {
+                quantity,
+                missing: self.task_missing(),
+            });
+        }
+        violations.extend(self.tasks_master_violation(Some(TaskType::Items)));
+        violations
+    }
+
+    /// Runs every check of `can_complete_task` and returns all the failing ones.
+    pub fn explain_complete_task(&self) -> Vec<Violation> {
+        let Some(task_type) = self.task_type() else {
+            return vec![Violation::NoCurrentTask];
+        };
+        let mut violations = vec![];
+        if !self.task_finished() {
+            violations.push(Violation::TaskNotFinished {
+                progress: self.task_progress(),
+                total: self.task_total(),
+            });
+        }
+        if self.inventory().free_space() < 2 {
+            violations.push(Violation::InsufficientInventorySpace);
+        }
+        violations.extend(self.tasks_master_violation(Some(task_type)));
+        violations
+    }
+
+    /// Runs every check of `can_exchange_tasks_coins` and returns all the failing ones.
+    pub fn explain_exchange_tasks_coins(&self) -> Vec<Violation> {
+        let coins = self.inventory().total_of(TASKS_COIN);
+        let mut violations = self.missing_items(&[SimpleItemSchema {
+            code: TASKS_COIN.to_owned(),
+            quantity: TASK_EXCHANGE_PRICE,
+        }]);
+        let extra_quantity = self
+            .tasks
+            .reward
+            .max_quantity()
+            .saturating_sub(TASK_EXCHANGE_PRICE);
+        if self.inventory().free_space() < extra_quantity
+            || self.inventory().free_slots() < 1 && coins > TASK_EXCHANGE_PRICE
+        {
+            violations.push(Violation::InsufficientInventorySpace);
+        }
+        violations.extend(self.tasks_master_violation(None));
+        violations
+    }
+
+    /// Runs every check of `can_npc_buy` and returns all the failing ones.
+    pub fn explain_npc_buy(&self, item_code: &str, quantity: u32) -> Vec<Violation> {
+        if self.items.get(item_code).is_none() {
+            return vec![Violation::ItemNotFound(item_code.to_owned())];
+        }
+        let Some(item) = self.npcs.items.get(item_code) else {
+            return vec![Violation::ItemNotBuyable(item_code.to_owned())];
+        };
+        let Some(buy_price) = item.buy_price() else {
+            return vec![Violation::ItemNotBuyable(item_code.to_owned())];
+        };
+        if item.currency() == GOLD {
+            self.gold_violation(buy_price * quantity)
+                .into_iter()
+                .collect()
+        } else {
+            self.missing_items(&[SimpleItemSchema {
+                code: item.currency().to_owned(),
+                quantity: buy_price * quantity,
+            }])
+        }
+    }
+
+    /// Runs every check of `can_npc_sell` and returns all the failing ones.
+    pub fn explain_npc_sell(&self, item_code: &str, quantity: u32) -> Vec<Violation> {
+        if self.items.get(item_code).is_none() {
+            return vec![Violation::ItemNotFound(item_code.to_owned())];
+        }
+        let mut violations = vec![];
+        if !self.items.is_salable(item_code) {
+            violations.push(Violation::ItemNotSalable(item_code.to_owned()));
+        }
+        violations.extend(self.missing_items(&[SimpleItemSchema {
+            code: item_code.to_owned(),
+            quantity,
+        }]));
+        violations
+    }
+
+    /// Runs every check of `can_give_item` and returns all the failing ones.
+    pub fn explain_give_item(&self, items: &[SimpleItemSchema], character: &str) -> Vec<Violation> {
+        let mut violations = items
+            .iter()
+            .filter(|i| self.items.get(&i.code).is_none())
+            .map(|i| Violation::ItemNotFound(i.code.clone()))
+            .collect::<Vec<_>>();
+        violations.extend(self.missing_items(items));
+        let Some(recipient) = self.account.get_character_by_name(character) else {
+            violations.push(Violation::CharacterNotFound(character.to_owned()));
+            return violations;
+        };
+        if recipient.position() != self.position() {
+            violations.push(Violation::RecipientNotOnMap(character.to_owned()));
+        }
+        if !recipient.inventory().has_room_for_multiple(items) {
+            violations.push(Violation::RecipientInventoryFull(character.to_owned()));
+        }
+        violations
+    }
+
+    /// Runs every check of `can_give_gold` and returns all the failing ones.
+    pub fn explain_give_gold(&self, quantity: u32, character: &str) -> Vec<Violation> {
+        let mut violations = vec![];
+        violations.extend(self.gold_violation(quantity));
+        let Some(recipient) = self.account.get_character_by_name(character) else {
+            violations.push(Violation::CharacterNotFound(character.to_owned()));
+            return violations;
+        };
+        if recipient.position() != self.position() {
+            violations.push(Violation::RecipientNotOnMap(character.to_owned()));
+        }
+        violations
+    }
+
+    /// Runs every check of `can_ge_buy_order` and returns all the failing ones.
+    ///
+    /// The order is fetched from the API.
+    pub fn explain_ge_buy_order(&self, id: &str, quantity: u32) -> Vec<Violation> {
+        let Some(order) = self.grand_exchange.get_order_by_id(id) else {
+            return vec![Violation::OrderNotFound(id.to_owned())];
+        };
+        let mut violations = vec![];
+        if self.account.name == order.seller {
+            violations.push(Violation::CannotTradeWithSelf);
+        }
+        if order.quantity < quantity {
+            violations.push(Violation::InsufficientOrderQuantity {
+                required: quantity,
+                available: order.quantity,
+            });
+        }
+        violations.extend(self.gold_violation(order.price * quantity));
+        if !self.inventory().has_room_for(&order.code, quantity) {
+            violations.push(Violation::InsufficientInventorySpace);
+        }
+        violations.extend(self.grand_exchange_violation());
+        violations
+    }
+
+    /// Runs every check of `can_ge_create_order` and returns all the failing ones.
+    pub fn explain_ge_create_order(
+        &self,
+        item_code: &str,
+        quantity: u32,
+        price: u32,
+    ) -> Vec<Violation> {
+        let Some(item) = self.items.get(item_code) else {
+            return vec![Violation::ItemNotFound(item_code.to_owned())];
+        };
+        let mut violations = vec![];
+        if !item.is_tradable() {
+            violations.push(Violation::ItemNotTradable(item_code.to_owned()));
+        }
+        violations.extend(self.missing_items(&[SimpleItemSchema {
+            code: item_code.to_owned(),
+            quantity,
+        }]));
+        violations.extend(self.gold_violation(((price * quantity) as f32 * 0.03) as u32));
+        violations.extend(self.grand_exchange_violation());
+        violations
+    }
+
+    /// Runs every check of `can_ge_cancel_order` and returns all the failing ones.
+    ///
+    /// The order is fetched from the API.
+    pub fn explain_ge_cancel_order(&self, id: &str) -> Vec<Violation> {
+        let Some(order) = self.grand_exchange.get_order_by_id(id) else {
+            return vec![Violation::OrderNotFound(id.to_owned())];
+        };
+        let mut violations = vec![];
+        if self.account.name != order.seller {
+            violations.push(Violation::OrderNotOwned(id.to_owned()));
+        }
+        if !self.inventory().has_room_for(&order.code, order.quantity) {
+            violations.push(Violation::InsufficientInventorySpace);
+        }
+        violations.extend(self.grand_exchange_violation());
+        violations
+    }
+
+    fn gold_violation(&self, required: u32) -> Option<Violation> {
+        (self.gold() < required).then(|| Violation::InsufficientGold {
+            required,
+            available: self.gold(),
+        })
+    }
+
+    fn skill_level_violation(&self, skill: Skill, required: u32) -> Option<Violation> {
+        let current = self.skill_level(skill);
+        (current < required).then_some(Violation::InsufficientSkillLevel {
+            skill,
+            required,
+            current,
+        })
+    }
+
+    fn missing_items(&self, items: &[SimpleItemSchema]) -> Vec<Violation> {
+        items
+            .iter()
+            .filter_map(|i| {
+                let available = self.inventory().total_of(&i.code);
+                (available < i.quantity).then(|| Violation::MissingItem {
+                    code: i.code.clone(),
+                    required: i.quantity,
+                    available,
+                })
+            })
+            .collect()
+    }
+
+    fn workshop_violation(&self, skill: Skill) -> Option<Violation> {
+        let map = self.current_map();
+        (!map.content_code_is(skill.as_ref())).then(|| Violation::WrongMap {
+            expected: format!("{skill} workshop"),
+            nearest: self
+                .maps
+                .closest_with_content_code_from(map, skill.as_ref()),
+        })
+    }
+
+    fn tasks_master_violation(&self, r#type: Option<TaskType>) -> Option<Violation> {
+        let map = self.current_map();
+        (!map.is_tasksmaster(r#type)).then(|| Violation::WrongMap {
+            expected: r#type.map_or_else(
+                || "tasks master".to_owned(),
+                |t| format!("{t} tasks master"),
+            ),
+            nearest: self.maps.closest_tasksmaster_from(map, r#type),
+        })
+    }
+
+    /// The grand exchange is reached from the bank maps.
+    fn grand_exchange_violation(&self) -> Option<Violation> {
+        let map = self.current_map();
+        (!map.content_type_is(MapContentType::Bank)).then(|| Violation::WrongMap {
+            expected: "grand exchange".to_owned(),
+            nearest: self.maps.closest_of_type_from(map, MapContentType::Bank),
+        })
+    }
+
+    fn bank_violation(&self) -> Option<Violation> {
+        let map = self.current_map();
+        (!map.content_type_is(MapContentType::Bank)).then(|| Violation::WrongMap {
+            expected: "bank".to_owned(),
+            nearest: self.maps.closest_of_type_from(map, MapContentType::Bank),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        client::character::request_handler::CharacterRequestHandler,
+        entities::{Item, Monster},
+        fixtures,
+    };
+    use artifactsmmo_openapi::models::{
+        AccessSchema, CharacterSchema, CraftSchema, CraftSkill, DropRateSchema, InteractionSchema,
+        InventorySlot, ItemSchema, MapAccessType, MapContentSchema, MapSchema, MonsterSchema,
+        MonsterType, TransitionSchema,
+    };
+    use std::sync::{Arc, RwLock};
+
+    fn map(x: i32, r#type: MapContentType, code: &str) -> MapSchema {
+        MapSchema {
+            x,
+            y: 0,
+            interactions: InteractionSchema {
+                content: Some(Box::new(MapContentSchema {
+                    r#type,
+                    code: code.to_owned(),
+                })),
+                ..Default::default()
+            }
+            .into(),
+            ..Default::default()
+        }
+    }
+
+    fn character(data: CharacterSchema, maps: Vec<MapSchema>) -> CharacterClient {
+        let dagger = Item::new(ItemSchema {
+            code: "copper_dagger".to_owned(),
+            level: 5,
+            craft: Some(Box::new(CraftSchema {
+                skill: Some(CraftSkill::Weaponcrafting),
+                items: Some(vec![SimpleItemSchema {
+                    code: "copper_bar".to_owned(),
+                    quantity: 6,
+                }]),
+                quantity: Some(1),
+                ..Default::default()
+            })),
+            ..Default::default()
+        });
+        let monster = |code: &str, r#type| {
+            Monster::new(MonsterSchema {
+                code: code.to_owned(),
+                r#type,
+                drops: vec![DropRateSchema {
+                    code: "feather".to_owned(),
+                    rate: 100,
+                    min_quantity: 1,
+                    max_quantity: 1,
+                    ..Default::default()
+                }],
+                ..Default::default()
+            })
+        };
+        CharacterClient {
+            inner: CharacterRequestHandler::detached(Arc::new(RwLock::new(Arc::new(data)))),
+            items: Arc::new(fixtures::items(vec![dagger])),
+            monsters: Arc::new(fixtures::monsters(vec![
+                monster("chicken", MonsterType::Normal),
+                monster("king_slime", MonsterType::Boss),
+            ])),
+            maps: Arc::new(fixtures::maps(maps)),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn explain_craft_reports_every_violation() {
+        let workshop = map(2, MapContentType::Workshop, "weaponcrafting");
+        let char = character(
+            CharacterSchema::default(),
+            vec![map(0, MapContentType::Bank, "bank"), workshop.clone()],
+        );
+
+        assert_eq!(
+            char.explain_craft("copper_dagger", 2),
+            vec![
+                Violation::InsufficientSkillLevel {
+                    skill: Skill::Weaponcrafting,
+                    required: 5,
+                    current: 0,
+                },
+                Violation::MissingItem {
+                    code: "copper_bar".to_owned(),
+                    required: 12,
+                    available: 0,
+                },
+                Violation::WrongMap {
+                    expected: format!("{} workshop", Skill::Weaponcrafting),
+                    nearest: Some(Map::new(workshop)),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn explain_fight_reports_every_violation() {
+        let participants = ["ally1".to_owned(), "ally2".to_owned()];
+        let char = character(
+            CharacterSchema::default(),
+            vec![map(0, MapContentType::Monster, "chicken")],
+        );
+
+        assert_eq!(
+            char.explain_fight(Some(&participants)),
+            vec![
+                Violation::InsufficientInventorySpace,
+                Violation::MonsterIsNotABoss,
+            ]
+        );
+        assert_eq!(
+            char.explain_fight(None),
+            vec![Violation::InsufficientInventorySpace]
+        );
+    }
+
+    #[test]
+    fn bosses_can_be_fought_with_participants() {
+        let participants = ["ally1".to_owned(), "ally2".to_owned()];
+        let data = CharacterSchema {
+            inventory_max_items: 100,
+            inventory: Some(vec![InventorySlot {
+                slot: 1,
+                code: String::new(),
+                quantity: 0,
+            }]),
+            ..Default::default()
+        };
+        let boss = character(
+            data.clone(),
+            vec![map(0, MapContentType::Monster, "king_slime")],
+        );
+        let chicken = character(data, vec![map(0, MapContentType::Monster, "chicken")]);
+
+        assert!(boss.explain_fight(Some(&participants)).is_empty());
+        assert_eq!(
+            chicken.explain_fight(Some(&participants)),
+            vec![Violation::MonsterIsNotABoss]
+        );
+    }
+
+    #[test]
+    fn explain_move_reports_the_nearest_accessible_map() {
+        let blocked = MapSchema {
+            x: 1,
+            access: AccessSchema {
+                r#type: MapAccessType::Blocked,
+                ..Default::default()
+            }
+            .into(),
+            ..Default::default()
+        };
+        let char = character(
+            CharacterSchema {
+                x: 3,
+                ..Default::default()
+            },
+            vec![
+                MapSchema::default(),
+                blocked,
+                MapSchema {
+                    x: 3,
+                    ..Default::default()
+                },
+            ],
+        );
+
+        assert_eq!(char.explain_move(3, 0), vec![Violation::AlreadyOnMap]);
+        assert_eq!(char.explain_move(5, 0), vec![Violation::MapNotFound(5, 0)]);
+        assert!(char.explain_move(0, 0).is_empty());
+        assert!(matches!(
+            &char.explain_move(1, 0)[..],
+            [Violation::InaccessibleMap { map, nearest: Some(nearest) }]
+                if map.x() == 1 && nearest.x() == 0
+        ));
+    }
+
+    #[test]
+    fn explain_transition_reports_the_nearest_transition() {
+        let transition = |x| MapSchema {
+            x,
+            interactions: InteractionSchema {
+                transition: Some(Box::new(TransitionSchema::default())),
+                ..Default::default()
+            }
+            .into(),
+            ..Default::default()
+        };
+        let char = character(
+            CharacterSchema::default(),
+            vec![MapSchema::default(), transition(2), transition(5)],
+        );
+
+        assert!(matches!(
+            &char.explain_transition()[..],
+            [Violation::WrongMap { expected, nearest: Some(nearest) }]
+                if expected == "transition" && nearest.x() == 2
+        ));
+    }
+
+    #[test]
+    fn explain_unequip_reports_every_violation() {
+        let char = character(
+            CharacterSchema {
+                weapon_slot: "copper_dagger".to_owned(),
+                ..Default::default()
+            },
+            vec![],
+        );
+
+        assert_eq!(
+            char.explain_unequip(Slot::Weapon, 2),
+            vec![
+                Violation::InsufficientHealth {
+                    required: 0,
+                    current: 0
+                },
+                Violation::InsufficientSlotQuantity {
+                    slot: Slot::Weapon,
+                    required: 2,
+                    available: 1
+                },
+                Violation::InsufficientInventorySpace,
+            ]
+        );
+        assert_eq!(
+            char.explain_unequip(Slot::Shield, 1),
+            vec![Violation::SlotEmpty(Slot::Shield)]
+        );
+    }
+
+    #[test]
+    fn explain_complete_task_reports_every_violation() {
+        let char = character(
+            CharacterSchema {
+                task: "chicken".to_owned(),
+                task_type: "monsters".to_owned(),
+                task_progress: 3,
+                task_total: 10,
+                ..Default::default()
+            },
+            vec![
+                map(0, MapContentType::TasksMaster, "items"),
+                map(4, MapContentType::TasksMaster, "monsters"),
+            ],
+        );
+
+        let violations = char.explain_complete_task();
+        assert_eq!(
+            violations[..2],
+            [
+                Violation::TaskNotFinished {
+                    progress: 3,
+                    total: 10
+                },
+                Violation::InsufficientInventorySpace,
+            ]
+        );
+        assert!(matches!(
+            &violations[2..],
+            [Violation::WrongMap { expected, nearest: Some(nearest) }]
+                if expected == "monsters tasks master" && nearest.x() == 4
+        ));
+        assert_eq!(
+            character(CharacterSchema::default(), vec![]).explain_complete_task(),
+            vec![Violation::NoCurrentTask]
+        );
+    }
+
+    #[test]
+    fn explain_give_gold_reports_unknown_recipients() {
+        let char = character(CharacterSchema::default(), vec![MapSchema::default()]);
+
+        assert_eq!(
+            char.explain_give_gold(10, "bob"),
+            vec![
+                Violation::InsufficientGold {
+                    required: 10,
+                    available: 0
+                },
+                Violation::CharacterNotFound("bob".to_owned()),
+            ]
+        );
+    }
+}
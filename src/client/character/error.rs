@@ -1,10 +1,11 @@
 use artifactsmmo_openapi::apis::Error;
 use derive_more::TryFrom;
+use sdk_derive::FromRequestError;
 use serde::{Deserialize, Serialize};
 use std::fmt::{self, Display, Formatter};
 use thiserror::Error;
 
-const ENTITY_NOT_FOUND: isize = 404;
+pub(crate) const ENTITY_NOT_FOUND: isize = 404;
 const MAXIMUM_ORDERS_CREATED: isize = 433;
 const INSUFFICIENT_ORDER_QUANTITY: isize = 434;
 const CANNOT_TRADE_WITH_SELF: isize = 435;
@@ -13,31 +14,31 @@ const GE_ITEM_NOT_SALABLE: isize = 437;
 const ORDER_NOT_OWNED: isize = 438;
 const ITEM_NOT_BUYABLE: isize = 441;
 const ITEM_NOT_SALABLE: isize = 442;
-const BANK_GOLD_INSUFFICIENT: isize = 460;
+pub(crate) const BANK_GOLD_INSUFFICIENT: isize = 460;
 //const TRANSACTION_ALREADY_IN_PROGRESS: isize = 461;
-const BANK_FULL: isize = 462;
+pub(crate) const BANK_FULL: isize = 462;
 const ITEM_NOT_RECYCLABLE: isize = 473;
 const WRONG_TASK: isize = 474;
 const TASK_ALREADY_COMPLETED_OR_TOO_MANY_ITEM_TRADED: isize = 475;
-const ITEM_NOT_CONSUMABLE: isize = 476;
-const MISSING_ITEM_OR_INSUFFICIENT_QUANTITY: isize = 478;
+pub(crate) const ITEM_NOT_CONSUMABLE: isize = 476;
+pub(crate) const MISSING_ITEM_OR_INSUFFICIENT_QUANTITY: isize = 478;
 const INSUFFICIENT_HEALTH: isize = 483;
 const SUPERFLOUS_UTILITY_QUANTITY: isize = 484;
 const ITEM_ALREADY_EQUIPED: isize = 485;
-const ACTION_ALREADY_IN_PROGRESS: isize = 486;
+pub(crate) const ACTION_ALREADY_IN_PROGRESS: isize = 486;
 const NO_TASK: isize = 487;
 const TASK_NOT_COMPLETED: isize = 488;
 const TASK_ALREADY_IN_PROGRESS: isize = 489;
-const ALREADY_ON_MAP: isize = 490;
-const INVALID_SLOT_STATE: isize = 491;
-const CHARACTER_GOLD_INSUFFICIENT: isize = 492;
-const SKILL_LEVEL_INSUFFICIENT: isize = 493;
-const CONDITIONS_NOT_MET: isize = 496;
-const INVENTORY_FULL: isize = 497;
-const CHARACTER_NOT_FOUND: isize = 498;
-//const CHARACTER_ON_COOLDOWN: isize = 499;
-const NO_PATH_AVAILABLE: isize = 595;
-const ENTITY_NOT_FOUND_ON_MAP: isize = 598;
+pub(crate) const ALREADY_ON_MAP: isize = 490;
+pub(crate) const INVALID_SLOT_STATE: isize = 491;
+pub(crate) const CHARACTER_GOLD_INSUFFICIENT: isize = 492;
+pub(crate) const SKILL_LEVEL_INSUFFICIENT: isize = 493;
+pub(crate) const CONDITIONS_NOT_MET: isize = 496;
+pub(crate) const INVENTORY_FULL: isize = 497;
+pub(crate) const CHARACTER_NOT_FOUND: isize = 498;
+pub(crate) const CHARACTER_ON_COOLDOWN: isize = 499;
+pub(crate) const NO_PATH_AVAILABLE: isize = 595;
+pub(crate) const ENTITY_NOT_FOUND_ON_MAP: isize = 598;
 
 #[derive(Error, Debug)]
 pub enum RequestError {
@@ -51,6 +52,15 @@ pub enum RequestError {
     ResponseError(ApiErrorResponseSchema),
     #[error("downcast error")]
     DowncastError,
+    #[error("transport error: {0}")]
+    Transport(String),
+    #[error("client is offline")]
+    Offline,
+    #[error("gave up after {attempts} attempts: {last}")]
+    RetriesExhausted {
+        attempts: u32,
+        last: Box<RequestError>,
+    },
 }
 
 impl<T> From<Error<T>> for RequestError {
@@ -84,7 +94,7 @@ pub struct ApiErrorSchema {
     pub message: String,
 }
 
-#[derive(Debug, Error, TryFrom)]
+#[derive(Debug, Error, TryFrom, FromRequestError)]
 #[try_from(repr)]
 #[repr(isize)]
 pub enum FightError {
@@ -95,10 +105,10 @@ pub enum FightError {
     #[error("No monster on map")]
     NoMonsterOnMap = ENTITY_NOT_FOUND_ON_MAP,
     #[error(transparent)]
-    UnhandledError(#[from] RequestError),
+    UnhandledError(RequestError),
 }
 
-#[derive(Debug, Error, TryFrom)]
+#[derive(Debug, Error, TryFrom, FromRequestError)]
 #[try_from(repr)]
 #[repr(isize)]
 pub enum GatherError {
@@ -109,10 +119,10 @@ pub enum GatherError {
     #[error("Insufficient skill level")]
     SkillLevelInsufficient = SKILL_LEVEL_INSUFFICIENT,
     #[error(transparent)]
-    UnhandledError(#[from] RequestError),
+    UnhandledError(RequestError),
 }
 
-#[derive(Debug, Error, TryFrom)]
+#[derive(Debug, Error, TryFrom, FromRequestError)]
 #[try_from(repr)]
 #[repr(isize)]
 pub enum MoveError {
@@ -125,10 +135,10 @@ pub enum MoveError {
     #[error("No path available")]
     NoPathAvailable = NO_PATH_AVAILABLE,
     #[error(transparent)]
-    UnhandledError(#[from] RequestError),
+    UnhandledError(RequestError),
 }
 
-#[derive(Debug, Error, TryFrom)]
+#[derive(Debug, Error, TryFrom, FromRequestError)]
 #[try_from(repr)]
 #[repr(isize)]
 pub enum TransitionError {
@@ -141,18 +151,28 @@ pub enum TransitionError {
     #[error("Conditions are not met")]
     ConditionsNotMet = CONDITIONS_NOT_MET,
     #[error(transparent)]
-    UnhandledError(#[from] RequestError),
+    UnhandledError(RequestError),
 }
 
-#[derive(Debug, Error, TryFrom)]
+#[derive(Debug, Error)]
+pub enum TravelError {
+    #[error("No path available")]
+    NoPathAvailable,
+    #[error(transparent)]
+    Move(#[from] MoveError),
+    #[error(transparent)]
+    Transition(#[from] TransitionError),
+}
+
+#[derive(Debug, Error, TryFrom, FromRequestError)]
 #[try_from(repr)]
 #[repr(isize)]
 pub enum RestError {
     #[error(transparent)]
-    UnhandledError(#[from] RequestError),
+    UnhandledError(RequestError),
 }
 
-#[derive(Debug, Error, TryFrom)]
+#[derive(Debug, Error, TryFrom, FromRequestError)]
 #[try_from(repr)]
 #[repr(isize)]
 pub enum UseError {
@@ -165,10 +185,10 @@ pub enum UseError {
     #[error("Insufficient character level")]
     InsufficientCharacterLevel = CONDITIONS_NOT_MET,
     #[error(transparent)]
-    UnhandledError(#[from] RequestError),
+    UnhandledError(RequestError),
 }
 
-#[derive(Debug, Error, TryFrom)]
+#[derive(Debug, Error, TryFrom, FromRequestError)]
 #[try_from(repr)]
 #[repr(isize)]
 pub enum CraftError {
@@ -185,10 +205,10 @@ pub enum CraftError {
     #[error("Required workshop not on map")]
     NoWorkshopOnMap = ENTITY_NOT_FOUND_ON_MAP,
     #[error(transparent)]
-    UnhandledError(#[from] RequestError),
+    UnhandledError(RequestError),
 }
 
-#[derive(Debug, Error, TryFrom)]
+#[derive(Debug, Error, TryFrom, FromRequestError)]
 #[try_from(repr)]
 #[repr(isize)]
 pub enum RecycleError {
@@ -205,10 +225,10 @@ pub enum RecycleError {
     #[error("Required workshop not on map")]
     NoWorkshopOnMap = ENTITY_NOT_FOUND_ON_MAP,
     #[error(transparent)]
-    UnhandledError(#[from] RequestError),
+    UnhandledError(RequestError),
 }
 
-#[derive(Debug, Error, TryFrom)]
+#[derive(Debug, Error, TryFrom, FromRequestError)]
 #[try_from(repr)]
 #[repr(isize)]
 pub enum DeleteError {
@@ -217,10 +237,10 @@ pub enum DeleteError {
     #[error("Insufficient quantity")]
     InsufficientQuantity = MISSING_ITEM_OR_INSUFFICIENT_QUANTITY,
     #[error(transparent)]
-    UnhandledError(#[from] RequestError),
+    UnhandledError(RequestError),
 }
 
-#[derive(Debug, Error, TryFrom)]
+#[derive(Debug, Error, TryFrom, FromRequestError)]
 #[try_from(repr)]
 #[repr(isize)]
 pub enum WithdrawError {
@@ -233,10 +253,10 @@ pub enum WithdrawError {
     #[error("No bank on map")]
     NoBankOnMap = ENTITY_NOT_FOUND_ON_MAP,
     #[error(transparent)]
-    UnhandledError(#[from] RequestError),
+    UnhandledError(RequestError),
 }
 
-#[derive(Debug, Error, TryFrom)]
+#[derive(Debug, Error, TryFrom, FromRequestError)]
 #[try_from(repr)]
 #[repr(isize)]
 pub enum DepositError {
@@ -249,10 +269,10 @@ pub enum DepositError {
     #[error("No bank on map")]
     NoBankOnMap = ENTITY_NOT_FOUND_ON_MAP,
     #[error(transparent)]
-    UnhandledError(#[from] RequestError),
+    UnhandledError(RequestError),
 }
 
-#[derive(Debug, Error, TryFrom)]
+#[derive(Debug, Error, TryFrom, FromRequestError)]
 #[try_from(repr)]
 #[repr(isize)]
 pub enum GoldWithdrawError {
@@ -261,10 +281,10 @@ pub enum GoldWithdrawError {
     #[error("No bank on map")]
     NoBankOnMap = ENTITY_NOT_FOUND_ON_MAP,
     #[error(transparent)]
-    UnhandledError(#[from] RequestError),
+    UnhandledError(RequestError),
 }
 
-#[derive(Debug, Error, TryFrom)]
+#[derive(Debug, Error, TryFrom, FromRequestError)]
 #[try_from(repr)]
 #[repr(isize)]
 pub enum GoldDepositError {
@@ -273,10 +293,10 @@ pub enum GoldDepositError {
     #[error("No bank on map")]
     NoBankOnMap = ENTITY_NOT_FOUND_ON_MAP,
     #[error(transparent)]
-    UnhandledError(#[from] RequestError),
+    UnhandledError(RequestError),
 }
 
-#[derive(Debug, Error, TryFrom)]
+#[derive(Debug, Error, TryFrom, FromRequestError)]
 #[try_from(repr)]
 #[repr(isize)]
 pub enum BankExpansionError {
@@ -285,10 +305,10 @@ pub enum BankExpansionError {
     #[error("No bank on map")]
     NoBankOnMap = ENTITY_NOT_FOUND_ON_MAP,
     #[error(transparent)]
-    UnhandledError(#[from] RequestError),
+    UnhandledError(RequestError),
 }
 
-#[derive(Debug, Error, TryFrom)]
+#[derive(Debug, Error, TryFrom, FromRequestError)]
 #[try_from(repr)]
 #[repr(isize)]
 pub enum EquipError {
@@ -307,10 +327,10 @@ pub enum EquipError {
     #[error("Insufficient inventory space")]
     InsufficientInventorySpace = INVENTORY_FULL,
     #[error(transparent)]
-    UnhandledError(#[from] RequestError),
+    UnhandledError(RequestError),
 }
 
-#[derive(Debug, Error, TryFrom)]
+#[derive(Debug, Error, TryFrom, FromRequestError)]
 #[try_from(repr)]
 #[repr(isize)]
 pub enum UnequipError {
@@ -323,10 +343,10 @@ pub enum UnequipError {
     #[error("Insufficient inventory space")]
     InsufficientInventorySpace = INVENTORY_FULL,
     #[error(transparent)]
-    UnhandledError(#[from] RequestError),
+    UnhandledError(RequestError),
 }
 
-#[derive(Debug, Error, TryFrom)]
+#[derive(Debug, Error, TryFrom, FromRequestError)]
 #[try_from(repr)]
 #[repr(isize)]
 pub enum TaskAcceptationError {
@@ -335,10 +355,10 @@ pub enum TaskAcceptationError {
     #[error("No tasks master on map")]
     NoTasksMasterOnMap = ENTITY_NOT_FOUND_ON_MAP,
     #[error(transparent)]
-    UnhandledError(#[from] RequestError),
+    UnhandledError(RequestError),
 }
 
-#[derive(Debug, Error, TryFrom)]
+#[derive(Debug, Error, TryFrom, FromRequestError)]
 #[try_from(repr)]
 #[repr(isize)]
 pub enum TaskTradeError {
@@ -353,10 +373,10 @@ pub enum TaskTradeError {
     #[error("Wrong or no tasks master on map")]
     WrongOrNoTasksMasterOnMap = ENTITY_NOT_FOUND_ON_MAP,
     #[error(transparent)]
-    UnhandledError(#[from] RequestError),
+    UnhandledError(RequestError),
 }
 
-#[derive(Debug, Error, TryFrom)]
+#[derive(Debug, Error, TryFrom, FromRequestError)]
 #[try_from(repr)]
 #[repr(isize)]
 pub enum TaskCompletionError {
@@ -369,10 +389,10 @@ pub enum TaskCompletionError {
     #[error("Wrong or no tasks master on map")]
     WrongOrNoTasksMasterOnMap = ENTITY_NOT_FOUND_ON_MAP,
     #[error(transparent)]
-    UnhandledError(#[from] RequestError),
+    UnhandledError(RequestError),
 }
 
-#[derive(Debug, Error, TryFrom)]
+#[derive(Debug, Error, TryFrom, FromRequestError)]
 #[try_from(repr)]
 #[repr(isize)]
 pub enum TaskCancellationError {
@@ -383,10 +403,10 @@ pub enum TaskCancellationError {
     #[error("Wrong or no tasks master on map")]
     WrongOrNoTasksMasterOnMap = ENTITY_NOT_FOUND_ON_MAP,
     #[error(transparent)]
-    UnhandledError(#[from] RequestError),
+    UnhandledError(RequestError),
 }
 
-#[derive(Debug, Error, TryFrom)]
+#[derive(Debug, Error, TryFrom, FromRequestError)]
 #[try_from(repr)]
 #[repr(isize)]
 pub enum TasksCoinExchangeError {
@@ -397,10 +417,10 @@ pub enum TasksCoinExchangeError {
     #[error("No tasks master on map")]
     NoTasksMasterOnMap = ENTITY_NOT_FOUND_ON_MAP,
     #[error(transparent)]
-    UnhandledError(#[from] RequestError),
+    UnhandledError(RequestError),
 }
 
-#[derive(Debug, Error, TryFrom)]
+#[derive(Debug, Error, TryFrom, FromRequestError)]
 #[try_from(repr)]
 #[repr(isize)]
 pub enum BuyNpcError {
@@ -417,10 +437,10 @@ pub enum BuyNpcError {
     #[error("Npc not found on map")]
     NpcNotFound = ENTITY_NOT_FOUND_ON_MAP,
     #[error(transparent)]
-    UnhandledError(#[from] RequestError),
+    UnhandledError(RequestError),
 }
 
-#[derive(Debug, Error, TryFrom)]
+#[derive(Debug, Error, TryFrom, FromRequestError)]
 #[try_from(repr)]
 #[repr(isize)]
 pub enum SellNpcError {
@@ -435,10 +455,10 @@ pub enum SellNpcError {
     #[error("Npc not found on map")]
     NpcNotFound = ENTITY_NOT_FOUND_ON_MAP,
     #[error(transparent)]
-    UnhandledError(#[from] RequestError),
+    UnhandledError(RequestError),
 }
 
-#[derive(Debug, Error, TryFrom)]
+#[derive(Debug, Error, TryFrom, FromRequestError)]
 #[try_from(repr)]
 #[repr(isize)]
 pub enum GiveItemError {
@@ -451,10 +471,10 @@ pub enum GiveItemError {
     #[error("Character not found")]
     CharacterNotFound = CHARACTER_NOT_FOUND,
     #[error(transparent)]
-    UnhandledError(#[from] RequestError),
+    UnhandledError(RequestError),
 }
 
-#[derive(Debug, Error, TryFrom)]
+#[derive(Debug, Error, TryFrom, FromRequestError)]
 #[try_from(repr)]
 #[repr(isize)]
 pub enum GiveGoldError {
@@ -465,10 +485,10 @@ pub enum GiveGoldError {
     #[error("Character not found")]
     CharacterNotFound = CHARACTER_NOT_FOUND,
     #[error(transparent)]
-    UnhandledError(#[from] RequestError),
+    UnhandledError(RequestError),
 }
 
-#[derive(Debug, Error, TryFrom)]
+#[derive(Debug, Error, TryFrom, FromRequestError)]
 #[try_from(repr)]
 #[repr(isize)]
 pub enum GeBuyOrderError {
@@ -485,10 +505,10 @@ pub enum GeBuyOrderError {
     #[error("No grand exchange on map")]
     NoGrandExchangeOnMap = ENTITY_NOT_FOUND_ON_MAP,
     #[error(transparent)]
-    UnhandledError(#[from] RequestError),
+    UnhandledError(RequestError),
 }
 
-#[derive(Debug, Error, TryFrom)]
+#[derive(Debug, Error, TryFrom, FromRequestError)]
 #[try_from(repr)]
 #[repr(isize)]
 pub enum GeCreateOrderError {
@@ -505,10 +525,10 @@ pub enum GeCreateOrderError {
     #[error("No grand exchange on map")]
     NoGrandExchangeOnMap = ENTITY_NOT_FOUND_ON_MAP,
     #[error(transparent)]
-    UnhandledError(#[from] RequestError),
+    UnhandledError(RequestError),
 }
 
-#[derive(Debug, Error, TryFrom)]
+#[derive(Debug, Error, TryFrom, FromRequestError)]
 #[try_from(repr)]
 #[repr(isize)]
 pub enum GeCancelOrderError {
@@ -521,7 +541,75 @@ pub enum GeCancelOrderError {
     #[error("No grand exchange on map")]
     NoGrandExchangeOnMap = ENTITY_NOT_FOUND_ON_MAP,
     #[error(transparent)]
-    UnhandledError(#[from] RequestError),
+    UnhandledError(RequestError),
+}
+
+#[derive(Debug, Error)]
+pub enum ActionError {
+    #[error(transparent)]
+    Move(#[from] MoveError),
+    #[error(transparent)]
+    Transition(#[from] TransitionError),
+    #[error(transparent)]
+    Fight(#[from] FightError),
+    #[error(transparent)]
+    Rest(#[from] RestError),
+    #[error(transparent)]
+    Gather(#[from] GatherError),
+    #[error(transparent)]
+    Craft(#[from] CraftError),
+    #[error(transparent)]
+    Recycle(#[from] RecycleError),
+    #[error(transparent)]
+    Delete(#[from] DeleteError),
+    #[error(transparent)]
+    Deposit(#[from] DepositError),
+    #[error(transparent)]
+    Withdraw(#[from] WithdrawError),
+    #[error(transparent)]
+    GoldDeposit(#[from] GoldDepositError),
+    #[error(transparent)]
+    GoldWithdraw(#[from] GoldWithdrawError),
+    #[error(transparent)]
+    BankExpansion(#[from] BankExpansionError),
+    #[error(transparent)]
+    Equip(#[from] EquipError),
+    #[error(transparent)]
+    Unequip(#[from] UnequipError),
+    #[error(transparent)]
+    Use(#[from] UseError),
+    #[error(transparent)]
+    TaskAcceptation(#[from] TaskAcceptationError),
+    #[error(transparent)]
+    TaskCancellation(#[from] TaskCancellationError),
+    #[error(transparent)]
+    TaskTrade(#[from] TaskTradeError),
+    #[error(transparent)]
+    TaskCompletion(#[from] TaskCompletionError),
+    #[error(transparent)]
+    TasksCoinExchange(#[from] TasksCoinExchangeError),
+    #[error(transparent)]
+    BuyNpc(#[from] BuyNpcError),
+    #[error(transparent)]
+    SellNpc(#[from] SellNpcError),
+    #[error(transparent)]
+    GiveItem(#[from] GiveItemError),
+    #[error(transparent)]
+    GiveGold(#[from] GiveGoldError),
+    #[error(transparent)]
+    GeBuyOrder(#[from] GeBuyOrderError),
+    #[error(transparent)]
+    GeCreateOrder(#[from] GeCreateOrderError),
+    #[error(transparent)]
+    GeCancelOrder(#[from] GeCancelOrderError),
+}
+
+#[derive(Debug, Error)]
+#[error("step {step} ({action}) failed: {source}")]
+pub struct PlanStepError {
+    pub step: usize,
+    pub action: String,
+    pub source: ActionError,
 }
 
 // #[derive(Debug, Error, TryFrom)]
@@ -537,3 +625,33 @@ pub enum GeCancelOrderError {
 //     #[error(transparent)]
 //     UnhandledError(#[from] RequestError),
 // }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn response_error(code: u32) -> RequestError {
+        RequestError::ResponseError(ApiErrorResponseSchema {
+            error: ApiErrorSchema {
+                code,
+                message: String::new(),
+            },
+        })
+    }
+
+    #[test]
+    fn response_errors_are_mapped_to_their_variant() {
+        assert!(matches!(
+            FightError::from(response_error(INVENTORY_FULL as u32)),
+            FightError::InsufficientInventorySpace
+        ));
+        assert!(matches!(
+            FightError::from(response_error(CHARACTER_ON_COOLDOWN as u32)),
+            FightError::UnhandledError(RequestError::ResponseError(_))
+        ));
+        assert!(matches!(
+            FightError::from(RequestError::DowncastError),
+            FightError::UnhandledError(RequestError::DowncastError)
+        ));
+    }
+}
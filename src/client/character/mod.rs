@@ -15,18 +15,21 @@ use crate::{
                 BankExpansionError, BuyNpcError, CraftError, DeleteError, DepositError, EquipError,
                 FightError, GatherError, GoldDepositError, GoldWithdrawError, MoveError,
                 RecycleError, RestError, SellNpcError, TaskAcceptationError, TaskCancellationError,
-                TaskCompletionError, TaskTradeError, TasksCoinExchangeError, UnequipError,
-                UseError, WithdrawError,
+                TaskCompletionError, TaskTradeError, TasksCoinExchangeError, TravelError,
+                UnequipError, UseError, WithdrawError,
             },
             request_handler::CharacterRequestHandler,
         },
-        items::{ItemsClient, LevelConditionCode},
-        maps::MapsClient,
+        items::ItemsClient,
+        maps::{MapsClient, Route, RouteStep},
         monsters::MonstersClient,
         npcs::NpcsClient,
         resources::ResourcesClient,
         server::ServerClient,
+        transport::Transport,
     },
+    clock::Clock,
+    conditions::{CharacterState, UnmetCondition},
     entities::Map,
     gear::Slot,
     grand_exchange::GrandExchangeClient,
@@ -35,25 +38,35 @@ use crate::{
 };
 use artifactsmmo_api_wrapper::ArtifactApi;
 use artifactsmmo_openapi::models::{
-    CharacterFightSchema, CharacterSchema, ConditionOperator, GeTransactionSchema, MapContentType,
-    MapLayer, MapSchema, NpcItemTransactionSchema, RecyclingItemsSchema, RewardsSchema,
-    SimpleItemSchema, SkillDataSchema, SkillInfoSchema, TaskSchema, TaskTradeSchema, TaskType,
+    CharacterFightSchema, CharacterSchema, GeTransactionSchema, MapContentType, MapLayer,
+    MapSchema, NpcItemTransactionSchema, RecyclingItemsSchema, RewardsSchema, SimpleItemSchema,
+    SkillDataSchema, SkillInfoSchema, TaskSchema, TaskTradeSchema, TaskType,
 };
 use chrono::{DateTime, Utc};
 use std::{
-    str::FromStr,
     sync::{Arc, RwLock},
     time::Duration,
 };
 use strum::IntoEnumIterator;
 
+#[cfg(feature = "async")]
+pub use async_character::AsyncCharacterClient;
+pub use diagnostic::Violation;
 pub use inventory::InventoryClient;
+pub use plan::{ActionPlan, PlanStatus};
+pub use request_handler::ResponseSchema;
 
 mod request_handler;
 
+pub(crate) mod projection;
+
 pub mod action;
+#[cfg(feature = "async")]
+pub mod async_character;
+pub mod diagnostic;
 pub mod error;
 pub mod inventory;
+pub mod plan;
 
 pub type CharacterData = Arc<RwLock<Arc<CharacterSchema>>>;
 
@@ -87,10 +100,17 @@ impl CharacterClient {
         grand_exchange: Arc<GrandExchangeClient>,
         server: Arc<ServerClient>,
         api: Arc<ArtifactApi>,
+        transport: Arc<dyn Transport>,
     ) -> Self {
         Self {
             id,
-            inner: CharacterRequestHandler::new(api, data.clone(), account.clone(), server.clone()),
+            inner: CharacterRequestHandler::new(
+                api,
+                transport,
+                data.clone(),
+                account.clone(),
+                server.clone(),
+            ),
             account: account.clone(),
             bank: account.bank.clone(),
             items,
@@ -136,6 +156,37 @@ impl CharacterClient {
         }
         Ok(())
     }
+
+    /// Walks the shortest accessible route to `map`, taking transitions when needed.
+    pub fn travel_to(&self, map: &Map) -> Result<Map, TravelError> {
+        let Some(route) = self.route_to(map) else {
+            return Err(TravelError::NoPathAvailable);
+        };
+        for step in route.steps {
+            match step {
+                RouteStep::Move(map) => {
+                    self.r#move(map.x(), map.y())?;
+                }
+                RouteStep::Transition(_) => {
+                    self.transition()?;
+                }
+            }
+        }
+        Ok(self.current_map())
+    }
+
+    /// Returns the shortest route to `map` through the maps and transitions the
+    /// character meets the conditions for.
+    pub fn route_to(&self, map: &Map) -> Option<Route> {
+        let state = CharacterState::from(self);
+        self.maps.route(
+            self.position(),
+            (map.layer(), map.x(), map.y()),
+            |m| state.meets(m.access()),
+            |t| state.meets(t),
+        )
+    }
+
     pub fn fight(
         &self,
         participants: Option<&[String; 2]>,
@@ -158,7 +209,7 @@ impl CharacterClient {
         let Some(participants) = participants else {
             return Ok(());
         };
-        if !participants.is_empty() && monster.is_boss() {
+        if !participants.is_empty() && !monster.is_boss() {
             return Err(FightError::MonsterIsNotABoss);
         }
         for p in participants.iter() {
@@ -785,34 +836,15 @@ impl CharacterClient {
         }
     }
 
-    // TODO: return a result
+    /// Builds the character state on each call: callers checking many
+    /// entities should build a `CharacterState` once and use it instead.
     pub fn meets_conditions_for(&self, entity: &impl HasConditions) -> bool {
-        entity.conditions().iter().flatten().all(|condition| {
-            let value = condition.value as u32;
-            // TODO: simplify this
-            match condition.operator {
-                ConditionOperator::Cost => {
-                    if condition.code == GOLD {
-                        self.gold() >= value
-                    } else {
-                        self.inventory().total_of(&condition.code) >= value
-                    }
-                }
-                ConditionOperator::HasItem => self.has_equiped(&condition.code) >= value,
-                ConditionOperator::AchievementUnlocked => self
-                    .account()
-                    .get_achievement(&condition.code)
-                    .is_some_and(|a| a.completed_at.is_some()),
-                ConditionOperator::Eq => LevelConditionCode::from_str(&condition.code)
-                    .is_ok_and(|code| self.skill_level(Skill::from(code)) == value),
-                ConditionOperator::Ne => LevelConditionCode::from_str(&condition.code)
-                    .is_ok_and(|code| self.skill_level(Skill::from(code)) != value),
-                ConditionOperator::Gt => LevelConditionCode::from_str(&condition.code)
-                    .is_ok_and(|code| self.skill_level(Skill::from(code)) > value),
-                ConditionOperator::Lt => LevelConditionCode::from_str(&condition.code)
-                    .is_ok_and(|code| self.skill_level(Skill::from(code)) < value),
-            }
-        })
+        CharacterState::from(self).meets(entity)
+    }
+
+    /// Returns the conditions of `entity` the character does not meet.
+    pub fn unmet_conditions_for(&self, entity: &impl HasConditions) -> Vec<UnmetCondition> {
+        CharacterState::from(self).unmet(entity)
     }
 
     pub fn account(&self) -> Arc<AccountClient> {
@@ -823,6 +855,11 @@ impl CharacterClient {
         self.inner.remaining_cooldown()
     }
 
+    /// Returns the clock the cooldowns of the character are measured with.
+    pub fn clock(&self) -> Arc<dyn Clock> {
+        self.inner.clock()
+    }
+
     pub fn current_map(&self) -> Map {
         let (layer, x, y) = self.position();
         self.maps.get(layer, x, y).unwrap()
@@ -1024,7 +1061,10 @@ pub trait HasCharacterData {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use artifactsmmo_openapi::models::InventorySlot;
+    use crate::{client::transport::MockTransport, entities::Monster, fixtures};
+    use artifactsmmo_openapi::models::{
+        InteractionSchema, InventorySlot, MapContentSchema, MonsterSchema, MonsterType,
+    };
     use std::sync::RwLock;
 
     impl From<CharacterSchema> for CharacterClient {
@@ -1042,6 +1082,7 @@ mod tests {
                 Default::default(),
                 Default::default(),
                 Default::default(),
+                Arc::new(MockTransport::new()),
             )
         }
     }
@@ -1378,5 +1419,49 @@ mod tests {
         ]);
         // assert!(char.can_withdraw_item("iron_sword", 10).is_ok());
     }
+
+    #[test]
+    fn only_bosses_can_be_fought_with_participants() {
+        let participants = ["ally1".to_owned(), "ally2".to_owned()];
+        let fighting = |code: &str, r#type| CharacterClient {
+            inner: CharacterRequestHandler::detached(Arc::new(RwLock::new(Arc::new(
+                CharacterSchema {
+                    inventory_max_items: 100,
+                    inventory: Some(vec![InventorySlot {
+                        slot: 1,
+                        code: String::new(),
+                        quantity: 0,
+                    }]),
+                    ..Default::default()
+                },
+            )))),
+            monsters: Arc::new(fixtures::monsters(vec![Monster::new(MonsterSchema {
+                code: code.to_owned(),
+                r#type,
+                ..Default::default()
+            })])),
+            maps: Arc::new(fixtures::maps(vec![MapSchema {
+                interactions: InteractionSchema {
+                    content: Some(Box::new(MapContentSchema {
+                        r#type: MapContentType::Monster,
+                        code: code.to_owned(),
+                    })),
+                    ..Default::default()
+                }
+                .into(),
+                ..Default::default()
+            }])),
+            ..Default::default()
+        };
+        let boss = fighting("king_slime", MonsterType::Boss);
+        let chicken = fighting("chicken", MonsterType::Normal);
+
+        assert!(boss.can_fight(Some(&participants)).is_ok());
+        assert!(chicken.can_fight(None).is_ok());
+        assert!(matches!(
+            chicken.can_fight(Some(&participants)),
+            Err(FightError::MonsterIsNotABoss)
+        ));
+    }
     //TODO: add more tests
 }
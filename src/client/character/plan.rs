@@ -0,0 +1,750 @@
+use crate::{
+    CollectionClient, GOLD, ItemContainer,
+    client::{
+        bank::{Bank, BankClient},
+        character::{
+            CharacterClient, HasCharacterData,
+            action::Action,
+            error::{ActionError, PlanStepError, UnequipError},
+            projection::{
+                add_item, deposit_to_bank, quantity_in, remove_item, set_quantity_in, slot_of,
+                withdraw_from_bank,
+            },
+            request_handler::CharacterRequestHandler,
+        },
+    },
+    consts::BANK_EXTENSION_SIZE,
+    simulator::HasEffects,
+};
+use artifactsmmo_openapi::models::{BankSchema, CharacterSchema, SimpleItemSchema};
+use std::{
+    cmp::min,
+    sync::{Arc, RwLock},
+    time::Duration,
+};
+
+/// Ordered list of `Action`s to be executed by a `CharacterClient`.
+///
+/// The plan is validated up front by running the `can_*` check of each step against the
+/// character and bank state projected from the previous steps. Drops from fights, gathering
+/// and recycling cannot be predicted and are not projected. Execution stops at the first
+/// failing step, which stays at the cursor so the plan can be resumed.
+#[derive(Debug, Default)]
+pub struct ActionPlan<'a> {
+    steps: Vec<Action<'a>>,
+    cursor: usize,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PlanStatus {
+    /// The character is in cooldown for the given duration before the next step.
+    Paused(Duration),
+    Completed,
+}
+
+impl<'a> ActionPlan<'a> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn then(mut self, action: Action<'a>) -> Self {
+        self.steps.push(action);
+        self
+    }
+
+    pub fn steps(&self) -> &[Action<'a>] {
+        &self.steps
+    }
+
+    /// Returns the index of the next step to be executed.
+    pub fn cursor(&self) -> usize {
+        self.cursor
+    }
+
+    pub fn is_done(&self) -> bool {
+        self.cursor >= self.steps.len()
+    }
+
+    /// Checks the remaining steps against the state of `character` projected step by step.
+    pub fn validate(&self, character: &CharacterClient) -> Result<(), PlanStepError> {
+        let mut data = (*character.data()).clone();
+        let mut details = (*character.bank.details()).clone();
+        let mut content = (*character.bank.content()).clone();
+        for (step, action) in self.steps.iter().enumerate().skip(self.cursor) {
+            let projected = character.projected(
+                data.clone(),
+                BankClient::new(details.clone(), content.clone()),
+            );
+            projected
+                .check(action)
+                .and_then(|_| projected.project(action, &mut data, &mut details, &mut content))
+                .map_err(|source| PlanStepError {
+                    step,
+                    action: action.to_string(),
+                    source,
+                })?;
+        }
+        Ok(())
+    }
+
+    /// Executes the next step unless the character is in cooldown, in which case the plan
+    /// is paused for the remaining cooldown.
+    pub fn step(&mut self, character: &CharacterClient) -> Result<PlanStatus, PlanStepError> {
+        let Some(action) = self.steps.get(self.cursor) else {
+            return Ok(PlanStatus::Completed);
+        };
+        let cooldown = character.remaining_cooldown();
+        if !cooldown.is_zero() {
+            return Ok(PlanStatus::Paused(cooldown));
+        }
+        character.perform(action).map_err(|source| PlanStepError {
+            step: self.cursor,
+            action: action.to_string(),
+            source,
+        })?;
+        self.cursor += 1;
+        if self.is_done() {
+            Ok(PlanStatus::Completed)
+        } else {
+            Ok(PlanStatus::Paused(character.remaining_cooldown()))
+        }
+    }
+
+    /// Validates the remaining steps then executes them, waiting for the cooldowns in between.
+    pub fn execute(&mut self, character: &CharacterClient) -> Result<(), PlanStepError> {
+        self.validate(character)?;
+        while let PlanStatus::Paused(cooldown) = self.step(character)? {
+            character.clock().sleep(cooldown);
+        }
+        Ok(())
+    }
+}
+
+impl CharacterClient {
+    fn projected(&self, data: CharacterSchema, bank: BankClient) -> CharacterClient {
+        CharacterClient {
+            id: self.id,
+            inner: CharacterRequestHandler::detached(Arc::new(RwLock::new(Arc::new(data)))),
+            account: self.account.clone(),
+            bank: Arc::new(bank),
+            items: self.items.clone(),
+            resources: self.resources.clone(),
+            monsters: self.monsters.clone(),
+            maps: self.maps.clone(),
+            npcs: self.npcs.clone(),
+            tasks: self.tasks.clone(),
+            grand_exchange: self.grand_exchange.clone(),
+        }
+    }
+
+    fn check(&self, action: &Action) -> Result<(), ActionError> {
+        match action {
+            Action::Move { x, y } => self.can_move(*x, *y)?,
+            Action::Transition => self.can_transition()?,
+            Action::Fight { participants } => self.can_fight(*participants)?,
+            Action::Rest => {}
+            Action::Gather => self.can_gather()?,
+            Action::Craft {
+                item_code,
+                quantity,
+            } => self.can_craft(item_code, *quantity)?,
+            Action::Recycle {
+                item_code,
+                quantity,
+            } => self.can_recycle(item_code, *quantity)?,
+            Action::Delete {
+                item_code,
+                quantity,
+            } => self.can_delete(item_code, *quantity)?,
+            Action::DepositItem { items } => self.can_deposit_items(items)?,
+            Action::WithdrawItem { items } => self.can_withdraw_items(items)?,
+            Action::DepositGold { quantity } => self.can_deposit_gold(*quantity)?,
+            Action::WithdrawGold { quantity } => self.can_withdraw_gold(*quantity)?,
+            Action::ExpandBank => self.can_expand_bank()?,
+            Action::Equip {
+                item_code,
+                slot,
+                quantity,
+            } => self.can_equip(item_code, *slot, *quantity)?,
+            Action::Unequip { slot, quantity } => self.can_unequip(*slot, *quantity)?,
+            Action::UseItem {
+                item_code,
+                quantity,
+            } => self.can_use_item(item_code, *quantity)?,
+            Action::AcceptTask => self.can_accept_task()?,
+            Action::CancelTask => self.can_cancel_task()?,
+            Action::TradeTaskItem {
+                item_code,
+                quantity,
+            } => self.can_trade_task_item(item_code, *quantity)?,
+            Action::CompleteTask => self.can_complete_task()?,
+            Action::ExchangeTasksCoins => self.can_exchange_tasks_coins()?,
+            Action::NpcBuy {
+                item_code,
+                quantity,
+            } => self.can_npc_buy(item_code, *quantity)?,
+            Action::NpcSell {
+                item_code,
+                quantity,
+            } => self.can_npc_sell(item_code, *quantity)?,
+            Action::GiveItem { items, character } => self.can_give_item(items, character)?,
+            Action::GiveGold {
+                quantity,
+                character,
+            } => self.can_give_gold(*quantity, character)?,
+            Action::GeBuyOrder { id, quantity } => self.can_ge_buy_order(id, *quantity)?,
+            Action::GeCreateOrder {
+                item_code,
+                quantity,
+                price,
+            } => self.can_ge_create_order(item_code, *quantity, *price)?,
+            Action::GeCancelOrder { id } => self.can_ge_cancel_order(id)?,
+        }
+        Ok(())
+    }
+
+    fn perform(&self, action: &Action) -> Result<(), ActionError> {
+        match action {
+            Action::Move { x, y } => {
+                self.r#move(*x, *y)?;
+            }
+            Action::Transition => {
+                self.transition()?;
+            }
+            Action::Fight { participants } => {
+                self.fight(*participants)?;
+            }
+            Action::Rest => {
+                self.rest()?;
+            }
+            Action::Gather => {
+                self.gather()?;
+            }
+            Action::Craft {
+                item_code,
+                quantity,
+            } => {
+                self.craft(item_code, *quantity)?;
+            }
+            Action::Recycle {
+                item_code,
+                quantity,
+            } => {
+                self.recycle(item_code, *quantity)?;
+            }
+            Action::Delete {
+                item_code,
+                quantity,
+            } => {
+                self.delete(item_code, *quantity)?;
+            }
+            Action::DepositItem { items } => self.deposit_item(items)?,
+            Action::WithdrawItem { items } => self.withdraw_item(items)?,
+            Action::DepositGold { quantity } => {
+                self.deposit_gold(*quantity)?;
+            }
+            Action::WithdrawGold { quantity } => {
+                self.withdraw_gold(*quantity)?;
+            }
+            Action::ExpandBank => {
+                self.expand_bank()?;
+            }
+            Action::Equip {
+                item_code,
+                slot,
+                quantity,
+            } => self.equip(item_code, *slot, *quantity)?,
+            Action::Unequip { slot, quantity } => self.unequip(*slot, *quantity)?,
+            Action::UseItem {
+                item_code,
+                quantity,
+            } => self.use_item(item_code, *quantity)?,
+            Action::AcceptTask => {
+                self.accept_task()?;
+            }
+            Action::CancelTask => self.cancel_task()?,
+            Action::TradeTaskItem {
+                item_code,
+                quantity,
+            } => {
+                self.trade_task_item(item_code, *quantity)?;
+            }
+            Action::CompleteTask => {
+                self.complete_task()?;
+            }
+            Action::ExchangeTasksCoins => {
+                self.exchange_tasks_coins()?;
+            }
+            Action::NpcBuy {
+                item_code,
+                quantity,
+            } => {
+                self.npc_buy(item_code, *quantity)?;
+            }
+            Action::NpcSell {
+                item_code,
+                quantity,
+            } => {
+                self.npc_sell(item_code, *quantity)?;
+            }
+            Action::GiveItem { items, character } => self.give_item(items, character)?,
+            Action::GiveGold {
+                quantity,
+                character,
+            } => self.give_gold(*quantity, character)?,
+            Action::GeBuyOrder { id, quantity } => {
+                self.ge_buy_order(id, *quantity)?;
+            }
+            Action::GeCreateOrder {
+                item_code,
+                quantity,
+                price,
+            } => self.ge_create_order(item_code, *quantity, *price)?,
+            Action::GeCancelOrder { id } => {
+                self.ge_cancel_order(id)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Applies the predictable effects of `action` on the projected character and bank.
+    /// Fails if the action cannot be applied to the projected state.
+    fn project(
+        &self,
+        action: &Action,
+        data: &mut CharacterSchema,
+        details: &mut BankSchema,
+        content: &mut Vec<SimpleItemSchema>,
+    ) -> Result<(), ActionError> {
+        match action {
+            Action::Move { x, y } => {
+                data.x = *x;
+                data.y = *y;
+            }
+            Action::Transition => {
+                if let Some(transition) = &self.current_map().interactions().transition {
+                    data.layer = transition.layer;
+                    data.x = transition.x;
+                    data.y = transition.y;
+                }
+            }
+            Action::Rest => data.hp = data.max_hp,
+            Action::Craft {
+                item_code,
+                quantity,
+            } => {
+                let Some(item) = self.items.get(item_code) else {
+                    return Ok(());
+                };
+                item.mats_for(*quantity).iter().for_each(|m| {
+                    remove_item(data, &m.code, m.quantity);
+                });
+                add_item(data, item_code, item.craft_yield() * quantity);
+            }
+            Action::Recycle {
+                item_code,
+                quantity,
+            }
+            | Action::Delete {
+                item_code,
+                quantity,
+            }
+            | Action::NpcSell {
+                item_code,
+                quantity,
+            }
+            | Action::TradeTaskItem {
+                item_code,
+                quantity,
+            } => {
+                remove_item(data, item_code, *quantity);
+            }
+            Action::DepositItem { items } => {
+                items.iter().for_each(|i| {
+                    remove_item(data, &i.code, i.quantity);
+                });
+                deposit_to_bank(content, items);
+            }
+            Action::WithdrawItem { items } => {
+                withdraw_from_bank(content, items);
+                items
+                    .iter()
+                    .for_each(|i| add_item(data, &i.code, i.quantity));
+            }
+            Action::DepositGold { quantity } => {
+                data.gold -= *quantity as i32;
+                details.gold += quantity;
+            }
+            Action::WithdrawGold { quantity } => {
+                data.gold += *quantity as i32;
+                details.gold -= quantity;
+            }
+            Action::ExpandBank => {
+                data.gold -= details.next_expansion_cost as i32;
+                details.slots += BANK_EXTENSION_SIZE;
+            }
+            Action::Equip {
+                item_code,
+                slot,
+                quantity,
+            } => {
+                remove_item(data, item_code, *quantity);
+                *slot_of(data, *slot) = item_code.to_string();
+                set_quantity_in(data, *slot, quantity_in(data, *slot) + quantity);
+                if let Some(item) = self.items.get(item_code) {
+                    data.max_hp += item.health();
+                    data.inventory_max_items += item.inventory_space();
+                }
+            }
+            Action::Unequip { slot, quantity } => {
+                let equiped = slot_of(data, *slot).clone();
+                let Some(remaining) = quantity_in(data, *slot).checked_sub(*quantity) else {
+                    return Err(UnequipError::InsufficientQuantity.into());
+                };
+                if remaining == 0 {
+                    slot_of(data, *slot).clear();
+                    if let Some(item) = self.items.get(&equiped) {
+                        data.max_hp -= item.health();
+                        data.hp = min(data.hp, data.max_hp);
+                        data.inventory_max_items -= item.inventory_space();
+                    }
+                }
+                set_quantity_in(data, *slot, remaining);
+                add_item(data, &equiped, *quantity);
+            }
+            Action::UseItem {
+                item_code,
+                quantity,
+            } => {
+                remove_item(data, item_code, *quantity);
+                if let Some(item) = self.items.get(item_code) {
+                    data.hp = min(data.hp + item.heal() * *quantity as i32, data.max_hp);
+                }
+            }
+            Action::NpcBuy {
+                item_code,
+                quantity,
+            } => {
+                let Some(item) = self.npcs.items.get(item_code) else {
+                    return Ok(());
+                };
+                let price = item.buy_price().unwrap_or(0) * quantity;
+                if item.currency() == GOLD {
+                    data.gold -= price as i32;
+                } else {
+                    remove_item(data, item.currency(), price);
+                }
+                add_item(data, item_code, *quantity);
+            }
+            Action::GiveItem { items, .. } => items.iter().for_each(|i| {
+                remove_item(data, &i.code, i.quantity);
+            }),
+            Action::GiveGold { quantity, .. } => data.gold -= *quantity as i32,
+            Action::Fight { .. }
+            | Action::Gather
+            | Action::AcceptTask
+            | Action::CancelTask
+            | Action::CompleteTask
+            | Action::ExchangeTasksCoins
+            | Action::GeBuyOrder { .. }
+            | Action::GeCreateOrder { .. }
+            | Action::GeCancelOrder { .. } => {}
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        client::{
+            character::error::{
+                ApiErrorResponseSchema, ApiErrorSchema, DepositError, MoveError, NO_PATH_AVAILABLE,
+                RequestError,
+            },
+            server::ServerClient,
+            transport::MockTransport,
+        },
+        clock::{Clock, VirtualClock},
+        entities::Item,
+        fixtures,
+        gear::Slot,
+    };
+    use artifactsmmo_openapi::models::{
+        CharacterMovementResponseSchema, CraftSchema, CraftSkill, InteractionSchema, InventorySlot,
+        ItemSchema, MapContentSchema, MapContentType, MapSchema,
+    };
+    use chrono::{DateTime, TimeDelta, Utc};
+
+    fn character(
+        data: CharacterSchema,
+        transport: Arc<MockTransport>,
+        clock: Arc<VirtualClock>,
+        bank: Vec<SimpleItemSchema>,
+    ) -> CharacterClient {
+        let dagger = Item::new(ItemSchema {
+            code: "copper_dagger".to_owned(),
+            level: 5,
+            craft: Some(Box::new(CraftSchema {
+                skill: Some(CraftSkill::Weaponcrafting),
+                items: Some(vec![SimpleItemSchema {
+                    code: "copper_bar".to_owned(),
+                    quantity: 6,
+                }]),
+                quantity: Some(1),
+                ..Default::default()
+            })),
+            ..Default::default()
+        });
+        let map = |x, r#type, code: &str| MapSchema {
+            x,
+            interactions: InteractionSchema {
+                content: Some(Box::new(MapContentSchema {
+                    r#type,
+                    code: code.to_owned(),
+                })),
+                ..Default::default()
+            }
+            .into(),
+            ..Default::default()
+        };
+        CharacterClient {
+            inner: CharacterRequestHandler::new(
+                Arc::default(),
+                transport,
+                Arc::new(RwLock::new(Arc::new(CharacterSchema {
+                    inventory_max_items: 100,
+                    inventory: Some(
+                        (1..=20)
+                            .map(|slot| InventorySlot {
+                                slot,
+                                code: String::new(),
+                                quantity: 0,
+                            })
+                            .collect(),
+                    ),
+                    ..data
+                }))),
+                Arc::default(),
+                Arc::new(ServerClient::new(Arc::default(), clock)),
+            ),
+            bank: Arc::new(BankClient::new(
+                BankSchema {
+                    slots: 50,
+                    ..Default::default()
+                },
+                bank,
+            )),
+            items: Arc::new(fixtures::items(vec![dagger])),
+            maps: Arc::new(fixtures::maps(vec![
+                map(0, MapContentType::Bank, "bank"),
+                map(1, MapContentType::Workshop, "weaponcrafting"),
+                MapSchema {
+                    x: 2,
+                    ..Default::default()
+                },
+            ])),
+            ..Default::default()
+        }
+    }
+
+    fn moved(x: i32, cooldown_expiration: DateTime<Utc>) -> CharacterMovementResponseSchema {
+        let mut res = CharacterMovementResponseSchema::default();
+        res.data.destination.x = x;
+        res.data.character = CharacterSchema {
+            x,
+            cooldown_expiration: Some(cooldown_expiration.to_rfc3339()),
+            ..Default::default()
+        }
+        .into();
+        res
+    }
+
+    fn no_path_available() -> RequestError {
+        RequestError::ResponseError(ApiErrorResponseSchema {
+            error: ApiErrorSchema {
+                code: NO_PATH_AVAILABLE as u32,
+                message: String::new(),
+            },
+        })
+    }
+
+    #[test]
+    fn validate_reports_failing_step() {
+        let char = CharacterClient::from(CharacterSchema::default());
+        let plan = ActionPlan::new()
+            .then(Action::Rest)
+            .then(Action::Move { x: 1, y: 1 });
+        let err = plan.validate(&char).unwrap_err();
+        assert_eq!(err.step, 1);
+        assert!(matches!(
+            err.source,
+            ActionError::Move(MoveError::MapNotFound)
+        ));
+    }
+
+    #[test]
+    fn projection_cannot_unequip_more_than_equipped() {
+        let mut data = CharacterSchema {
+            utility1_slot: "potion".to_owned(),
+            utility1_slot_quantity: 2,
+            inventory: Some(vec![]),
+            ..Default::default()
+        };
+        let char = CharacterClient::from(data.clone());
+        let (mut details, mut content) = (BankSchema::default(), vec![]);
+        let unequip = |quantity| Action::Unequip {
+            slot: Slot::Utility1,
+            quantity,
+        };
+
+        assert!(matches!(
+            char.project(&unequip(3), &mut data, &mut details, &mut content),
+            Err(ActionError::Unequip(UnequipError::InsufficientQuantity))
+        ));
+        assert!(
+            char.project(&unequip(2), &mut data, &mut details, &mut content)
+                .is_ok()
+        );
+        assert!(data.utility1_slot.is_empty());
+        assert_eq!(data.utility1_slot_quantity, 0);
+    }
+
+    #[test]
+    fn step_pauses_at_cooldowns_and_resumes() {
+        let start = DateTime::from_timestamp(0, 0).unwrap();
+        let clock = Arc::new(VirtualClock::new(start));
+        let transport = Arc::new(MockTransport::new());
+        transport.push(moved(1, start + TimeDelta::seconds(5)));
+        transport.push(moved(2, start + TimeDelta::seconds(12)));
+        let char = character(
+            CharacterSchema::default(),
+            transport.clone(),
+            clock.clone(),
+            vec![],
+        );
+        let mut plan = ActionPlan::new()
+            .then(Action::Move { x: 1, y: 0 })
+            .then(Action::Move { x: 2, y: 0 });
+
+        assert_eq!(
+            plan.step(&char).unwrap(),
+            PlanStatus::Paused(Duration::from_secs(5))
+        );
+        clock.advance(Duration::from_secs(2));
+        assert_eq!(
+            plan.step(&char).unwrap(),
+            PlanStatus::Paused(Duration::from_secs(3))
+        );
+        assert_eq!(transport.requests().len(), 1);
+        assert_eq!(plan.cursor(), 1);
+        clock.advance(Duration::from_secs(3));
+        assert_eq!(plan.step(&char).unwrap(), PlanStatus::Completed);
+        assert_eq!(transport.requests().len(), 2);
+        assert!(plan.is_done());
+        assert_eq!(plan.step(&char).unwrap(), PlanStatus::Completed);
+        assert_eq!(char.position().1, 2);
+    }
+
+    #[test]
+    fn execute_waits_for_the_cooldowns_on_the_clock() {
+        let start = DateTime::from_timestamp(0, 0).unwrap();
+        let clock = Arc::new(VirtualClock::new(start));
+        let transport = Arc::new(MockTransport::new());
+        transport.push(moved(1, start + TimeDelta::seconds(5)));
+        transport.push(moved(2, start + TimeDelta::seconds(12)));
+        let char = character(
+            CharacterSchema::default(),
+            transport.clone(),
+            clock.clone(),
+            vec![],
+        );
+        let mut plan = ActionPlan::new()
+            .then(Action::Move { x: 1, y: 0 })
+            .then(Action::Move { x: 2, y: 0 });
+
+        plan.execute(&char).unwrap();
+        assert!(plan.is_done());
+        assert_eq!(transport.pending(), 0);
+        assert_eq!(clock.now(), start + TimeDelta::seconds(5));
+        assert_eq!(char.position().1, 2);
+    }
+
+    #[test]
+    fn execute_reports_the_step_rejected_by_the_server() {
+        let start = DateTime::from_timestamp(0, 0).unwrap();
+        let clock = Arc::new(VirtualClock::new(start));
+        let transport = Arc::new(MockTransport::new());
+        transport.push(moved(1, start));
+        transport.push_error(no_path_available());
+        let char = character(CharacterSchema::default(), transport.clone(), clock, vec![]);
+        let mut plan = ActionPlan::new()
+            .then(Action::Move { x: 1, y: 0 })
+            .then(Action::Move { x: 2, y: 0 });
+
+        let err = plan.execute(&char).unwrap_err();
+        assert_eq!(err.step, 1);
+        assert!(matches!(
+            err.source,
+            ActionError::Move(MoveError::NoPathAvailable)
+        ));
+        assert_eq!(plan.cursor(), 1);
+        assert!(!plan.is_done());
+    }
+
+    #[test]
+    fn validate_projects_withdraw_craft_and_deposit() {
+        let char = character(
+            CharacterSchema {
+                weaponcrafting_level: 5,
+                ..Default::default()
+            },
+            Arc::new(MockTransport::new()),
+            Arc::default(),
+            vec![SimpleItemSchema {
+                code: "copper_bar".to_owned(),
+                quantity: 6,
+            }],
+        );
+        let bars = [SimpleItemSchema {
+            code: "copper_bar".to_owned(),
+            quantity: 6,
+        }];
+        let dagger = |quantity| {
+            [SimpleItemSchema {
+                code: "copper_dagger".to_owned(),
+                quantity,
+            }]
+        };
+        fn plan<'a>(
+            bars: &'a [SimpleItemSchema],
+            deposit: &'a [SimpleItemSchema],
+        ) -> ActionPlan<'a> {
+            ActionPlan::new()
+                .then(Action::WithdrawItem { items: bars })
+                .then(Action::Move { x: 1, y: 0 })
+                .then(Action::Craft {
+                    item_code: "copper_dagger",
+                    quantity: 1,
+                })
+                .then(Action::Move { x: 0, y: 0 })
+                .then(Action::DepositItem { items: deposit })
+        }
+
+        assert!(plan(&bars, &dagger(1)).validate(&char).is_ok());
+        let err = plan(&bars, &dagger(2)).validate(&char).unwrap_err();
+        assert_eq!(err.step, 4);
+        assert!(matches!(
+            err.source,
+            ActionError::Deposit(DepositError::InsufficientQuantity)
+        ));
+        assert!(
+            char.data()
+                .inventory
+                .iter()
+                .flatten()
+                .all(|s| s.code.is_empty())
+        );
+    }
+}
@@ -0,0 +1,95 @@
+use crate::gear::Slot;
+use artifactsmmo_openapi::models::{CharacterSchema, InventorySlot, SimpleItemSchema};
+
+pub(crate) fn add_item(character: &mut CharacterSchema, item_code: &str, quantity: u32) {
+    let inventory = character.inventory.get_or_insert_with(Vec::new);
+    if let Some(slot) = inventory.iter_mut().find(|s| s.code == item_code) {
+        slot.quantity += quantity as i32;
+    } else if let Some(slot) = inventory.iter_mut().find(|s| s.code.is_empty()) {
+        slot.code = item_code.to_owned();
+        slot.quantity = quantity as i32;
+    } else {
+        inventory.push(InventorySlot {
+            slot: inventory.len() as i32,
+            code: item_code.to_owned(),
+            quantity: quantity as i32,
+        });
+    }
+}
+
+pub(crate) fn remove_item(character: &mut CharacterSchema, item_code: &str, quantity: u32) -> bool {
+    let Some(slot) = character
+        .inventory
+        .iter_mut()
+        .flatten()
+        .find(|s| s.code == item_code && s.quantity >= quantity as i32)
+    else {
+        return false;
+    };
+    slot.quantity -= quantity as i32;
+    if slot.quantity == 0 {
+        slot.code.clear();
+    }
+    true
+}
+
+pub(crate) fn slot_of(character: &mut CharacterSchema, slot: Slot) -> &mut String {
+    match slot {
+        Slot::Weapon => &mut character.weapon_slot,
+        Slot::Shield => &mut character.shield_slot,
+        Slot::Helmet => &mut character.helmet_slot,
+        Slot::BodyArmor => &mut character.body_armor_slot,
+        Slot::LegArmor => &mut character.leg_armor_slot,
+        Slot::Boots => &mut character.boots_slot,
+        Slot::Ring1 => &mut character.ring1_slot,
+        Slot::Ring2 => &mut character.ring2_slot,
+        Slot::Amulet => &mut character.amulet_slot,
+        Slot::Artifact1 => &mut character.artifact1_slot,
+        Slot::Artifact2 => &mut character.artifact2_slot,
+        Slot::Artifact3 => &mut character.artifact3_slot,
+        Slot::Utility1 => &mut character.utility1_slot,
+        Slot::Utility2 => &mut character.utility2_slot,
+        Slot::Bag => &mut character.bag_slot,
+        Slot::Rune => &mut character.rune_slot,
+    }
+}
+
+pub(crate) fn quantity_in(character: &mut CharacterSchema, slot: Slot) -> u32 {
+    match slot {
+        Slot::Utility1 => character.utility1_slot_quantity,
+        Slot::Utility2 => character.utility2_slot_quantity,
+        _ => (!slot_of(character, slot).is_empty()) as u32,
+    }
+}
+
+pub(crate) fn set_quantity_in(character: &mut CharacterSchema, slot: Slot, quantity: u32) {
+    match slot {
+        Slot::Utility1 => character.utility1_slot_quantity = quantity,
+        Slot::Utility2 => character.utility2_slot_quantity = quantity,
+        _ => {}
+    }
+}
+
+pub(crate) fn deposit_to_bank(content: &mut Vec<SimpleItemSchema>, items: &[SimpleItemSchema]) {
+    for item in items {
+        if let Some(slot) = content.iter_mut().find(|i| i.code == item.code) {
+            slot.quantity += item.quantity;
+        } else {
+            content.push(item.clone());
+        }
+    }
+}
+
+pub(crate) fn withdraw_from_bank(
+    content: &mut Vec<SimpleItemSchema>,
+    items: &[SimpleItemSchema],
+) -> bool {
+    for item in items {
+        match content.iter_mut().find(|i| i.code == item.code) {
+            Some(slot) if slot.quantity >= item.quantity => slot.quantity -= item.quantity,
+            _ => return false,
+        }
+    }
+    content.retain(|i| i.quantity > 0);
+    true
+}
@@ -3,9 +3,16 @@ use crate::{
     AccountClient, DropSchemas, SimpleItemSchemas,
     client::{
         bank::BankClient,
-        character::{HasCharacterData, action::Action, error::RequestError},
+        character::{
+            HasCharacterData,
+            action::Action,
+            error::{CHARACTER_ON_COOLDOWN, RequestError},
+        },
+        retry::RetryRule,
         server::ServerClient,
+        transport::{MockTransport, Transport},
     },
+    clock::Clock,
     consts::BANK_EXTENSION_SIZE,
     entities::Map,
     gear::Slot,
@@ -24,23 +31,25 @@ use artifactsmmo_openapi::models::{
     SkillResponseSchema, TaskCancelledResponseSchema, TaskResponseSchema, TaskSchema,
     TaskTradeResponseSchema, TaskTradeSchema, UseItemResponseSchema,
 };
-use chrono::Utc;
 use downcast_rs::{Downcast, impl_downcast};
 use itertools::Itertools;
 use log::{debug, error, info, warn};
 use std::{
     cmp::Ordering,
     sync::{Arc, RwLockWriteGuard},
-    thread::sleep,
     time::Duration,
 };
 
+type BankContentGuard<'a> = RwLockWriteGuard<'a, Arc<Vec<SimpleItemSchema>>>;
+type BankDetailsGuard<'a> = RwLockWriteGuard<'a, Arc<BankSchema>>;
+
 /// First layer of abstraction around the character API.
 /// It is responsible for handling the character action requests responce and errors
 /// by updating character and bank data, and retrying requests in case of errors.
-#[derive(Default, Debug)]
+#[derive(Debug)]
 pub(crate) struct CharacterRequestHandler {
     api: Arc<ArtifactApi>,
+    transport: Arc<dyn Transport>,
     account: Arc<AccountClient>,
     data: CharacterData,
     bank: Arc<BankClient>,
@@ -50,12 +59,14 @@ pub(crate) struct CharacterRequestHandler {
 impl CharacterRequestHandler {
     pub fn new(
         api: Arc<ArtifactApi>,
+        transport: Arc<dyn Transport>,
         data: CharacterData,
         account: Arc<AccountClient>,
         server: Arc<ServerClient>,
     ) -> Self {
         Self {
             api,
+            transport,
             data,
             bank: account.bank.clone(),
             account,
@@ -63,11 +74,67 @@ impl CharacterRequestHandler {
         }
     }
 
+    /// Handler holding `data` without sending any request, used to run the `can_*`
+    /// checks against a projected character state.
+    pub fn detached(data: CharacterData) -> Self {
+        Self {
+            transport: Arc::new(MockTransport::new()),
+            data,
+            ..Default::default()
+        }
+    }
+
+    /// Sends `action`, retrying it according to the account `RetryPolicy`.
     fn request_action(&self, action: Action) -> Result<Box<dyn ResponseSchema>, RequestError> {
-        let mut bank_content: Option<RwLockWriteGuard<'_, Arc<Vec<SimpleItemSchema>>>> = None;
-        let mut bank_details: Option<RwLockWriteGuard<'_, Arc<BankSchema>>> = None;
+        let policy = self.account.retry_policy();
+        let mut attempt = 1;
+        loop {
+            let error = match self.send_action(&action) {
+                Ok(res) => return Ok(res),
+                Err(e) => e,
+            };
+            self.handle_request_error(&action, &error);
+            let rule = policy.rule_for(&error);
+            if rule == RetryRule::Never {
+                return Err(error);
+            }
+            if attempt >= policy.attempts() {
+                return Err(RequestError::RetriesExhausted {
+                    attempts: attempt,
+                    last: Box::new(error),
+                });
+            }
+            if rule == RetryRule::Backoff {
+                let delay = policy.jittered_delay_for(attempt);
+                warn!(
+                    "{}: retrying in {}.{}s (attempt {}/{})",
+                    self.name(),
+                    delay.as_secs(),
+                    delay.subsec_millis(),
+                    attempt + 1,
+                    policy.attempts()
+                );
+                self.clock().sleep(delay);
+            }
+            attempt += 1;
+        }
+    }
 
+    fn send_action(&self, action: &Action) -> Result<Box<dyn ResponseSchema>, RequestError> {
         self.wait_for_cooldown();
+        let (bank_content, bank_details) = self.bank_guards(action);
+        let res = self.transport.request(&self.name(), action)?;
+        self.apply_response(res.as_ref(), bank_content, bank_details);
+        Ok(res)
+    }
+
+    /// Locks the parts of the bank modified by `action`.
+    pub(crate) fn bank_guards(
+        &self,
+        action: &Action,
+    ) -> (Option<BankContentGuard<'_>>, Option<BankDetailsGuard<'_>>) {
+        let mut bank_content = None;
+        let mut bank_details = None;
         if action.is_deposit_item() || action.is_withdraw_item() {
             bank_content = Some(
                 self.bank
@@ -84,109 +151,98 @@ impl CharacterRequestHandler {
                     .expect("bank_details to be writable"),
             );
         }
-        match action.request(&self.name(), &self.api) {
-            Ok(res) => {
-                info!("{}", res.to_string());
-                if let Some(res) = res.downcast_ref::<CharacterFightResponseSchema>() {
-                    res.data.characters.iter().for_each(|c| {
-                        if let Some(char_client) = self.account.get_character_by_name(&c.name) {
-                            char_client.update_data(c.clone());
-                        }
-                    });
-                } else {
-                    self.update_data(res.character().clone());
-                }
-                if let Some(res) = res.downcast_ref::<BankItemTransactionResponseSchema>()
-                    && let Some(mut content) = bank_content
-                {
-                    *content = res.data.bank.clone().into();
-                } else if let Some(res) = res.downcast_ref::<BankGoldTransactionResponseSchema>()
-                    && let Some(mut details) = bank_details
-                {
-                    let mut new_details = (*(*details)).clone();
-                    new_details.gold = res.data.bank.quantity;
-                    *details = Arc::new(new_details);
-                } else if res
-                    .downcast_ref::<BankExtensionTransactionResponseSchema>()
-                    .is_some()
-                    && let Some(mut details) = bank_details
-                {
-                    let mut new_details = (*(*details)).clone();
-                    new_details.slots += BANK_EXTENSION_SIZE;
-                    *details = Arc::new(new_details);
-                };
-                if let Some(res) = res.downcast_ref::<GiveItemResponseSchema>()
-                    && let Some(c) = self
-                        .account
-                        .get_character_by_name(&res.data.receiver_character.name)
-                {
-                    c.update_data(*res.data.receiver_character.clone());
-                }
-                if let Some(res) = res.downcast_ref::<GiveGoldResponseSchema>()
-                    && let Some(c) = self
-                        .account
-                        .get_character_by_name(&res.data.receiver_character.name)
-                {
-                    c.update_data(*res.data.receiver_character.clone());
+        (bank_content, bank_details)
+    }
+
+    /// Updates the character, the other characters involved and the bank with
+    /// the data returned by a successful action.
+    pub(crate) fn apply_response(
+        &self,
+        res: &dyn ResponseSchema,
+        bank_content: Option<BankContentGuard<'_>>,
+        bank_details: Option<BankDetailsGuard<'_>>,
+    ) {
+        info!("{}", res.to_string());
+        if let Some(res) = res.downcast_ref::<CharacterFightResponseSchema>() {
+            res.data.characters.iter().for_each(|c| {
+                if let Some(char_client) = self.account.get_character_by_name(&c.name) {
+                    char_client.update_data(c.clone());
                 }
-                Ok(res)
-            }
-            Err(e) => {
-                drop(bank_content);
-                drop(bank_details);
-                self.handle_request_error(action, e)
-            }
+            });
+        } else {
+            self.update_data(res.character().clone());
+        }
+        if let Some(res) = res.downcast_ref::<BankItemTransactionResponseSchema>()
+            && let Some(mut content) = bank_content
+        {
+            *content = res.data.bank.clone().into();
+        } else if let Some(res) = res.downcast_ref::<BankGoldTransactionResponseSchema>()
+            && let Some(mut details) = bank_details
+        {
+            let mut new_details = (*(*details)).clone();
+            new_details.gold = res.data.bank.quantity;
+            *details = Arc::new(new_details);
+        } else if res
+            .downcast_ref::<BankExtensionTransactionResponseSchema>()
+            .is_some()
+            && let Some(mut details) = bank_details
+        {
+            let mut new_details = (*(*details)).clone();
+            new_details.slots += BANK_EXTENSION_SIZE;
+            *details = Arc::new(new_details);
+        };
+        if let Some(res) = res.downcast_ref::<GiveItemResponseSchema>()
+            && let Some(c) = self
+                .account
+                .get_character_by_name(&res.data.receiver_character.name)
+        {
+            c.update_data(*res.data.receiver_character.clone());
+        }
+        if let Some(res) = res.downcast_ref::<GiveGoldResponseSchema>()
+            && let Some(c) = self
+                .account
+                .get_character_by_name(&res.data.receiver_character.name)
+        {
+            c.update_data(*res.data.receiver_character.clone());
         }
     }
 
-    fn handle_request_error(
-        &self,
-        action: Action,
-        error: RequestError,
-    ) -> Result<Box<dyn ResponseSchema>, RequestError> {
+    fn handle_request_error(&self, action: &Action, error: &RequestError) {
+        self.log_request_error(action, error);
+        if let Some(recovery) = Recovery::for_error(error) {
+            self.recover(recovery);
+        }
+    }
+
+    pub(crate) fn log_request_error(&self, action: &Action, error: &RequestError) {
         error!(
             "{}: failed to request action '{}': {}",
             self.name(),
             action,
             error
         );
-        match error {
-            RequestError::ResponseError(ref res) => {
-                if res.error.code == 499 {
-                    error!(
-                        "{}: code 499 received, resyncronizing server time",
-                        self.name()
-                    );
-                    self.server.update_offset();
-                    return self.request_action(action);
-                }
-                if res.error.code == 500 || res.error.code == 520 {
-                    error!(
-                        "{}: unknown error ({}), retrying in 10 secondes.",
-                        self.name(),
-                        res.error.code
-                    );
-                    sleep(Duration::from_secs(10));
-                    return self.request_action(action);
-                }
-            }
-            RequestError::Reqwest(ref req) => {
-                if req.is_timeout() {
-                    error!("{}: request timed-out, retrying...", self.name());
-                    return self.request_action(action);
-                }
+    }
+
+    /// Blocks while resynchronizing the state that caused a request to fail.
+    pub(crate) fn recover(&self, recovery: Recovery) {
+        match recovery {
+            Recovery::ServerTime => {
+                error!(
+                    "{}: code 499 received, resyncronizing server time",
+                    self.name()
+                );
+                self.server.update_offset();
             }
-            RequestError::Serde(_) | RequestError::Io(_) | RequestError::DowncastError => {
+            Recovery::CharacterData => {
                 warn!("{}: refreshing data", self.name());
                 self.refresh_data()
             }
         }
-        Err(error)
     }
 
     fn wait_for_cooldown(&self) {
         if let Some(expiration) = self.cooldown_expiration() {
-            let late = Utc::now() - expiration;
+            let late = self.clock().now() - expiration;
             if late.num_seconds() > 1 {
                 warn!("{}: is late by {}s", self.name(), late.num_seconds())
             }
@@ -201,12 +257,16 @@ impl CharacterRequestHandler {
             s.as_secs(),
             s.subsec_millis()
         );
-        sleep(s);
+        self.clock().sleep(s);
+    }
+
+    pub fn clock(&self) -> Arc<dyn Clock> {
+        self.server.clock()
     }
 
     pub fn remaining_cooldown(&self) -> Duration {
         if let Some(exp) = self.cooldown_expiration() {
-            let synced = Utc::now() - *self.server.server_offset.read().unwrap();
+            let synced = self.clock().now() - *self.server.server_offset.read().unwrap();
             if synced.cmp(&exp.to_utc()) == Ordering::Less {
                 return (exp.to_utc() - synced).to_std().unwrap();
             }
@@ -519,6 +579,50 @@ impl CharacterRequestHandler {
     }
 }
 
+/// Resynchronization needed after a failed request.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Recovery {
+    /// The server rejected the action because of a cooldown the local clock
+    /// thought was over.
+    ServerTime,
+    /// The response could not be decoded, the local character data may be stale.
+    CharacterData,
+}
+
+impl Recovery {
+    pub(crate) fn for_error(error: &RequestError) -> Option<Self> {
+        match error {
+            RequestError::ResponseError(res)
+                if res.error.code as isize == CHARACTER_ON_COOLDOWN =>
+            {
+                Some(Self::ServerTime)
+            }
+            RequestError::Serde(_) | RequestError::Io(_) | RequestError::DowncastError => {
+                Some(Self::CharacterData)
+            }
+            RequestError::ResponseError(_)
+            | RequestError::Reqwest(_)
+            | RequestError::Transport(_)
+            | RequestError::Offline
+            | RequestError::RetriesExhausted { .. } => None,
+        }
+    }
+}
+
+impl Default for CharacterRequestHandler {
+    fn default() -> Self {
+        let api = Arc::new(ArtifactApi::default());
+        Self {
+            transport: api.clone(),
+            api,
+            account: Default::default(),
+            data: Default::default(),
+            bank: Default::default(),
+            server: Default::default(),
+        }
+    }
+}
+
 impl HasCharacterData for CharacterRequestHandler {
     fn data(&self) -> Arc<CharacterSchema> {
         self.data.read().unwrap().clone()
@@ -536,7 +640,7 @@ impl HasCharacterData for CharacterRequestHandler {
     }
 }
 
-pub trait ResponseSchema: Downcast {
+pub trait ResponseSchema: Downcast + Send {
     fn character(&self) -> &CharacterSchema;
     fn to_string(&self) -> String;
 }
@@ -926,3 +1030,113 @@ impl<T: ResponseSchema + 'static> From<T> for Box<dyn ResponseSchema> {
         Box::new(value)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        client::{
+            character::error::{
+                ACTION_ALREADY_IN_PROGRESS, ApiErrorResponseSchema, ApiErrorSchema,
+                ENTITY_NOT_FOUND_ON_MAP, FightError,
+            },
+            retry::RetryPolicy,
+        },
+        clock::VirtualClock,
+    };
+    use chrono::{DateTime, TimeDelta};
+    use std::sync::RwLock;
+
+    #[test]
+    fn cooldown_is_measured_with_the_server_clock() {
+        let start = DateTime::from_timestamp(0, 0).unwrap();
+        let clock = Arc::new(VirtualClock::new(start));
+        let data = CharacterSchema {
+            cooldown_expiration: Some((start + TimeDelta::seconds(30)).to_rfc3339()),
+            ..Default::default()
+        };
+        let handler = CharacterRequestHandler::new(
+            Arc::default(),
+            Arc::new(MockTransport::new()),
+            Arc::new(RwLock::new(Arc::new(data))),
+            Arc::default(),
+            Arc::new(ServerClient::new(Arc::default(), clock.clone())),
+        );
+
+        assert_eq!(handler.remaining_cooldown(), Duration::from_secs(30));
+        handler.wait_for_cooldown();
+        assert_eq!(clock.now(), start + TimeDelta::seconds(30));
+        assert!(handler.remaining_cooldown().is_zero());
+    }
+
+    fn response_error(code: isize) -> RequestError {
+        RequestError::ResponseError(ApiErrorResponseSchema {
+            error: ApiErrorSchema {
+                code: code as u32,
+                message: String::new(),
+            },
+        })
+    }
+
+    fn handler_with(
+        transport: Arc<MockTransport>,
+        policy: RetryPolicy,
+        clock: Arc<VirtualClock>,
+    ) -> CharacterRequestHandler {
+        let account = Arc::new(AccountClient::default());
+        account.set_retry_policy(policy);
+        CharacterRequestHandler::new(
+            Arc::default(),
+            transport,
+            Arc::new(RwLock::new(Arc::new(CharacterSchema::default()))),
+            account,
+            Arc::new(ServerClient::new(Arc::default(), clock)),
+        )
+    }
+
+    #[test]
+    fn game_errors_are_not_retried() {
+        let start = DateTime::from_timestamp(0, 0).unwrap();
+        let clock = Arc::new(VirtualClock::new(start));
+        let transport = Arc::new(MockTransport::new());
+        transport.push_error(response_error(ENTITY_NOT_FOUND_ON_MAP));
+        transport.push_error(response_error(ENTITY_NOT_FOUND_ON_MAP));
+        let handler = handler_with(transport.clone(), RetryPolicy::default(), clock.clone());
+
+        assert!(matches!(
+            handler.request_fight(None),
+            Err(RequestError::ResponseError(_))
+        ));
+        assert_eq!(transport.requests().len(), 1);
+        assert_eq!(clock.now(), start);
+    }
+
+    #[test]
+    fn exhausted_retries_are_mapped_to_the_action_error() {
+        let clock = Arc::new(VirtualClock::default());
+        let transport = Arc::new(MockTransport::new());
+        transport.push_error(response_error(ENTITY_NOT_FOUND_ON_MAP));
+        transport.push_error(response_error(ACTION_ALREADY_IN_PROGRESS));
+        transport.push_error(response_error(ACTION_ALREADY_IN_PROGRESS));
+        let handler = handler_with(
+            transport.clone(),
+            RetryPolicy::default().max_attempts(2),
+            clock,
+        );
+
+        assert!(matches!(
+            FightError::from(handler.request_fight(None).unwrap_err()),
+            FightError::NoMonsterOnMap
+        ));
+        let error = handler.request_fight(None).unwrap_err();
+        assert!(matches!(
+            error,
+            RequestError::RetriesExhausted { attempts: 2, .. }
+        ));
+        assert!(matches!(
+            FightError::from(error),
+            FightError::MonsterIsNotABoss
+        ));
+        assert_eq!(transport.requests().len(), 3);
+    }
+}
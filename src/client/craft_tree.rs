@@ -0,0 +1,308 @@
+use crate::{Code, Level, Skill, entities::Item, items::ItemSource};
+use artifactsmmo_openapi::models::SimpleItemSchema;
+use itertools::Itertools;
+use std::collections::HashMap;
+
+/// Recursive breakdown of the materials needed to obtain a quantity of an item.
+///
+/// Each node holds the quantity of its item still needed, where it can be
+/// obtained from and, for craftable items, the skill and level required along
+/// with the sub-trees of its materials. Materials are computed for the number
+/// of crafts needed, each craft yielding `Item::craft_yield` items.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CraftTree {
+    pub item: Item,
+    pub quantity: u32,
+    pub sources: Vec<ItemSource>,
+    pub skill: Option<Skill>,
+    pub level: u32,
+    pub mats: Vec<CraftTree>,
+    /// Materials of the recipe missing from the game data, which could not be
+    /// broken down.
+    pub unknown_mats: Vec<SimpleItemSchema>,
+}
+
+impl CraftTree {
+    pub(crate) fn build(
+        item: Item,
+        quantity: u32,
+        get: &impl Fn(&str) -> Option<Item>,
+        sources_of: &impl Fn(&str) -> Vec<ItemSource>,
+    ) -> Self {
+        let mut mats = vec![];
+        let mut unknown_mats = vec![];
+        if quantity > 0 {
+            for mat in item.mats_for(quantity.div_ceil(item.craft_yield())) {
+                match get(&mat.code) {
+                    Some(i) => mats.push(Self::build(i, mat.quantity, get, sources_of)),
+                    None => unknown_mats.push(mat),
+                }
+            }
+        }
+        Self {
+            sources: sources_of(item.code()),
+            skill: item.skill_to_craft(),
+            level: item.level(),
+            quantity,
+            mats,
+            unknown_mats,
+            item,
+        }
+    }
+
+    pub fn code(&self) -> &str {
+        self.item.code()
+    }
+
+    /// Returns `true` if the item of this node needs to be crafted.
+    pub fn is_crafted(&self) -> bool {
+        !self.mats.is_empty() || !self.unknown_mats.is_empty()
+    }
+
+    /// Returns the number of craft actions needed to obtain the quantity of
+    /// this node.
+    pub fn craft_count(&self) -> u32 {
+        if !self.is_crafted() {
+            return 0;
+        }
+        self.quantity.div_ceil(self.item.craft_yield())
+    }
+
+    /// Returns the materials, merged by code, missing from the game data
+    /// anywhere in the tree. They are not part of `raw_materials`.
+    pub fn unknown_materials(&self) -> Vec<SimpleItemSchema> {
+        let mut unknown = vec![];
+        self.visit(&mut |node| {
+            node.unknown_mats
+                .iter()
+                .for_each(|m| merge_into(&mut unknown, &m.code, m.quantity))
+        });
+        unknown
+    }
+
+    /// Returns the raw materials, merged by code, needed to obtain the item.
+    pub fn raw_materials(&self) -> Vec<SimpleItemSchema> {
+        let mut raw = vec![];
+        self.visit(&mut |node| {
+            if !node.is_crafted() && node.quantity > 0 {
+                merge_into(&mut raw, node.code(), node.quantity);
+            }
+        });
+        raw
+    }
+
+    /// Returns the quantity of every item below the root, intermediate crafts
+    /// included, merged by code.
+    pub fn totals(&self) -> Vec<SimpleItemSchema> {
+        let mut totals = vec![];
+        self.mats.iter().for_each(|mat| {
+            mat.visit(&mut |node| {
+                if node.quantity > 0 {
+                    merge_into(&mut totals, node.code(), node.quantity);
+                }
+            })
+        });
+        totals
+    }
+
+    /// Returns the craft actions to perform, as item codes and number of
+    /// crafts, ordered so that every item comes after its materials.
+    pub fn crafts(&self) -> Vec<SimpleItemSchema> {
+        let mut crafts = vec![];
+        self.visit(&mut |node| {
+            if node.is_crafted() {
+                merge_into(&mut crafts, node.code(), node.craft_count());
+            }
+        });
+        crafts
+    }
+
+    /// Returns the tree left once the items already held are used.
+    ///
+    /// `held` returns the quantity available for an item code, typically the
+    /// sum of the character inventory and the bank content. Held items are
+    /// consumed top-down, so that an intermediate craft already held spares
+    /// the materials it is made of.
+    pub fn reduced_by(&self, held: impl Fn(&str) -> u32) -> CraftTree {
+        let mut budget = HashMap::new();
+        self.reduce(self.quantity, &held, &mut budget)
+    }
+
+    fn reduce(
+        &self,
+        quantity: u32,
+        held: &impl Fn(&str) -> u32,
+        budget: &mut HashMap<String, u32>,
+    ) -> CraftTree {
+        let available = budget
+            .entry(self.code().to_owned())
+            .or_insert_with(|| held(self.code()));
+        let taken = quantity.min(*available);
+        *available -= taken;
+        let remaining = quantity - taken;
+        let mut mats = vec![];
+        let mut unknown_mats = vec![];
+        if remaining > 0 {
+            for mat in self
+                .item
+                .mats_for(remaining.div_ceil(self.item.craft_yield()))
+            {
+                match self.mats.iter().find(|node| node.code() == mat.code) {
+                    Some(node) => mats.push(node.reduce(mat.quantity, held, budget)),
+                    None => unknown_mats.push(mat),
+                }
+            }
+        }
+        CraftTree {
+            item: self.item.clone(),
+            quantity: remaining,
+            sources: self.sources.clone(),
+            skill: self.skill,
+            level: self.level,
+            mats,
+            unknown_mats,
+        }
+    }
+
+    /// Calls `f` on every node, materials before the item they craft.
+    fn visit(&self, f: &mut impl FnMut(&CraftTree)) {
+        self.mats.iter().for_each(|mat| mat.visit(f));
+        f(self)
+    }
+}
+
+fn merge_into(items: &mut Vec<SimpleItemSchema>, code: &str, quantity: u32) {
+    if let Some(item) = items.iter_mut().find(|i| i.code == code) {
+        item.quantity += quantity;
+    } else {
+        items.push(SimpleItemSchema {
+            code: code.to_owned(),
+            quantity,
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use artifactsmmo_openapi::models::{CraftSchema, ItemSchema};
+
+    fn item(code: &str, mats: &[(&str, u32)]) -> Item {
+        item_yielding(code, mats, 1)
+    }
+
+    fn item_yielding(code: &str, mats: &[(&str, u32)], craft_yield: i32) -> Item {
+        Item::new(ItemSchema {
+            code: code.to_string(),
+            level: 1,
+            r#type: "resource".to_string(),
+            craft: (!mats.is_empty()).then(|| {
+                Box::new(CraftSchema {
+                    items: Some(
+                        mats.iter()
+                            .map(|(code, quantity)| SimpleItemSchema {
+                                code: code.to_string(),
+                                quantity: *quantity,
+                            })
+                            .collect(),
+                    ),
+                    quantity: Some(craft_yield),
+                    ..Default::default()
+                })
+            }),
+            ..Default::default()
+        })
+    }
+
+    fn tree(code: &str, quantity: u32) -> CraftTree {
+        let items = [
+            item("copper_ring", &[("copper_bar", 6), ("copper_ore", 2)]),
+            item("copper_bar", &[("copper_ore", 10)]),
+            item("copper_ore", &[]),
+        ]
+        .into_iter()
+        .map(|i| (i.code().to_owned(), i))
+        .collect::<HashMap<_, _>>();
+        CraftTree::build(
+            items[code].clone(),
+            quantity,
+            &|code| items.get(code).cloned(),
+            &|_| vec![],
+        )
+    }
+
+    #[test]
+    fn raw_materials_are_merged_across_levels() {
+        let tree = tree("copper_ring", 2);
+        assert_eq!(
+            tree.raw_materials(),
+            vec![SimpleItemSchema {
+                code: "copper_ore".to_string(),
+                quantity: 124,
+            }]
+        );
+        assert_eq!(
+            tree.crafts(),
+            vec![
+                SimpleItemSchema {
+                    code: "copper_bar".to_string(),
+                    quantity: 12,
+                },
+                SimpleItemSchema {
+                    code: "copper_ring".to_string(),
+                    quantity: 2,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn held_items_are_subtracted_top_down() {
+        let tree = tree("copper_ring", 2).reduced_by(|code| match code {
+            "copper_bar" => 5,
+            "copper_ore" => 20,
+            _ => 0,
+        });
+        assert_eq!(
+            tree.raw_materials(),
+            vec![SimpleItemSchema {
+                code: "copper_ore".to_string(),
+                quantity: 54,
+            }]
+        );
+        assert_eq!(tree.mats[0].quantity, 7);
+    }
+
+    #[test]
+    fn materials_account_for_the_craft_yield() {
+        let feather = item("feather", &[]);
+        let arrow = item_yielding("arrow", &[("feather", 2), ("glue", 1)], 5);
+        let tree = CraftTree::build(
+            arrow,
+            12,
+            &|code| (code == "feather").then(|| feather.clone()),
+            &|_| vec![],
+        );
+        assert_eq!(
+            tree.crafts(),
+            vec![SimpleItemSchema {
+                code: "arrow".to_string(),
+                quantity: 3,
+            }]
+        );
+        assert_eq!(
+            tree.raw_materials(),
+            vec![SimpleItemSchema {
+                code: "feather".to_string(),
+                quantity: 6,
+            }]
+        );
+        assert_eq!(
+            tree.unknown_materials(),
+            vec![SimpleItemSchema {
+                code: "glue".to_string(),
+                quantity: 3,
+            }]
+        );
+    }
+}
@@ -1,3 +1,4 @@
+use crate::cache::CacheError;
 use std::error::Error as StdError;
 use thiserror::Error;
 
@@ -5,4 +6,21 @@ use thiserror::Error;
 pub enum ClientError {
     #[error("API Error: {0}")]
     Api(Box<dyn StdError + Send + Sync>),
+    #[error("HTTP client error: {0}")]
+    Http(#[from] reqwest::Error),
+    #[error("`{key}` is not cached: {source}")]
+    NotCached {
+        key: &'static str,
+        source: CacheError,
+    },
+    #[error("client is offline")]
+    Offline,
+    #[error("loading of `{0}` panicked")]
+    LoaderPanicked(&'static str),
+}
+
+impl ClientError {
+    pub(crate) fn api(error: impl StdError + Send + Sync + 'static) -> Self {
+        Self::Api(Box::new(error))
+    }
 }
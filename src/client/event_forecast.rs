@@ -0,0 +1,229 @@
+use crate::entities::{ActiveEvent, Event};
+use chrono::{DateTime, TimeDelta, Utc};
+use std::collections::HashMap;
+
+/// Weight, in spawns, given to the rate defined by the game against the
+/// observed spawn history.
+const PRIOR_SPAWNS: f32 = 1.0;
+
+/// Estimated availability of an event's content.
+#[derive(Debug, Clone, PartialEq)]
+pub struct EventForecast {
+    pub event: Event,
+    /// Time the forecast was made at.
+    pub at: DateTime<Utc>,
+    /// Expiration of the event if it is currently active.
+    pub active_until: Option<DateTime<Utc>>,
+    /// Estimated chance of the event spawning each minute.
+    pub spawn_rate: f32,
+    pub windows: Vec<ForecastWindow>,
+}
+
+/// Availability of an event within `within` from the time of the forecast.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ForecastWindow {
+    pub within: TimeDelta,
+    /// Probability of the event being active at some point in the window.
+    pub probability: f32,
+    /// Expected time the event is active during the window.
+    pub uptime: TimeDelta,
+}
+
+impl EventForecast {
+    pub(crate) fn new(
+        event: Event,
+        at: DateTime<Utc>,
+        active_until: Option<DateTime<Utc>>,
+        spawn_rate: f32,
+        horizons: &[TimeDelta],
+    ) -> Self {
+        let mut forecast = Self {
+            event,
+            at,
+            active_until,
+            spawn_rate,
+            windows: vec![],
+        };
+        forecast.windows = horizons
+            .iter()
+            .map(|&within| ForecastWindow {
+                within,
+                probability: forecast.probability_within(within),
+                uptime: forecast.uptime_within(within),
+            })
+            .collect();
+        forecast
+    }
+
+    pub fn is_active(&self) -> bool {
+        self.active_until.is_some()
+    }
+
+    /// Returns the probability of the event being active at some point within
+    /// `within` from now.
+    pub fn probability_within(&self, within: TimeDelta) -> f32 {
+        if self.is_active() {
+            return 1.0;
+        }
+        let minutes = within.num_minutes().max(0) as f32;
+        1.0 - (1.0 - self.spawn_rate.clamp(0.0, 1.0)).powf(minutes)
+    }
+
+    /// Returns the expected time the event is active within `within` from
+    /// now. Each spawn keeps the event active for its duration, so a spawn
+    /// late in the window only counts for the part of it left.
+    pub fn uptime_within(&self, within: TimeDelta) -> TimeDelta {
+        let minutes = within.num_minutes().max(0);
+        let remaining = self
+            .active_until
+            .map_or(0, |until| (until - self.at).num_minutes().clamp(0, minutes));
+        let no_spawn = 1.0 - self.spawn_rate.clamp(0.0, 1.0);
+        let duration = self.event.duration().num_minutes().max(0);
+        // chance of being active each minute after the current occurrence expired
+        let uptime = (1..=minutes - remaining)
+            .map(|t| 1.0 - no_spawn.powf(t.min(duration) as f32))
+            .sum::<f32>()
+            + remaining as f32;
+        TimeDelta::seconds((uptime * 60.0).round() as i64)
+    }
+
+    /// Returns the expected time before the event spawns, or `None` if it is
+    /// never expected to.
+    pub fn expected_wait(&self) -> Option<TimeDelta> {
+        if self.is_active() {
+            return Some(TimeDelta::zero());
+        }
+        (self.spawn_rate > 0.0).then(|| TimeDelta::seconds((60.0 / self.spawn_rate).round() as i64))
+    }
+}
+
+/// Spawns of events observed while refreshing active events.
+#[derive(Debug, Default)]
+pub(crate) struct SpawnHistory {
+    since: Option<DateTime<Utc>>,
+    last: Option<DateTime<Utc>>,
+    /// Time during which a spawn of each event would have been seen.
+    observed: HashMap<String, TimeDelta>,
+    spawns: HashMap<String, Vec<DateTime<Utc>>>,
+}
+
+impl SpawnHistory {
+    /// Records the `active` events returned by a refresh made at `now`.
+    ///
+    /// A spawn is only seen if the event is still active at the next refresh,
+    /// so the interval since the previous refresh only counts as observed for
+    /// up to the duration of each of the `events`.
+    pub(crate) fn record(&mut self, now: DateTime<Utc>, active: &[ActiveEvent], events: &[Event]) {
+        let since = *self.since.get_or_insert(now);
+        if let Some(last) = self.last.replace(now) {
+            let interval = (now - last).max(TimeDelta::zero());
+            for event in events {
+                *self
+                    .observed
+                    .entry(event.code().to_owned())
+                    .or_insert(TimeDelta::zero()) += interval.min(event.duration());
+            }
+        }
+        for event in active {
+            let Some(created_at) = event.created_at_time() else {
+                continue;
+            };
+            if created_at < since {
+                continue;
+            }
+            let spawns = self.spawns.entry(event.code().to_owned()).or_default();
+            if !spawns.contains(&created_at) {
+                spawns.push(created_at);
+            }
+        }
+    }
+
+    /// Estimates the chance of `event` spawning each minute, blending the rate
+    /// defined by the game with the spawns observed so far.
+    pub(crate) fn spawn_rate(&self, event: &Event) -> f32 {
+        let prior = event.spawn_rate();
+        if self.since.is_none() {
+            return prior;
+        }
+        if prior <= 0.0 {
+            return 0.0;
+        }
+        let observed_minutes = self
+            .observed
+            .get(event.code())
+            .map_or(0.0, |t| t.num_seconds() as f32 / 60.0);
+        let spawns = self.spawns.get(event.code()).map_or(0, |s| s.len()) as f32;
+        (spawns + PRIOR_SPAWNS) / (observed_minutes + PRIOR_SPAWNS / prior)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use artifactsmmo_openapi::models::EventSchema;
+
+    fn event(rate: i32) -> Event {
+        Event::new(EventSchema {
+            code: "bandit_camp".to_string(),
+            rate,
+            duration: 60,
+            ..Default::default()
+        })
+    }
+
+    #[test]
+    fn probability_grows_with_window() {
+        let forecast = EventForecast::new(
+            event(100),
+            Utc::now(),
+            None,
+            0.01,
+            &[TimeDelta::minutes(10), TimeDelta::minutes(100)],
+        );
+        let short = forecast.windows[0].probability;
+        let long = forecast.windows[1].probability;
+        assert!((short - 0.0956).abs() < 0.001);
+        assert!((long - 0.634).abs() < 0.001);
+        assert_eq!(forecast.windows[0].uptime, TimeDelta::seconds(32));
+        assert_eq!(forecast.expected_wait(), Some(TimeDelta::minutes(100)));
+    }
+
+    #[test]
+    fn active_event_is_up_until_it_expires() {
+        let now = Utc::now();
+        let forecast = EventForecast::new(
+            event(100),
+            now,
+            Some(now + TimeDelta::minutes(5)),
+            0.01,
+            &[TimeDelta::minutes(3), TimeDelta::minutes(10)],
+        );
+        assert_eq!(forecast.windows[0].probability, 1.0);
+        assert_eq!(forecast.windows[0].uptime, TimeDelta::minutes(3));
+        assert_eq!(forecast.windows[1].uptime, TimeDelta::seconds(309));
+    }
+
+    #[test]
+    fn history_without_spawns_lowers_the_rate() {
+        let now = Utc::now();
+        let mut history = SpawnHistory::default();
+        let event = event(100);
+        assert_eq!(history.spawn_rate(&event), 0.01);
+        for minutes in (0..=300).step_by(30) {
+            history.record(now + TimeDelta::minutes(minutes), &[], &[event.clone()]);
+        }
+        let rate = history.spawn_rate(&event);
+        assert!((rate - 1.0 / 400.0).abs() < f32::EPSILON);
+    }
+
+    #[test]
+    fn downtime_only_counts_for_the_event_duration() {
+        let now = Utc::now();
+        let mut history = SpawnHistory::default();
+        let event = event(100);
+        history.record(now, &[], &[event.clone()]);
+        history.record(now + TimeDelta::minutes(300), &[], &[event.clone()]);
+        let rate = history.spawn_rate(&event);
+        assert!((rate - 1.0 / 160.0).abs() < f32::EPSILON);
+    }
+}
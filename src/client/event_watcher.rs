@@ -0,0 +1,283 @@
+use crate::{
+    client::{events::EventsClient, maps::MapsClient},
+    entities::{ActiveEvent, Map},
+};
+use chrono::{DateTime, Utc};
+use itertools::Itertools;
+use log::debug;
+use std::{
+    sync::{
+        Arc, Mutex,
+        atomic::{AtomicBool, Ordering},
+        mpsc::{self, Receiver, RecvTimeoutError, Sender},
+    },
+    thread::{self, JoinHandle},
+    time::Duration,
+};
+
+/// Interval between two refreshes when no active event expires sooner, new
+/// events being unpredictable.
+const DEFAULT_POLL_INTERVAL: Duration = Duration::from_secs(60);
+/// Delay added after an expiration before refreshing, to let the server
+/// remove the event.
+const EXPIRATION_MARGIN: Duration = Duration::from_secs(1);
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum EventNotification {
+    /// An event appeared on `map`, which now holds its content.
+    Spawned {
+        code: String,
+        content_code: String,
+        map: Map,
+    },
+    /// An event expired and `map` got back its previous content.
+    Expired {
+        code: String,
+        content_code: String,
+        map: Map,
+    },
+}
+
+impl EventNotification {
+    pub fn code(&self) -> &str {
+        match self {
+            Self::Spawned { code, .. } | Self::Expired { code, .. } => code,
+        }
+    }
+
+    pub fn content_code(&self) -> &str {
+        match self {
+            Self::Spawned { content_code, .. } | Self::Expired { content_code, .. } => content_code,
+        }
+    }
+
+    pub fn map(&self) -> &Map {
+        match self {
+            Self::Spawned { map, .. } | Self::Expired { map, .. } => map,
+        }
+    }
+}
+
+/// Keeps the active events and the event maps up to date from a background
+/// thread.
+///
+/// Refreshes are scheduled at the expiration of the next active event, or
+/// after the poll interval if none expires sooner. Both are measured with the
+/// clock of the `EventsClient` at the time the watcher starts: with a
+/// `VirtualClock`, the thread is woken each time the clock is moved. Events
+/// are reported expired once their expiration is reached on that clock, even
+/// if the active events could not be refreshed. The thread stops when the
+/// watcher is dropped.
+#[derive(Debug)]
+pub struct EventWatcher {
+    subscribers: Arc<Mutex<Vec<Sender<EventNotification>>>>,
+    stop: Arc<AtomicBool>,
+    wake: Sender<()>,
+    handle: Option<JoinHandle<()>>,
+}
+
+impl EventWatcher {
+    pub fn start(events: Arc<EventsClient>, maps: Arc<MapsClient>) -> Self {
+        Self::with_poll_interval(events, maps, DEFAULT_POLL_INTERVAL)
+    }
+
+    pub fn with_poll_interval(
+        events: Arc<EventsClient>,
+        maps: Arc<MapsClient>,
+        poll_interval: Duration,
+    ) -> Self {
+        let subscribers: Arc<Mutex<Vec<Sender<EventNotification>>>> = Default::default();
+        let stop = Arc::new(AtomicBool::new(false));
+        let (wake, woken) = mpsc::channel();
+        let clock = events.clock();
+        clock.notify_on_change(wake.clone());
+        let subscribers_clone = subscribers.clone();
+        let stop_clone = stop.clone();
+        let mut known = unexpired(events.active(), clock.now());
+        let handle = thread::spawn(move || {
+            loop {
+                events.refresh_active_now();
+                maps.apply_events();
+                let now = clock.now();
+                let active = unexpired(events.active(), now);
+                let notifications = diff(&known, &active);
+                subscribers_clone.lock().unwrap().retain(|subscriber| {
+                    notifications
+                        .iter()
+                        .all(|n| subscriber.send(n.clone()).is_ok())
+                });
+                let wait = next_wake(&active, poll_interval, now);
+                debug!("event watcher: next refresh in {}s", wait.as_secs());
+                known = active;
+                let wake_at = now + wait;
+                // wait in real time, checking the clock again each time it is moved
+                loop {
+                    if stop_clone.load(Ordering::Relaxed) {
+                        return;
+                    }
+                    let remaining = (wake_at - clock.now()).to_std().unwrap_or_default();
+                    if remaining.is_zero() {
+                        break;
+                    }
+                    if let Err(RecvTimeoutError::Disconnected) = woken.recv_timeout(remaining) {
+                        return;
+                    }
+                }
+            }
+        });
+        Self {
+            subscribers,
+            stop,
+            wake,
+            handle: Some(handle),
+        }
+    }
+
+    /// Returns a receiver of the notifications sent from now on.
+    pub fn subscribe(&self) -> Receiver<EventNotification> {
+        let (sender, receiver) = mpsc::channel();
+        self.subscribers.lock().unwrap().push(sender);
+        receiver
+    }
+}
+
+impl Drop for EventWatcher {
+    fn drop(&mut self) {
+        self.stop.store(true, Ordering::Relaxed);
+        let _ = self.wake.send(());
+        if let Some(handle) = self.handle.take() {
+            let _ = handle.join();
+        }
+    }
+}
+
+fn diff(known: &[ActiveEvent], active: &[ActiveEvent]) -> Vec<EventNotification> {
+    let expired =
+        known
+            .iter()
+            .filter(|e| !active.contains(e))
+            .map(|e| EventNotification::Expired {
+                code: e.code().to_owned(),
+                content_code: e.content_code().unwrap_or_default().to_owned(),
+                map: Map::new(e.previous_map().clone()),
+            });
+    let spawned =
+        active
+            .iter()
+            .filter(|e| !known.contains(e))
+            .map(|e| EventNotification::Spawned {
+                code: e.code().to_owned(),
+                content_code: e.content_code().unwrap_or_default().to_owned(),
+                map: Map::new(e.map().clone()),
+            });
+    expired.chain(spawned).collect_vec()
+}
+
+fn unexpired(active: Vec<ActiveEvent>, now: DateTime<Utc>) -> Vec<ActiveEvent> {
+    active
+        .into_iter()
+        .filter(|e| !e.is_expired_at(now))
+        .collect_vec()
+}
+
+fn next_wake(active: &[ActiveEvent], poll_interval: Duration, now: DateTime<Utc>) -> Duration {
+    active
+        .iter()
+        .filter_map(|e| e.expiration_time())
+        .map(|e| (e - now).to_std().unwrap_or_default() + EXPIRATION_MARGIN)
+        .min()
+        .map_or(poll_interval, |wake| wake.min(poll_interval))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{client::server::ServerClient, clock::VirtualClock, fixtures};
+    use artifactsmmo_openapi::models::{ActiveEventSchema, MapSchema};
+
+    fn event(code: &str, x: i32) -> ActiveEvent {
+        ActiveEvent::new(ActiveEventSchema {
+            code: code.to_string(),
+            map: MapSchema {
+                x,
+                ..Default::default()
+            }
+            .into(),
+            previous_map: MapSchema::default().into(),
+            ..Default::default()
+        })
+    }
+
+    #[test]
+    fn diff_reports_spawned_and_expired_events() {
+        let merchant = event("merchant", 1);
+        let boss = event("boss", 2);
+        let notifications = diff(&[merchant.clone()], &[boss.clone()]);
+        assert!(matches!(
+            &notifications[..],
+            [
+                EventNotification::Expired { code: expired, .. },
+                EventNotification::Spawned { code: spawned, map, .. },
+            ] if expired == "merchant" && spawned == "boss" && map.x() == 2
+        ));
+        assert!(diff(&[boss.clone()], &[boss]).is_empty());
+    }
+
+    #[test]
+    fn wake_is_scheduled_with_the_given_time() {
+        let now = DateTime::from_timestamp(0, 0).unwrap();
+        let poll = Duration::from_secs(60);
+        let merchant = ActiveEvent::new(ActiveEventSchema {
+            code: "merchant".to_string(),
+            expiration: (now + Duration::from_secs(20)).to_rfc3339(),
+            ..Default::default()
+        });
+
+        assert_eq!(next_wake(&[], poll, now), poll);
+        assert_eq!(
+            next_wake(&[merchant.clone()], poll, now),
+            Duration::from_secs(20) + EXPIRATION_MARGIN
+        );
+        assert_eq!(
+            next_wake(&[merchant], poll, now + Duration::from_secs(30)),
+            EXPIRATION_MARGIN
+        );
+    }
+
+    #[test]
+    fn subscribers_are_notified_of_spawns_then_expirations() {
+        let start = DateTime::from_timestamp(0, 0).unwrap();
+        let clock = Arc::new(VirtualClock::new(start));
+        let events =
+            fixtures::events_on(Arc::new(ServerClient::new(Arc::default(), clock.clone())));
+        let watcher = EventWatcher::with_poll_interval(
+            events.clone(),
+            Arc::new(fixtures::maps(vec![])),
+            Duration::from_secs(10),
+        );
+        let notifications = watcher.subscribe();
+        events.set_active(vec![ActiveEvent::new(ActiveEventSchema {
+            code: "merchant".to_string(),
+            map: MapSchema {
+                x: 3,
+                ..Default::default()
+            }
+            .into(),
+            previous_map: MapSchema::default().into(),
+            expiration: (start + Duration::from_secs(60)).to_rfc3339(),
+            ..Default::default()
+        })]);
+        let timeout = Duration::from_secs(5);
+
+        clock.advance(Duration::from_secs(10));
+        assert!(matches!(
+            notifications.recv_timeout(timeout),
+            Ok(EventNotification::Spawned { code, map, .. }) if code == "merchant" && map.x() == 3
+        ));
+        clock.advance(Duration::from_secs(60));
+        assert!(matches!(
+            notifications.recv_timeout(timeout),
+            Ok(EventNotification::Expired { code, map, .. }) if code == "merchant" && map.x() == 0
+        ));
+    }
+}
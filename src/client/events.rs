@@ -1,12 +1,16 @@
 use crate::{
-    DataEntity, Persist,
+    Cache, ClientError, CollectionClient, DataEntity, Persist,
+    client::{
+        event_forecast::{EventForecast, SpawnHistory},
+        server::ServerClient,
+    },
+    clock::Clock,
     entities::{ActiveEvent, Event},
 };
 use artifactsmmo_api_wrapper::ArtifactApi;
 use chrono::{DateTime, Duration, Utc};
 use itertools::Itertools;
 use log::debug;
-use sdk_derive::CollectionClient;
 use std::{
     collections::HashMap,
     sync::{Arc, RwLock},
@@ -16,21 +20,31 @@ use std::{
 pub struct EventsClient {
     data: RwLock<HashMap<String, Event>>,
     api: Arc<ArtifactApi>,
+    cache: Arc<Cache>,
+    server: Arc<ServerClient>,
     active: RwLock<Vec<ActiveEvent>>,
     last_refresh: RwLock<DateTime<Utc>>,
+    history: RwLock<SpawnHistory>,
 }
 
 impl EventsClient {
-    pub(crate) fn new(api: Arc<ArtifactApi>) -> Self {
+    pub(crate) fn new(
+        api: Arc<ArtifactApi>,
+        cache: Arc<Cache>,
+        server: Arc<ServerClient>,
+    ) -> Result<Self, ClientError> {
         let events = Self {
             data: Default::default(),
             api,
+            cache,
+            server,
             active: RwLock::new(vec![]),
             last_refresh: RwLock::new(DateTime::<Utc>::MIN_UTC),
+            history: Default::default(),
         };
-        *events.data.write().unwrap() = events.load();
+        *events.data.write().unwrap() = events.load()?;
         events.refresh_active();
-        events
+        Ok(events)
     }
 
     pub fn active(&self) -> Vec<ActiveEvent> {
@@ -38,19 +52,71 @@ impl EventsClient {
     }
 
     pub fn refresh_active(&self) {
-        let now = Utc::now();
-        if Utc::now() - self.last_refresh() <= Duration::seconds(30) {
+        if self.clock().now() - self.last_refresh() <= Duration::seconds(30) {
             return;
         }
+        self.refresh_active_now();
+    }
+
+    /// Same as `refresh_active`, without throttling. Does nothing when the
+    /// cache is offline.
+    pub fn refresh_active_now(&self) {
+        if self.cache.is_offline() {
+            return;
+        }
+        let now = self.clock().now();
         // NOTE: keep `events` locked before updating last refresh
         let mut events = self.active.write().unwrap();
         self.update_last_refresh(now);
         if let Ok(new) = self.api.events.get_active() {
             *events = new.into_iter().map(ActiveEvent::new).collect_vec();
+            self.history
+                .write()
+                .unwrap()
+                .record(now, &events, &self.all());
             debug!("events refreshed.");
         }
     }
 
+    /// Forecasts the availability of the event `code` within each of the
+    /// `horizons`, from its spawn rate and the spawns observed so far.
+    pub fn forecast(&self, code: &str, horizons: &[Duration]) -> Option<EventForecast> {
+        let event = self.get(code)?;
+        let now = self.clock().now();
+        let active_until = self
+            .active()
+            .iter()
+            .filter(|e| e.code() == code)
+            .filter_map(|e| e.expiration_time())
+            .filter(|expiration| *expiration > now)
+            .max();
+        let spawn_rate = self.history.read().unwrap().spawn_rate(&event);
+        Some(EventForecast::new(
+            event,
+            now,
+            active_until,
+            spawn_rate,
+            horizons,
+        ))
+    }
+
+    /// Forecasts every event bringing the monster, resource or NPC `content_code`.
+    pub fn forecast_content(
+        &self,
+        content_code: &str,
+        horizons: &[Duration],
+    ) -> Vec<EventForecast> {
+        self.all()
+            .iter()
+            .filter(|e| e.content().code == content_code)
+            .filter_map(|e| self.forecast(e.code(), horizons))
+            .collect_vec()
+    }
+
+    pub(crate) fn clock(&self) -> Arc<dyn Clock> {
+        self.server.clock()
+    }
+
     fn update_last_refresh(&self, now: DateTime<Utc>) {
         self.last_refresh
             .write()
@@ -66,21 +132,35 @@ impl EventsClient {
     }
 }
 
+#[cfg(test)]
+impl EventsClient {
+    /// Replaces the active events, the way a refresh returning `active` would.
+    pub(crate) fn set_active(&self, active: Vec<ActiveEvent>) {
+        *self.active.write().unwrap() = active;
+    }
+}
+
 impl Persist<HashMap<String, Event>> for EventsClient {
-    const PATH: &'static str = ".cache/events.json";
+    const KEY: &'static str = "events";
+
+    fn cache(&self) -> &Cache {
+        &self.cache
+    }
 
-    fn load_from_api(&self) -> HashMap<String, Event> {
-        self.api
+    fn load_from_api(&self) -> Result<HashMap<String, Event>, ClientError> {
+        Ok(self
+            .api
             .events
             .get_all()
-            .unwrap()
+            .map_err(ClientError::api)?
             .into_iter()
             .map(|event| (event.code.clone(), Event::new(event)))
-            .collect()
+            .collect())
     }
 
-    fn refresh(&self) {
-        *self.data.write().unwrap() = self.load_from_api();
+    fn refresh(&self) -> Result<(), ClientError> {
+        *self.data.write().unwrap() = self.fetch()?;
+        Ok(())
     }
 }
 
@@ -0,0 +1,208 @@
+use crate::{
+    Persist,
+    cache::Cache,
+    client::{
+        error::ClientError, events::EventsClient, items::ItemsClient, maps::MapsClient,
+        monsters::MonstersClient, npcs::NpcsClient, npcs_items::NpcsItemsClient,
+        resources::ResourcesClient, server::ServerClient, tasks::TasksClient,
+        tasks_rewards::TasksRewardsClient,
+    },
+    clock::{Clock, RealClock},
+};
+use artifactsmmo_api_wrapper::ArtifactApi;
+use std::{
+    sync::Arc,
+    thread::{self, ScopedJoinHandle},
+};
+
+/// Static game data: items, monsters, resources, maps, NPCs, tasks and events.
+///
+/// It is public and loaded without credentials, so that a single `GameData`
+/// can be shared through an `Arc` by several account `Client`s, the simulator
+/// and the optimizers.
+#[derive(Default, Debug)]
+pub struct GameData {
+    pub server: Arc<ServerClient>,
+    pub events: Arc<EventsClient>,
+    pub resources: Arc<ResourcesClient>,
+    pub monsters: Arc<MonstersClient>,
+    pub items: Arc<ItemsClient>,
+    pub tasks: Arc<TasksClient>,
+    pub maps: Arc<MapsClient>,
+    pub npcs: Arc<NpcsClient>,
+    pub cache: Arc<Cache>,
+}
+
+impl GameData {
+    /// Loads the game data from the API at `url`, cached under `.cache`.
+    pub fn new(url: String) -> Result<Self, ClientError> {
+        Self::with_cache(url, Cache::default())
+    }
+
+    /// Loads the game data from the API at `url`, cached in `cache`. If the
+    /// cache is offline the data is read from it only.
+    pub fn with_cache(url: String, cache: Cache) -> Result<Self, ClientError> {
+        Self::load(
+            Arc::new(ArtifactApi::public(url)),
+            cache,
+            Arc::new(RealClock),
+        )
+    }
+
+    /// Loads the game data from `cache` only, failing if it is not cached.
+    /// The returned data never touches the network, even when refreshed.
+    pub fn from_cache(cache: Cache) -> Result<Self, ClientError> {
+        cache.set_offline(true);
+        Self::load(Arc::default(), cache, Arc::new(RealClock))
+    }
+
+    pub(crate) fn load(
+        api: Arc<ArtifactApi>,
+        cache: Cache,
+        clock: Arc<dyn Clock>,
+    ) -> Result<Self, ClientError> {
+        let server = Arc::new(ServerClient::new(api.clone(), clock));
+        if !cache.is_offline() {
+            server.update_offset();
+            cache.set_version(server.cache_stamp());
+        }
+        let cache = Arc::new(cache);
+
+        let (events, tasks, npcs) = thread::scope(|s| {
+            let api_clone = api.clone();
+            let cache_clone = cache.clone();
+            let server_clone = server.clone();
+            let events_handle = s.spawn(move || -> Result<_, ClientError> {
+                Ok(Arc::new(EventsClient::new(
+                    api_clone,
+                    cache_clone,
+                    server_clone,
+                )?))
+            });
+
+            let api_clone = api.clone();
+            let cache_clone = cache.clone();
+            let tasks_handle = s.spawn(move || -> Result<_, ClientError> {
+                let rewards = TasksRewardsClient::new(api_clone.clone(), cache_clone.clone())?;
+                Ok(Arc::new(TasksClient::new(
+                    api_clone,
+                    cache_clone,
+                    Arc::new(rewards),
+                )?))
+            });
+
+            let api_clone = api.clone();
+            let cache_clone = cache.clone();
+            let npcs_handle = s.spawn(move || -> Result<_, ClientError> {
+                let items = NpcsItemsClient::new(api_clone.clone(), cache_clone.clone())?;
+                Ok(Arc::new(NpcsClient::new(
+                    api_clone,
+                    cache_clone,
+                    Arc::new(items),
+                )?))
+            });
+
+            Ok::<_, ClientError>((
+                join(events_handle, "events")?,
+                join(tasks_handle, "tasks")?,
+                join(npcs_handle, "npcs")?,
+            ))
+        })?;
+
+        let (resources, monsters, maps) = thread::scope(|s| {
+            let api_clone = api.clone();
+            let cache_clone = cache.clone();
+            let events_clone = events.clone();
+            let resources_handle = s.spawn(move || -> Result<_, ClientError> {
+                Ok(Arc::new(ResourcesClient::new(
+                    api_clone,
+                    cache_clone,
+                    events_clone,
+                )?))
+            });
+
+            let api_clone = api.clone();
+            let cache_clone = cache.clone();
+            let events_clone = events.clone();
+            let monsters_handle = s.spawn(move || -> Result<_, ClientError> {
+                Ok(Arc::new(MonstersClient::new(
+                    api_clone,
+                    cache_clone,
+                    events_clone,
+                )?))
+            });
+
+            let api_clone = api.clone();
+            let cache_clone = cache.clone();
+            let events_clone = events.clone();
+            let maps_handle = s.spawn(move || -> Result<_, ClientError> {
+                Ok(Arc::new(MapsClient::new(
+                    api_clone,
+                    cache_clone,
+                    events_clone,
+                )?))
+            });
+
+            Ok::<_, ClientError>((
+                join(resources_handle, "resources")?,
+                join(monsters_handle, "monsters")?,
+                join(maps_handle, "maps")?,
+            ))
+        })?;
+
+        let items = Arc::new(ItemsClient::new(
+            api.clone(),
+            cache.clone(),
+            resources.clone(),
+            monsters.clone(),
+            tasks.reward.clone(),
+            npcs.clone(),
+        )?);
+
+        Ok(Self {
+            server,
+            events,
+            resources,
+            monsters,
+            items,
+            tasks,
+            maps,
+            npcs,
+            cache,
+        })
+    }
+
+    /// Sets the clock used by the cooldown and event expiry logic of every
+    /// client sharing this data.
+    pub fn set_clock(&self, clock: Arc<dyn Clock>) {
+        self.server.set_clock(clock);
+    }
+
+    /// Stamps the cache with the current server version and season and reloads every
+    /// collection from the API, persisting it.
+    pub fn refresh(&self) -> Result<(), ClientError> {
+        if self.cache.is_offline() {
+            return Err(ClientError::Offline);
+        }
+        self.cache.set_version(self.server.cache_stamp());
+        self.items.refresh()?;
+        self.monsters.refresh()?;
+        self.resources.refresh()?;
+        self.events.refresh()?;
+        self.tasks.refresh()?;
+        self.tasks.reward.refresh()?;
+        self.npcs.refresh()?;
+        self.npcs.items.refresh()?;
+        self.maps.refresh()?;
+        Ok(())
+    }
+}
+
+fn join<T>(
+    handle: ScopedJoinHandle<'_, Result<T, ClientError>>,
+    name: &'static str,
+) -> Result<T, ClientError> {
+    handle
+        .join()
+        .map_err(|_| ClientError::LoaderPanicked(name))?
+}
@@ -1,8 +1,8 @@
 use crate::{
-    Code, CollectionClient, DataEntity, DropsItems, Level, Persist,
+    Cache, ClientError, Code, CollectionClient, DataEntity, DropsItems, Level, Persist,
     client::{
-        monsters::MonstersClient, npcs::NpcsClient, resources::ResourcesClient,
-        tasks_rewards::TasksRewardsClient,
+        craft_tree::CraftTree, monsters::MonstersClient, npcs::NpcsClient,
+        resources::ResourcesClient, tasks_rewards::TasksRewardsClient,
     },
     consts::{TASKS_COIN, TASKS_REWARDS_SPECIFICS},
     entities::{Item, Monster, Npc, Resource},
@@ -25,6 +25,7 @@ use strum_macros::{AsRefStr, Display, EnumIs, EnumIter, EnumString};
 pub struct ItemsClient {
     data: RwLock<HashMap<String, Item>>,
     api: Arc<ArtifactApi>,
+    cache: Arc<Cache>,
     resources: Arc<ResourcesClient>,
     monsters: Arc<MonstersClient>,
     tasks_rewards: Arc<TasksRewardsClient>,
@@ -34,21 +35,23 @@ pub struct ItemsClient {
 impl ItemsClient {
     pub(crate) fn new(
         api: Arc<ArtifactApi>,
+        cache: Arc<Cache>,
         resources: Arc<ResourcesClient>,
         monsters: Arc<MonstersClient>,
         tasks_rewards: Arc<TasksRewardsClient>,
         npcs: Arc<NpcsClient>,
-    ) -> Self {
+    ) -> Result<Self, ClientError> {
         let items = Self {
             data: Default::default(),
             api,
+            cache,
             resources,
             monsters,
             tasks_rewards,
             npcs,
         };
-        *items.data.write().unwrap() = items.load();
-        items
+        *items.data.write().unwrap() = items.load()?;
+        Ok(items)
     }
 
     /// Takes an item `code` and return the mats required to craft it.
@@ -66,25 +69,22 @@ impl ItemsClient {
     /// Takes an item `code` and returns the mats down to the raw materials
     /// required to craft it.
     pub fn base_mats_of(&self, code: &str) -> Vec<SimpleItemSchema> {
-        self.mats_of(code)
-            .iter()
-            .flat_map(|mat| {
-                if self.mats_of(&mat.code).is_empty() {
-                    vec![SimpleItemSchema {
-                        code: mat.code.clone(),
-                        quantity: mat.quantity,
-                    }]
-                } else {
-                    self.mats_of(&mat.code)
-                        .iter()
-                        .map(|b| SimpleItemSchema {
-                            code: b.code.clone(),
-                            quantity: b.quantity * mat.quantity,
-                        })
-                        .collect_vec()
-                }
-            })
-            .collect_vec()
+        self.craft_tree(code, 1)
+            .filter(|tree| tree.is_crafted())
+            .map(|tree| tree.raw_materials())
+            .unwrap_or_default()
+    }
+
+    /// Takes an item `code` and returns the full tree of materials needed to
+    /// obtain `quantity` of it.
+    pub fn craft_tree(&self, code: &str, quantity: u32) -> Option<CraftTree> {
+        let item = self.get(code)?;
+        Some(CraftTree::build(
+            item,
+            quantity,
+            &|code| self.get(code),
+            &|code| self.sources_of(code),
+        ))
     }
 
     /// Takes an `resource` code and returns the items that can be crafted
@@ -259,20 +259,26 @@ impl ItemsClient {
 }
 
 impl Persist<HashMap<String, Item>> for ItemsClient {
-    const PATH: &'static str = ".cache/items.json";
+    const KEY: &'static str = "items";
+
+    fn cache(&self) -> &Cache {
+        &self.cache
+    }
 
-    fn load_from_api(&self) -> HashMap<String, Item> {
-        self.api
+    fn load_from_api(&self) -> Result<HashMap<String, Item>, ClientError> {
+        Ok(self
+            .api
             .items
             .get_all()
-            .unwrap()
+            .map_err(ClientError::api)?
             .into_iter()
             .map(|i| (i.code.clone(), Item::new(i)))
-            .collect()
+            .collect())
     }
 
-    fn refresh(&self) {
-        *self.data.write().unwrap() = self.load_from_api();
+    fn refresh(&self) -> Result<(), ClientError> {
+        *self.data.write().unwrap() = self.fetch()?;
+        Ok(())
     }
 }
 
@@ -388,6 +394,10 @@ pub enum LevelConditionCode {
     MiningLevel,
     WoodcuttingLevel,
     FishingLevel,
+    WeaponcraftingLevel,
+    GearcraftingLevel,
+    JewelrycraftingLevel,
+    CookingLevel,
     Level,
 }
 
@@ -398,6 +408,10 @@ impl From<LevelConditionCode> for Skill {
             LevelConditionCode::MiningLevel => Skill::Mining,
             LevelConditionCode::WoodcuttingLevel => Skill::Woodcutting,
             LevelConditionCode::FishingLevel => Skill::Fishing,
+            LevelConditionCode::WeaponcraftingLevel => Skill::Weaponcrafting,
+            LevelConditionCode::GearcraftingLevel => Skill::Gearcrafting,
+            LevelConditionCode::JewelrycraftingLevel => Skill::Jewelrycrafting,
+            LevelConditionCode::CookingLevel => Skill::Cooking,
             LevelConditionCode::Level => Skill::Combat,
         }
     }
@@ -1,89 +1,234 @@
-use crate::{client::events::EventsClient, entities::Map, skill::Skill};
+use crate::{
+    Cache, ClientError, HasConditions, Persist,
+    client::events::EventsClient,
+    consts::{MOVE_CD_PER_TILE, TRANSITION_CD},
+    entities::{ActiveEvent, Map},
+    skill::Skill,
+};
 use artifactsmmo_api_wrapper::ArtifactApi;
-use artifactsmmo_openapi::models::{MapContentSchema, MapContentType, MapLayer, TaskType};
-use chrono::{DateTime, Utc};
+use artifactsmmo_openapi::models::{
+    ConditionOperator, MapContentSchema, MapContentType, MapLayer, MapSchema, SimpleItemSchema,
+    TaskType, TransitionSchema,
+};
 use itertools::Itertools;
 use std::{
-    collections::HashMap,
+    cmp::Reverse,
+    collections::{BinaryHeap, HashMap},
     sync::{Arc, RwLock},
 };
 
+type Position = (MapLayer, i32, i32);
+
 #[derive(Default, Debug)]
 pub struct MapsClient {
-    data: HashMap<(MapLayer, i32, i32), RwLock<Map>>,
+    data: RwLock<HashMap<Position, Map>>,
+    overlay: RwLock<HashMap<Position, Map>>,
+    api: Arc<ArtifactApi>,
+    cache: Arc<Cache>,
     events: Arc<EventsClient>,
 }
 
 impl MapsClient {
-    pub(crate) fn new(api: &ArtifactApi, events: Arc<EventsClient>) -> Self {
-        Self {
-            data: api
-                .maps
-                .get_all()
-                .unwrap()
-                .into_iter()
-                .map(|m| ((m.layer, m.x, m.y), RwLock::new(Map::new(m))))
-                .collect(),
+    pub(crate) fn new(
+        api: Arc<ArtifactApi>,
+        cache: Arc<Cache>,
+        events: Arc<EventsClient>,
+    ) -> Result<Self, ClientError> {
+        let maps = Self {
+            data: Default::default(),
+            overlay: Default::default(),
+            api,
+            cache,
             events,
-        }
+        };
+        *maps.data.write().unwrap() = index(maps.load()?);
+        maps.refresh_from_events();
+        Ok(maps)
     }
 
+    /// Returns the map at the given position, with the content of the event
+    /// currently active on it if any.
     pub fn get(&self, layer: MapLayer, x: i32, y: i32) -> Option<Map> {
-        Some(self.data.get(&(layer, x, y))?.read().unwrap().clone())
+        let position = (layer, x, y);
+        if let Some(map) = self.overlay.read().unwrap().get(&position) {
+            return Some(map.clone());
+        }
+        self.base(layer, x, y)
     }
 
+    /// Returns the map at the given position, ignoring active events.
+    pub fn base(&self, layer: MapLayer, x: i32, y: i32) -> Option<Map> {
+        self.data.read().unwrap().get(&(layer, x, y)).cloned()
+    }
+
+    /// Returns every map, with the content of the events currently active.
+    pub fn all(&self) -> Vec<Map> {
+        let overlay = self.overlay.read().unwrap();
+        self.data
+            .read()
+            .unwrap()
+            .iter()
+            .map(|(position, map)| overlay.get(position).unwrap_or(map).clone())
+            .collect_vec()
+    }
+
+    /// Refreshes the active events and rebuilds the event layer from them.
     pub fn refresh_from_events(&self) {
-        self.events.active().iter().for_each(|e| {
-            if DateTime::parse_from_rfc3339(e.expiration()).is_ok_and(|e| e < Utc::now())
-                && let Some(map) = self.data.get(&(e.map().layer, e.map().x, e.map().y))
-            {
-                *map.write().unwrap() = Map::new(e.previous_map().clone())
-            }
-        });
         self.events.refresh_active();
-        self.events.active().iter().for_each(|e| {
-            if DateTime::parse_from_rfc3339(e.expiration()).is_ok_and(|e| e > Utc::now())
-                && let Some(map) = self.data.get(&(e.map().layer, e.map().x, e.map().y))
-            {
-                *map.write().unwrap() = Map::new(e.map().clone())
-            }
-        });
+        self.apply_events();
     }
 
-    //TODO: handle layer
-    pub fn closest_from_amoung(x: i32, y: i32, maps: &[Map]) -> Option<Map> {
+    /// Rebuilds the event layer from the currently known active events. The
+    /// cached base maps are left untouched.
+    pub fn apply_events(&self) {
+        let now = self.events.clock().now();
+        *self.overlay.write().unwrap() = self
+            .events
+            .active()
+            .iter()
+            .filter(|e| e.expiration_time().is_some_and(|e| e > now))
+            .map(|e| {
+                (
+                    (e.map().layer, e.map().x, e.map().y),
+                    Map::new(e.map().clone()),
+                )
+            })
+            .collect();
+    }
+
+    /// Returns the closest map among `maps`, maps on another layer than `layer`
+    /// being considered farther than any map on the same layer.
+    pub fn closest_from_amoung(layer: MapLayer, x: i32, y: i32, maps: &[Map]) -> Option<Map> {
         maps.iter()
-            .min_by_key(|m| i32::abs(x - m.x()) + i32::abs(y - m.y()))
+            .min_by_key(|m| {
+                (
+                    m.layer() != layer,
+                    i32::abs(x - m.x()) + i32::abs(y - m.y()),
+                )
+            })
             .cloned()
     }
 
+    /// Computes the shortest route from `from` to `to`, walking through the maps for which
+    /// `can_access` returns true and taking the transitions for which `can_transition`
+    /// returns true.
+    ///
+    /// Routes taking the fewest transitions with a `Cost` condition are preferred, the
+    /// cooldown only breaking ties, so that gold or items are never spent to save time.
+    pub fn route<A, T>(
+        &self,
+        from: Position,
+        to: Position,
+        can_access: A,
+        can_transition: T,
+    ) -> Option<Route>
+    where
+        A: Fn(&Map) -> bool,
+        T: Fn(&TransitionSchema) -> bool,
+    {
+        // Costs are ordered by number of paid transitions, then by cooldown.
+        let mut costs: HashMap<Position, (u32, u32)> = HashMap::from([(from, (0, 0))]);
+        let mut previous: HashMap<Position, (Position, bool)> = HashMap::new();
+        let mut positions = vec![from];
+        let mut queue = BinaryHeap::from([Reverse(((0, 0), 0))]);
+        while let Some(Reverse((cost, index))) = queue.pop() {
+            let position = positions[index];
+            if position == to {
+                break;
+            }
+            if costs.get(&position).is_some_and(|c| *c < cost) {
+                continue;
+            }
+            let Some(map) = self.get(position.0, position.1, position.2) else {
+                continue;
+            };
+            let (layer, x, y) = position;
+            let mut neighbours = [(x + 1, y), (x - 1, y), (x, y + 1), (x, y - 1)]
+                .into_iter()
+                .filter_map(|(x, y)| self.get(layer, x, y))
+                .filter(|m| !m.is_blocked() && can_access(m))
+                .map(|m| ((layer, m.x(), m.y()), (0, MOVE_CD_PER_TILE), false))
+                .collect_vec();
+            if let Some(transition) = map.transition()
+                && can_transition(transition)
+                && self
+                    .get(transition.layer, transition.x, transition.y)
+                    .is_some_and(|m| can_access(&m))
+            {
+                let paid = !transition_cost(transition).is_empty() as u32;
+                neighbours.push((
+                    (transition.layer, transition.x, transition.y),
+                    (paid, TRANSITION_CD),
+                    true,
+                ));
+            }
+            for (next, (step_paid, step_cooldown), is_transition) in neighbours {
+                let next_cost = (cost.0 + step_paid, cost.1 + step_cooldown);
+                if costs.get(&next).is_none_or(|c| next_cost < *c) {
+                    costs.insert(next, next_cost);
+                    previous.insert(next, (position, is_transition));
+                    positions.push(next);
+                    queue.push(Reverse((next_cost, positions.len() - 1)));
+                }
+            }
+        }
+        let (_, cooldown) = *costs.get(&to)?;
+        let mut path = vec![(to, false)];
+        let mut current = to;
+        while let Some(&(prev, is_transition)) = previous.get(&current) {
+            path.last_mut().unwrap().1 = is_transition;
+            path.push((prev, false));
+            current = prev;
+        }
+        path.reverse();
+        let mut steps = vec![];
+        let mut cost: Vec<SimpleItemSchema> = vec![];
+        for (i, (position, reached_by_transition)) in path.iter().enumerate().skip(1) {
+            let map = self.get(position.0, position.1, position.2)?;
+            let (before, _) = path[i - 1];
+            if *reached_by_transition {
+                let origin = self.get(before.0, before.1, before.2)?;
+                if i > 1 && !path[i - 1].1 {
+                    steps.push(RouteStep::Move(origin.clone()));
+                }
+                if let Some(transition) = origin.transition() {
+                    for item in transition_cost(transition) {
+                        match cost.iter_mut().find(|c| c.code == item.code) {
+                            Some(c) => c.quantity += item.quantity,
+                            None => cost.push(item),
+                        }
+                    }
+                }
+                steps.push(RouteStep::Transition(map));
+            } else if i == path.len() - 1 {
+                steps.push(RouteStep::Move(map));
+            }
+        }
+        Some(Route {
+            steps,
+            cooldown,
+            cost,
+        })
+    }
+
     pub fn of_type(&self, r#type: MapContentType) -> Vec<Map> {
-        self.data
-            .values()
-            .filter_map(|m| {
-                let map = m.read().unwrap().clone();
-                map.content_type_is(r#type).then_some(map)
-            })
+        self.all()
+            .into_iter()
+            .filter(|m| m.content_type_is(r#type))
             .collect_vec()
     }
 
     pub fn with_content_code(&self, code: &str) -> Vec<Map> {
-        self.data
-            .values()
-            .filter_map(|m| {
-                let map = m.read().unwrap().clone();
-                map.content_code_is(code).then_some(map)
-            })
+        self.all()
+            .into_iter()
+            .filter(|m| m.content_code_is(code))
             .collect()
     }
 
     pub fn with_content(&self, content: &MapContentSchema) -> Vec<Map> {
-        self.data
-            .values()
-            .filter_map(|m| {
-                let map = m.read().unwrap().clone();
-                map.content_is(content).then_some(map)
-            })
+        self.all()
+            .into_iter()
+            .filter(|m| m.content_is(content))
             .collect()
     }
 
@@ -125,6 +270,18 @@ impl MapsClient {
         map.closest_among(&maps)
     }
 
+    /// Returns the closest map from `map` for which `filter` returns true.
+    pub fn closest_matching_from<F>(&self, map: Map, filter: F) -> Option<Map>
+    where
+        F: Fn(&Map) -> bool,
+    {
+        let maps = self.all().into_iter().filter(filter).collect_vec();
+        if maps.is_empty() {
+            return None;
+        }
+        map.closest_among(&maps)
+    }
+
     pub fn closest_tasksmaster_from(&self, map: Map, r#type: Option<TaskType>) -> Option<Map> {
         if let Some(r#type) = r#type {
             self.closest_with_content_from(
@@ -140,9 +297,277 @@ impl MapsClient {
     }
 }
 
+impl Persist<Vec<Map>> for MapsClient {
+    const KEY: &'static str = "maps";
+
+    fn cache(&self) -> &Cache {
+        &self.cache
+    }
+
+    /// Maps hosting an active event are replaced by the map they had before
+    /// the event, so that the persisted maps never hold event content.
+    fn load_from_api(&self) -> Result<Vec<Map>, ClientError> {
+        self.events.refresh_active_now();
+        let maps = self.api.maps.get_all().map_err(ClientError::api)?;
+        Ok(without_events(maps, &self.events.active()))
+    }
+
+    fn refresh(&self) -> Result<(), ClientError> {
+        *self.data.write().unwrap() = index(self.fetch()?);
+        Ok(())
+    }
+}
+
+/// Replaces the maps hosting one of the `active` events by the map they had
+/// before the event.
+fn without_events(maps: Vec<MapSchema>, active: &[ActiveEvent]) -> Vec<Map> {
+    let previous = active
+        .iter()
+        .map(|e| {
+            (
+                (e.map().layer, e.map().x, e.map().y),
+                e.previous_map().clone(),
+            )
+        })
+        .collect::<HashMap<_, _>>();
+    maps.into_iter()
+        .map(|m| Map::new(previous.get(&(m.layer, m.x, m.y)).cloned().unwrap_or(m)))
+        .collect_vec()
+}
+
+fn index(maps: Vec<Map>) -> HashMap<Position, Map> {
+    maps.into_iter()
+        .map(|m| ((m.layer(), m.x(), m.y()), m))
+        .collect()
+}
+
+/// Route between two maps, moves within a layer being merged into a single step.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Route {
+    pub steps: Vec<RouteStep>,
+    /// Total cooldown of the route, in seconds. Transitions are counted as
+    /// `TRANSITION_CD`, an estimate.
+    pub cooldown: u32,
+    /// Gold and items consumed by the transitions of the route, gold being
+    /// listed under the `gold` code.
+    pub cost: Vec<SimpleItemSchema>,
+}
+
+/// Returns the gold and items consumed when taking `transition`, from its
+/// `Cost` conditions.
+fn transition_cost(transition: &TransitionSchema) -> Vec<SimpleItemSchema> {
+    transition
+        .conditions()
+        .iter()
+        .flatten()
+        .filter(|c| c.operator == ConditionOperator::Cost)
+        .map(|c| SimpleItemSchema::new(c.code.clone(), c.value as u32))
+        .collect_vec()
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum RouteStep {
+    /// Move to the given map.
+    Move(Map),
+    /// Take the transition of the current map, leading to the given map.
+    Transition(Map),
+}
+
 #[cfg(test)]
 mod tests {
-    //use super::*;
+    use super::*;
+    use crate::{GOLD, fixtures};
+    use artifactsmmo_openapi::models::{
+        AccessSchema, ActiveEventSchema, ConditionSchema, InteractionSchema, MapAccessType,
+    };
+    use chrono::{TimeDelta, Utc};
+
+    fn map(layer: MapLayer, x: i32, y: i32) -> MapSchema {
+        MapSchema {
+            layer,
+            x,
+            y,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn route_avoids_blocked_maps_and_takes_transitions() {
+        let overworld = MapLayer::Overworld;
+        let underground = MapLayer::Underground;
+        let maps = fixtures::maps(vec![
+            MapSchema {
+                interactions: InteractionSchema {
+                    transition: Some(
+                        TransitionSchema {
+                            layer: underground,
+                            x: 5,
+                            y: 5,
+                            ..Default::default()
+                        }
+                        .into(),
+                    ),
+                    ..Default::default()
+                }
+                .into(),
+                ..map(overworld, 0, 0)
+            },
+            MapSchema {
+                access: AccessSchema {
+                    r#type: MapAccessType::Blocked,
+                    ..Default::default()
+                }
+                .into(),
+                ..map(overworld, 1, 0)
+            },
+            map(overworld, 2, 0),
+            map(overworld, 0, 1),
+            map(overworld, 1, 1),
+            map(overworld, 2, 1),
+            map(underground, 5, 5),
+            map(underground, 6, 5),
+        ]);
+        let route = maps
+            .route((overworld, 0, 0), (overworld, 2, 0), |_| true, |_| true)
+            .unwrap();
+        assert_eq!(route.cooldown, 4 * MOVE_CD_PER_TILE);
+        assert_eq!(
+            route.steps,
+            vec![RouteStep::Move(maps.get(overworld, 2, 0).unwrap())]
+        );
+        let route = maps
+            .route((overworld, 0, 0), (underground, 6, 5), |_| true, |_| true)
+            .unwrap();
+        assert_eq!(route.cooldown, TRANSITION_CD + MOVE_CD_PER_TILE);
+        assert_eq!(
+            route.steps,
+            vec![
+                RouteStep::Transition(maps.get(underground, 5, 5).unwrap()),
+                RouteStep::Move(maps.get(underground, 6, 5).unwrap()),
+            ]
+        );
+        assert!(
+            maps.route((overworld, 0, 0), (underground, 6, 5), |_| true, |_| false)
+                .is_none()
+        );
+    }
+
+    #[test]
+    fn route_avoids_paid_transitions() {
+        let overworld = MapLayer::Overworld;
+        let underground = MapLayer::Underground;
+        let with_transition = |schema: MapSchema, transition: TransitionSchema| MapSchema {
+            interactions: InteractionSchema {
+                transition: Some(transition.into()),
+                ..Default::default()
+            }
+            .into(),
+            ..schema
+        };
+        let maps = fixtures::maps(vec![
+            with_transition(
+                map(overworld, 0, 0),
+                TransitionSchema {
+                    layer: underground,
+                    x: 5,
+                    y: 5,
+                    conditions: Some(vec![ConditionSchema {
+                        code: GOLD.to_owned(),
+                        operator: ConditionOperator::Cost,
+                        value: 1000,
+                    }]),
+                    ..Default::default()
+                },
+            ),
+            map(overworld, 1, 0),
+            with_transition(
+                map(overworld, 2, 0),
+                TransitionSchema {
+                    layer: underground,
+                    x: 7,
+                    y: 5,
+                    ..Default::default()
+                },
+            ),
+            map(underground, 5, 5),
+            map(underground, 6, 5),
+            map(underground, 7, 5),
+        ]);
+        let route = maps
+            .route((overworld, 0, 0), (underground, 5, 5), |_| true, |_| true)
+            .unwrap();
+        assert_eq!(route.cooldown, 4 * MOVE_CD_PER_TILE + TRANSITION_CD);
+        assert!(route.cost.is_empty());
+        assert_eq!(
+            route.steps,
+            vec![
+                RouteStep::Move(maps.get(overworld, 2, 0).unwrap()),
+                RouteStep::Transition(maps.get(underground, 7, 5).unwrap()),
+                RouteStep::Move(maps.get(underground, 5, 5).unwrap()),
+            ]
+        );
+        let route = maps
+            .route(
+                (overworld, 0, 0),
+                (underground, 5, 5),
+                |_| true,
+                |t| t.x == 5,
+            )
+            .unwrap();
+        assert_eq!(route.cooldown, TRANSITION_CD);
+        assert_eq!(
+            route.cost,
+            vec![SimpleItemSchema::new(GOLD.to_owned(), 1000)]
+        );
+    }
+
+    fn event(x: i32) -> ActiveEvent {
+        ActiveEvent::new(ActiveEventSchema {
+            code: "king_slime".to_owned(),
+            map: MapSchema {
+                interactions: InteractionSchema {
+                    content: Some(Box::new(MapContentSchema {
+                        r#type: MapContentType::Monster,
+                        code: "king_slime".to_owned(),
+                    })),
+                    ..Default::default()
+                }
+                .into(),
+                ..map(MapLayer::Overworld, x, 0)
+            }
+            .into(),
+            previous_map: map(MapLayer::Overworld, x, 0).into(),
+            expiration: (Utc::now() + TimeDelta::hours(1)).to_rfc3339(),
+            ..Default::default()
+        })
+    }
+
+    #[test]
+    fn get_returns_the_event_layer_and_base_the_cached_map() {
+        let overworld = MapLayer::Overworld;
+        let maps = fixtures::maps(vec![map(overworld, 0, 0), map(overworld, 1, 0)]);
+        maps.events.set_active(vec![event(1)]);
+        maps.apply_events();
+
+        assert_eq!(
+            maps.get(overworld, 1, 0).unwrap().monster(),
+            Some("king_slime")
+        );
+        assert!(maps.base(overworld, 1, 0).unwrap().content().is_none());
+        assert!(maps.get(overworld, 0, 0).unwrap().content().is_none());
+    }
+
+    #[test]
+    fn persisted_maps_never_hold_event_content() {
+        let king_slime = event(1);
+        let maps = without_events(
+            vec![map(MapLayer::Overworld, 0, 0), king_slime.map().clone()],
+            &[king_slime],
+        );
+
+        assert_eq!(maps.len(), 2);
+        assert!(maps.iter().all(|m| m.content().is_none()));
+    }
 
     // #[test]
     // fn check_content_type_as_string() {
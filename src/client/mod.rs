@@ -1,19 +1,51 @@
-use artifactsmmo_api_wrapper::ArtifactApi;
-use std::{sync::Arc, thread};
+use crate::{cache::Cache, clock::Clock, storage::Storage};
+use artifactsmmo_api_wrapper::{RateLimits, RateLimitsUsage};
+use std::{path::PathBuf, sync::Arc};
 
 pub use crate::client::{
-    account::AccountClient, bank::BankClient, character::CharacterClient, error::ClientError,
-    events::EventsClient, items::ItemsClient, maps::MapsClient, monsters::MonstersClient,
-    npcs::NpcsClient, npcs_items::NpcsItemsClient, resources::ResourcesClient,
-    server::ServerClient, tasks::TasksClient, tasks_rewards::TasksRewardsClient,
+    account::AccountClient,
+    bank::BankClient,
+    builder::ClientBuilder,
+    character::CharacterClient,
+    craft_tree::CraftTree,
+    error::ClientError,
+    event_forecast::{EventForecast, ForecastWindow},
+    event_watcher::{EventNotification, EventWatcher},
+    events::EventsClient,
+    game_data::GameData,
+    items::ItemsClient,
+    maps::MapsClient,
+    monsters::MonstersClient,
+    npcs::NpcsClient,
+    npcs_items::NpcsItemsClient,
+    resources::ResourcesClient,
+    retry::{ErrorClass, RetryPolicy, RetryRule},
+    server::ServerClient,
+    tasks::TasksClient,
+    tasks_rewards::TasksRewardsClient,
+    transport::{MockTransport, OfflineTransport, Transport},
 };
 use crate::grand_exchange::GrandExchangeClient;
 
+#[cfg(feature = "async")]
+pub use async_client::AsyncClient;
+#[cfg(feature = "async")]
+pub use async_transport::{AsyncTransport, HttpTransport};
+
 pub mod account;
+#[cfg(feature = "async")]
+pub mod async_client;
+#[cfg(feature = "async")]
+pub mod async_transport;
 pub mod bank;
+pub mod builder;
 pub mod character;
+pub mod craft_tree;
 pub mod error;
+pub mod event_forecast;
+pub mod event_watcher;
 pub mod events;
+pub mod game_data;
 pub mod grand_exchange;
 pub mod items;
 pub mod maps;
@@ -21,9 +53,11 @@ pub mod monsters;
 pub mod npcs;
 pub mod npcs_items;
 pub mod resources;
+pub mod retry;
 pub mod server;
 pub mod tasks;
 pub mod tasks_rewards;
+pub mod transport;
 
 #[derive(Default, Debug)]
 pub struct Client {
@@ -37,114 +71,108 @@ pub struct Client {
     pub maps: Arc<MapsClient>,
     pub npcs: Arc<NpcsClient>,
     pub grand_exchange: Arc<GrandExchangeClient>,
+    pub cache: Arc<Cache>,
+    /// Static game data, possibly shared with other clients. The collection
+    /// fields above point to the same clients.
+    pub game_data: Arc<GameData>,
+    pub rate_limits: RateLimits,
 }
 
 impl Client {
     pub fn new(url: String, account_name: String, token: String) -> Result<Self, ClientError> {
-        let api = Arc::new(ArtifactApi::new(url, token));
-
-        let (bank_res, events, server, tasks, npcs) = thread::scope(|s| {
-            let api_clone = api.clone();
-            let bank_handle = s.spawn(move || {
-                let bank_details = api_clone
-                    .bank
-                    .get_details()
-                    .map_err(|e| ClientError::Api(Box::new(e)))?;
-                let bank_items = api_clone
-                    .bank
-                    .get_items()
-                    .map_err(|e| ClientError::Api(Box::new(e)))?;
-                Ok(Arc::new(BankClient::new(*bank_details.data, bank_items)))
-            });
-
-            let api_clone = api.clone();
-            let events_handle = s.spawn(move || Arc::new(EventsClient::new(api_clone.clone())));
-
-            let api_clone = api.clone();
-            let server_handle = s.spawn(move || Arc::new(ServerClient::new(api_clone.clone())));
-
-            let api_clone = api.clone();
-            let tasks_handle = s.spawn(move || {
-                Arc::new(TasksClient::new(
-                    api_clone.clone(),
-                    Arc::new(TasksRewardsClient::new(api_clone.clone())),
-                ))
-            });
+        ClientBuilder::new(url, account_name, token).build()
+    }
 
-            let api_clone = api.clone();
-            let npcs_handle = s.spawn(move || {
-                Arc::new(NpcsClient::new(
-                    api_clone.clone(),
-                    Arc::new(NpcsItemsClient::new(api_clone.clone())),
-                ))
-            });
+    /// Returns a `ClientBuilder` to configure how the client is loaded.
+    pub fn builder(url: String, account_name: String, token: String) -> ClientBuilder {
+        ClientBuilder::new(url, account_name, token)
+    }
 
-            (
-                bank_handle.join().unwrap(),
-                events_handle.join().unwrap(),
-                server_handle.join().unwrap(),
-                tasks_handle.join().unwrap(),
-                npcs_handle.join().unwrap(),
-            )
-        });
+    /// Same as `new` but static game data is cached under `cache_root`
+    /// instead of the default `.cache` directory.
+    pub fn with_cache_root(
+        url: String,
+        account_name: String,
+        token: String,
+        cache_root: impl Into<PathBuf>,
+    ) -> Result<Self, ClientError> {
+        ClientBuilder::new(url, account_name, token)
+            .cache_root(cache_root)
+            .build()
+    }
 
-        let bank: Arc<BankClient> = bank_res?;
+    /// Same as `new` but static game data is cached in the given `storage`
+    /// backend, e.g. a SQLite database shared between tools.
+    pub fn with_storage(
+        url: String,
+        account_name: String,
+        token: String,
+        storage: impl Storage + 'static,
+    ) -> Result<Self, ClientError> {
+        ClientBuilder::new(url, account_name, token)
+            .storage(storage)
+            .build()
+    }
 
-        let (resources, monsters, maps) = thread::scope(|s| {
-            let api_clone = api.clone();
-            let events_clone = events.clone();
-            let resources_handle =
-                s.spawn(move || Arc::new(ResourcesClient::new(api_clone.clone(), events_clone)));
+    /// Same as `new` but character actions are sent through the given `transport`
+    /// instead of the HTTP API. Game data is still loaded from `url`.
+    pub fn with_transport(
+        url: String,
+        account_name: String,
+        token: String,
+        transport: Arc<dyn Transport>,
+    ) -> Result<Self, ClientError> {
+        ClientBuilder::new(url, account_name, token)
+            .transport(transport)
+            .build()
+    }
 
-            let api_clone = api.clone();
-            let events_clone = events.clone();
-            let monsters_handle =
-                s.spawn(move || Arc::new(MonstersClient::new(api_clone.clone(), events_clone)));
+    /// Builds a client for each `(account_name, token)` of `accounts`, with a
+    /// builder set up by `configure`. The static game data is loaded once by
+    /// the first builder, with its cache, timeouts and rate limits, and shared
+    /// with the other clients, while the data of each account is cached apart.
+    pub fn for_accounts(
+        url: String,
+        accounts: impl IntoIterator<Item = (String, String)>,
+        configure: impl Fn(ClientBuilder) -> ClientBuilder,
+    ) -> Result<Vec<Self>, ClientError> {
+        let mut clients: Vec<Self> = vec![];
+        for (account_name, token) in accounts {
+            let mut builder = configure(ClientBuilder::new(url.clone(), account_name, token));
+            if let Some(first) = clients.first() {
+                builder = builder.game_data(first.game_data.clone());
+            }
+            clients.push(builder.build()?);
+        }
+        Ok(clients)
+    }
 
-            let api_clone = api.clone();
-            let events_clone = events.clone();
-            let maps_handle = s.spawn(move || Arc::new(MapsClient::new(&api_clone, events_clone)));
+    /// Sets the policy used to retry the failed character actions.
+    pub fn set_retry_policy(&self, policy: RetryPolicy) {
+        self.account.set_retry_policy(policy);
+    }
 
-            (
-                resources_handle.join().unwrap(),
-                monsters_handle.join().unwrap(),
-                maps_handle.join().unwrap(),
-            )
-        });
+    /// Sets the clock used by the cooldown and event expiry logic, shared with
+    /// every client using the same `GameData`.
+    pub fn set_clock(&self, clock: Arc<dyn Clock>) {
+        self.game_data.set_clock(clock);
+    }
 
-        let items = Arc::new(ItemsClient::new(
-            api.clone(),
-            resources.clone(),
-            monsters.clone(),
-            tasks.reward.clone(),
-            npcs.clone(),
-        ));
+    /// Returns the current usage of the request budgets shared by every
+    /// character of the account.
+    pub fn rate_limit_usage(&self) -> RateLimitsUsage {
+        self.rate_limits.usage()
+    }
 
-        let account = Arc::new(AccountClient::new(account_name, bank, api.clone()));
-        let grand_exchange = Arc::new(GrandExchangeClient::new(api.clone()));
-        account.load_characters(
-            account.clone(),
-            items.clone(),
-            resources.clone(),
-            monsters.clone(),
-            maps.clone(),
-            npcs.clone(),
-            tasks.clone(),
-            server.clone(),
-            grand_exchange.clone(),
-        )?;
+    /// Starts a background `EventWatcher` keeping the active events and the
+    /// event maps up to date.
+    pub fn watch_events(&self) -> EventWatcher {
+        EventWatcher::start(self.events.clone(), self.maps.clone())
+    }
 
-        Ok(Self {
-            account,
-            items,
-            monsters,
-            resources,
-            server,
-            events,
-            tasks,
-            maps,
-            npcs,
-            grand_exchange,
-        })
+    /// Stamps the cache with the current server version and season and reloads every
+    /// collection of static game data from the API, persisting it.
+    pub fn refresh_static_data(&self) -> Result<(), ClientError> {
+        self.game_data.refresh()
     }
 }
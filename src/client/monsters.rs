@@ -1,5 +1,5 @@
 use crate::{
-    CanProvideXp, CollectionClient, DataEntity, DropsItems, Level, Persist,
+    Cache, CanProvideXp, ClientError, CollectionClient, DataEntity, DropsItems, Level, Persist,
     client::events::EventsClient, entities::Monster,
 };
 use artifactsmmo_api_wrapper::ArtifactApi;
@@ -13,18 +13,24 @@ use std::{
 pub struct MonstersClient {
     data: RwLock<HashMap<String, Monster>>,
     api: Arc<ArtifactApi>,
+    cache: Arc<Cache>,
     events: Arc<EventsClient>,
 }
 
 impl MonstersClient {
-    pub(crate) fn new(api: Arc<ArtifactApi>, events: Arc<EventsClient>) -> Self {
+    pub(crate) fn new(
+        api: Arc<ArtifactApi>,
+        cache: Arc<Cache>,
+        events: Arc<EventsClient>,
+    ) -> Result<Self, ClientError> {
         let monsters = Self {
             data: Default::default(),
             api,
+            cache,
             events,
         };
-        *monsters.data.write().unwrap() = monsters.load();
-        monsters
+        *monsters.data.write().unwrap() = monsters.load()?;
+        Ok(monsters)
     }
 
     pub fn dropping(&self, item_code: &str) -> Vec<Monster> {
@@ -54,20 +60,26 @@ impl MonstersClient {
 }
 
 impl Persist<HashMap<String, Monster>> for MonstersClient {
-    const PATH: &'static str = ".cache/monsters.json";
+    const KEY: &'static str = "monsters";
 
-    fn load_from_api(&self) -> HashMap<String, Monster> {
-        self.api
+    fn cache(&self) -> &Cache {
+        &self.cache
+    }
+
+    fn load_from_api(&self) -> Result<HashMap<String, Monster>, ClientError> {
+        Ok(self
+            .api
             .monsters
             .get_all()
-            .unwrap()
+            .map_err(ClientError::api)?
             .into_iter()
             .map(|m| (m.code.clone(), Monster::new(m)))
-            .collect()
+            .collect())
     }
 
-    fn refresh(&self) {
-        *self.data.write().unwrap() = self.load_from_api();
+    fn refresh(&self) -> Result<(), ClientError> {
+        *self.data.write().unwrap() = self.fetch()?;
+        Ok(())
     }
 }
 
@@ -1,5 +1,6 @@
 use crate::{
-    Code, CollectionClient, DataEntity, Persist, client::npcs_items::NpcsItemsClient, entities::Npc,
+    Cache, ClientError, Code, CollectionClient, DataEntity, Persist,
+    client::npcs_items::NpcsItemsClient, entities::Npc,
 };
 use artifactsmmo_api_wrapper::ArtifactApi;
 use itertools::Itertools;
@@ -12,18 +13,24 @@ use std::{
 pub struct NpcsClient {
     data: RwLock<HashMap<String, Npc>>,
     api: Arc<ArtifactApi>,
+    cache: Arc<Cache>,
     pub items: Arc<NpcsItemsClient>,
 }
 
 impl NpcsClient {
-    pub(crate) fn new(api: Arc<ArtifactApi>, items: Arc<NpcsItemsClient>) -> Self {
+    pub(crate) fn new(
+        api: Arc<ArtifactApi>,
+        cache: Arc<Cache>,
+        items: Arc<NpcsItemsClient>,
+    ) -> Result<Self, ClientError> {
         let npcs = Self {
             data: Default::default(),
             api,
+            cache,
             items,
         };
-        *npcs.data.write().unwrap() = npcs.load();
-        npcs
+        *npcs.data.write().unwrap() = npcs.load()?;
+        Ok(npcs)
     }
 
     pub fn selling(&self, code: &str) -> Vec<Npc> {
@@ -37,20 +44,26 @@ impl NpcsClient {
 }
 
 impl Persist<HashMap<String, Npc>> for NpcsClient {
-    const PATH: &'static str = ".cache/npcs.json";
+    const KEY: &'static str = "npcs";
 
-    fn load_from_api(&self) -> HashMap<String, Npc> {
-        self.api
+    fn cache(&self) -> &Cache {
+        &self.cache
+    }
+
+    fn load_from_api(&self) -> Result<HashMap<String, Npc>, ClientError> {
+        Ok(self
+            .api
             .npcs
             .get_all()
-            .unwrap()
+            .map_err(ClientError::api)?
             .into_iter()
             .map(|npc| (npc.code.clone(), Npc::new(npc)))
-            .collect()
+            .collect())
     }
 
-    fn refresh(&self) {
-        *self.data.write().unwrap() = self.load_from_api();
+    fn refresh(&self) -> Result<(), ClientError> {
+        *self.data.write().unwrap() = self.fetch()?;
+        Ok(())
     }
 }
 
@@ -1,4 +1,4 @@
-use crate::{DataEntity, Persist, entities::NpcItem};
+use crate::{Cache, ClientError, DataEntity, Persist, entities::NpcItem};
 use artifactsmmo_api_wrapper::ArtifactApi;
 use sdk_derive::CollectionClient;
 use std::{
@@ -10,34 +10,42 @@ use std::{
 pub struct NpcsItemsClient {
     data: RwLock<HashMap<String, NpcItem>>,
     api: Arc<ArtifactApi>,
+    cache: Arc<Cache>,
 }
 
 impl NpcsItemsClient {
-    pub(crate) fn new(api: Arc<ArtifactApi>) -> Self {
+    pub(crate) fn new(api: Arc<ArtifactApi>, cache: Arc<Cache>) -> Result<Self, ClientError> {
         let npcs_items = Self {
             data: Default::default(),
             api,
+            cache,
         };
-        *npcs_items.data.write().unwrap() = npcs_items.load();
-        npcs_items
+        *npcs_items.data.write().unwrap() = npcs_items.load()?;
+        Ok(npcs_items)
     }
 }
 
 impl Persist<HashMap<String, NpcItem>> for NpcsItemsClient {
-    const PATH: &'static str = ".cache/npcs_items.json";
+    const KEY: &'static str = "npcs_items";
 
-    fn load_from_api(&self) -> HashMap<String, NpcItem> {
-        self.api
+    fn cache(&self) -> &Cache {
+        &self.cache
+    }
+
+    fn load_from_api(&self) -> Result<HashMap<String, NpcItem>, ClientError> {
+        Ok(self
+            .api
             .npcs
             .get_items()
-            .unwrap()
+            .map_err(ClientError::api)?
             .into_iter()
             .map(|npc| (npc.code.clone(), NpcItem::new(npc)))
-            .collect()
+            .collect())
     }
 
-    fn refresh(&self) {
-        *self.data.write().unwrap() = self.load_from_api();
+    fn refresh(&self) -> Result<(), ClientError> {
+        *self.data.write().unwrap() = self.fetch()?;
+        Ok(())
     }
 }
 
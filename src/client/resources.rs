@@ -1,6 +1,6 @@
 use crate::{
-    CollectionClient, DataEntity, DropsItems, Persist, client::events::EventsClient,
-    entities::Resource,
+    Cache, ClientError, CollectionClient, DataEntity, DropsItems, Persist,
+    client::events::EventsClient, entities::Resource,
 };
 use artifactsmmo_api_wrapper::ArtifactApi;
 use itertools::Itertools;
@@ -13,18 +13,24 @@ use std::{
 pub struct ResourcesClient {
     data: RwLock<HashMap<String, Resource>>,
     api: Arc<ArtifactApi>,
+    cache: Arc<Cache>,
     events: Arc<EventsClient>,
 }
 
 impl ResourcesClient {
-    pub(crate) fn new(api: Arc<ArtifactApi>, events: Arc<EventsClient>) -> Self {
+    pub(crate) fn new(
+        api: Arc<ArtifactApi>,
+        cache: Arc<Cache>,
+        events: Arc<EventsClient>,
+    ) -> Result<Self, ClientError> {
         let resources = Self {
             data: Default::default(),
             api,
+            cache,
             events,
         };
-        *resources.data.write().unwrap() = resources.load();
-        resources
+        *resources.data.write().unwrap() = resources.load()?;
+        Ok(resources)
     }
 
     pub fn dropping(&self, item_code: &str) -> Vec<Resource> {
@@ -40,20 +46,26 @@ impl ResourcesClient {
 }
 
 impl Persist<HashMap<String, Resource>> for ResourcesClient {
-    const PATH: &'static str = ".cache/resources.json";
+    const KEY: &'static str = "resources";
 
-    fn load_from_api(&self) -> HashMap<String, Resource> {
-        self.api
+    fn cache(&self) -> &Cache {
+        &self.cache
+    }
+
+    fn load_from_api(&self) -> Result<HashMap<String, Resource>, ClientError> {
+        Ok(self
+            .api
             .resources
             .get_all()
-            .unwrap()
+            .map_err(ClientError::api)?
             .into_iter()
             .map(|r| (r.code.clone(), Resource::new(r)))
-            .collect()
+            .collect())
     }
 
-    fn refresh(&self) {
-        *self.data.write().unwrap() = self.load_from_api();
+    fn refresh(&self) -> Result<(), ClientError> {
+        *self.data.write().unwrap() = self.fetch()?;
+        Ok(())
     }
 }
 
@@ -0,0 +1,198 @@
+use crate::character::error::{ACTION_ALREADY_IN_PROGRESS, CHARACTER_ON_COOLDOWN, RequestError};
+use std::time::Duration;
+
+/// Decides whether and when a failed character action is sent again.
+///
+/// Errors are sorted in `ErrorClass`es, each having its own `RetryRule`. An
+/// action is sent at most `max_attempts` times, after which the last error is
+/// returned wrapped in `RequestError::RetriesExhausted`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RetryPolicy {
+    max_attempts: u32,
+    base_delay: Duration,
+    max_delay: Duration,
+    jitter: f32,
+    on_cooldown: RetryRule,
+    on_server_error: RetryRule,
+    on_timeout: RetryRule,
+    on_action_in_progress: RetryRule,
+    on_transport_error: RetryRule,
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum RetryRule {
+    /// Return the error without retrying.
+    #[default]
+    Never,
+    /// Retry right away.
+    Immediately,
+    /// Retry after an exponentially growing delay.
+    Backoff,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ErrorClass {
+    /// The server considers the character still on cooldown (499), usually
+    /// because the local clock drifted.
+    Cooldown,
+    /// The server failed to handle the request (500, 502, 503, 504 or 520).
+    /// The game's own 5xx codes, such as `NO_PATH_AVAILABLE` (595), always
+    /// recur and are not part of this class.
+    ServerError,
+    /// The request timed out.
+    Timeout,
+    /// Another action of the character is being processed (486).
+    ActionInProgress,
+    /// The transport failed to send the request.
+    Transport,
+}
+
+impl RetryPolicy {
+    /// Policy sending every action only once.
+    pub fn none() -> Self {
+        Self {
+            max_attempts: 1,
+            ..Default::default()
+        }
+    }
+
+    pub fn max_attempts(mut self, max_attempts: u32) -> Self {
+        self.max_attempts = max_attempts.max(1);
+        self
+    }
+
+    /// Sets the delay before the first retry and the maximum delay between two retries.
+    pub fn backoff(mut self, base_delay: Duration, max_delay: Duration) -> Self {
+        self.base_delay = base_delay;
+        self.max_delay = max_delay.max(base_delay);
+        self
+    }
+
+    /// Sets the fraction, between 0 and 1, by which delays are randomly varied.
+    pub fn jitter(mut self, jitter: f32) -> Self {
+        self.jitter = jitter.clamp(0.0, 1.0);
+        self
+    }
+
+    pub fn rule(mut self, class: ErrorClass, rule: RetryRule) -> Self {
+        match class {
+            ErrorClass::Cooldown => self.on_cooldown = rule,
+            ErrorClass::ServerError => self.on_server_error = rule,
+            ErrorClass::Timeout => self.on_timeout = rule,
+            ErrorClass::ActionInProgress => self.on_action_in_progress = rule,
+            ErrorClass::Transport => self.on_transport_error = rule,
+        }
+        self
+    }
+
+    pub fn attempts(&self) -> u32 {
+        self.max_attempts
+    }
+
+    pub fn rule_for(&self, error: &RequestError) -> RetryRule {
+        match ErrorClass::of(error) {
+            Some(ErrorClass::Cooldown) => self.on_cooldown,
+            Some(ErrorClass::ServerError) => self.on_server_error,
+            Some(ErrorClass::Timeout) => self.on_timeout,
+            Some(ErrorClass::ActionInProgress) => self.on_action_in_progress,
+            Some(ErrorClass::Transport) => self.on_transport_error,
+            None => RetryRule::Never,
+        }
+    }
+
+    /// Returns the delay before sending the action again after its `attempt`th
+    /// failure, jitter excluded.
+    pub fn delay_for(&self, attempt: u32) -> Duration {
+        let factor = 2u32.saturating_pow(attempt.saturating_sub(1));
+        self.base_delay
+            .checked_mul(factor)
+            .unwrap_or(self.max_delay)
+            .min(self.max_delay)
+    }
+
+    pub(crate) fn jittered_delay_for(&self, attempt: u32) -> Duration {
+        let delay = self.delay_for(attempt);
+        if self.jitter <= 0.0 {
+            return delay;
+        }
+        delay.mul_f32(rand::random_range(1.0 - self.jitter..=1.0 + self.jitter))
+    }
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_attempts: 5,
+            base_delay: Duration::from_secs(1),
+            max_delay: Duration::from_secs(30),
+            jitter: 0.2,
+            on_cooldown: RetryRule::Immediately,
+            on_server_error: RetryRule::Backoff,
+            on_timeout: RetryRule::Backoff,
+            on_action_in_progress: RetryRule::Backoff,
+            on_transport_error: RetryRule::Never,
+        }
+    }
+}
+
+impl ErrorClass {
+    /// Returns the class of `error`, or `None` if it should never be retried.
+    pub fn of(error: &RequestError) -> Option<Self> {
+        match error {
+            RequestError::ResponseError(res) => match res.error.code as isize {
+                CHARACTER_ON_COOLDOWN => Some(Self::Cooldown),
+                ACTION_ALREADY_IN_PROGRESS => Some(Self::ActionInProgress),
+                500 | 502 | 503 | 504 | 520 => Some(Self::ServerError),
+                _ => None,
+            },
+            RequestError::Reqwest(e) if e.is_timeout() => Some(Self::Timeout),
+            RequestError::Transport(_) => Some(Self::Transport),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::character::error::{ApiErrorResponseSchema, ApiErrorSchema};
+
+    fn response_error(code: u32) -> RequestError {
+        RequestError::ResponseError(ApiErrorResponseSchema {
+            error: ApiErrorSchema {
+                code,
+                message: String::new(),
+            },
+        })
+    }
+
+    #[test]
+    fn rules_follow_error_class() {
+        let policy = RetryPolicy::default().rule(ErrorClass::ServerError, RetryRule::Never);
+        assert_eq!(
+            policy.rule_for(&response_error(499)),
+            RetryRule::Immediately
+        );
+        assert_eq!(policy.rule_for(&response_error(486)), RetryRule::Backoff);
+        assert_eq!(policy.rule_for(&response_error(520)), RetryRule::Never);
+        assert_eq!(policy.rule_for(&response_error(497)), RetryRule::Never);
+    }
+
+    #[test]
+    fn game_errors_are_not_server_errors() {
+        let policy = RetryPolicy::default();
+        assert_eq!(policy.rule_for(&response_error(503)), RetryRule::Backoff);
+        assert_eq!(policy.rule_for(&response_error(595)), RetryRule::Never);
+        assert_eq!(policy.rule_for(&response_error(598)), RetryRule::Never);
+    }
+
+    #[test]
+    fn delay_grows_exponentially_up_to_max() {
+        let policy =
+            RetryPolicy::default().backoff(Duration::from_secs(1), Duration::from_secs(10));
+        assert_eq!(policy.delay_for(1), Duration::from_secs(1));
+        assert_eq!(policy.delay_for(3), Duration::from_secs(4));
+        assert_eq!(policy.delay_for(5), Duration::from_secs(10));
+        assert_eq!(policy.delay_for(64), Duration::from_secs(10));
+    }
+}
@@ -1,29 +1,55 @@
+use crate::clock::{Clock, RealClock};
 use artifactsmmo_api_wrapper::ArtifactApi;
 use artifactsmmo_openapi::models::StatusResponseSchema;
 use chrono::{DateTime, TimeDelta, Utc};
 use log::{debug, error};
 use std::sync::{Arc, RwLock};
 
-#[derive(Default, Debug)]
+#[derive(Debug)]
 pub struct ServerClient {
     api: Arc<ArtifactApi>,
     pub server_offset: RwLock<TimeDelta>,
+    clock: RwLock<Arc<dyn Clock>>,
 }
 
 impl ServerClient {
-    pub(crate) fn new(api: Arc<ArtifactApi>) -> Self {
-        let server = Self {
+    pub(crate) fn new(api: Arc<ArtifactApi>, clock: Arc<dyn Clock>) -> Self {
+        Self {
             api,
             server_offset: RwLock::new(TimeDelta::default()),
-        };
-        server.update_offset();
-        server
+            clock: RwLock::new(clock),
+        }
+    }
+
+    /// Returns the clock used by every client sharing this server.
+    pub fn clock(&self) -> Arc<dyn Clock> {
+        self.clock.read().unwrap().clone()
+    }
+
+    pub fn set_clock(&self, clock: Arc<dyn Clock>) {
+        *self.clock.write().unwrap() = clock;
     }
 
     pub fn status(&self) -> Option<StatusResponseSchema> {
         self.api.server.status()
     }
 
+    /// Returns the version of the game running on the server.
+    pub fn version(&self) -> Option<String> {
+        Some(self.status()?.data.version)
+    }
+
+    /// Returns the stamp under which the static game data is cached: the game
+    /// version and the season number, data changing between seasons of a same
+    /// version.
+    pub fn cache_stamp(&self) -> Option<String> {
+        let status = self.status()?;
+        Some(cache_stamp(
+            &status.data.version,
+            status.data.season.map(|s| s.number),
+        ))
+    }
+
     pub fn time(&self) -> Option<DateTime<Utc>> {
         let status = self.status()?;
         let Ok(time) = DateTime::parse_from_rfc3339(&status.data.server_time) else {
@@ -33,7 +59,7 @@ impl ServerClient {
     }
 
     pub fn update_offset(&self) {
-        let now = Utc::now();
+        let now = self.clock().now();
         let Some(server_time) = self.time() else {
             error!("failed to update time offset");
             return;
@@ -49,3 +75,27 @@ impl ServerClient {
         debug!("synced time: {}", now - *self.server_offset.read().unwrap());
     }
 }
+
+impl Default for ServerClient {
+    fn default() -> Self {
+        Self::new(Arc::default(), Arc::new(RealClock))
+    }
+}
+
+fn cache_stamp(version: &str, season: Option<i32>) -> String {
+    match season {
+        Some(season) => format!("{version}+season.{season}"),
+        None => version.to_owned(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn cache_stamp_changes_with_the_season() {
+        assert_eq!(cache_stamp("6.0", None), "6.0");
+        assert_ne!(cache_stamp("6.0", Some(5)), cache_stamp("6.0", Some(6)));
+    }
+}
@@ -1,4 +1,4 @@
-use crate::{DataEntity, Persist, TasksRewardsClient, entities::Task};
+use crate::{Cache, ClientError, DataEntity, Persist, TasksRewardsClient, entities::Task};
 use artifactsmmo_api_wrapper::ArtifactApi;
 use sdk_derive::CollectionClient;
 use std::{
@@ -11,35 +11,47 @@ pub struct TasksClient {
     data: RwLock<HashMap<String, Task>>,
     pub reward: Arc<TasksRewardsClient>,
     api: Arc<ArtifactApi>,
+    cache: Arc<Cache>,
 }
 
 impl TasksClient {
-    pub(crate) fn new(api: Arc<ArtifactApi>, reward: Arc<TasksRewardsClient>) -> Self {
+    pub(crate) fn new(
+        api: Arc<ArtifactApi>,
+        cache: Arc<Cache>,
+        reward: Arc<TasksRewardsClient>,
+    ) -> Result<Self, ClientError> {
         let tasks = Self {
             data: Default::default(),
             reward,
             api,
+            cache,
         };
-        *tasks.data.write().unwrap() = tasks.load();
-        tasks
+        *tasks.data.write().unwrap() = tasks.load()?;
+        Ok(tasks)
     }
 }
 
 impl Persist<HashMap<String, Task>> for TasksClient {
-    const PATH: &'static str = ".cache/tasks.json";
+    const KEY: &'static str = "tasks";
 
-    fn load_from_api(&self) -> HashMap<String, Task> {
-        self.api
+    fn cache(&self) -> &Cache {
+        &self.cache
+    }
+
+    fn load_from_api(&self) -> Result<HashMap<String, Task>, ClientError> {
+        Ok(self
+            .api
             .tasks
             .get_all()
-            .unwrap()
+            .map_err(ClientError::api)?
             .into_iter()
             .map(|task| (task.code.clone(), Task::new(task)))
-            .collect()
+            .collect())
     }
 
-    fn refresh(&self) {
-        *self.data.write().unwrap() = self.load_from_api();
+    fn refresh(&self) -> Result<(), ClientError> {
+        *self.data.write().unwrap() = self.fetch()?;
+        Ok(())
     }
 }
 
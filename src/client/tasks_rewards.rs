@@ -1,4 +1,4 @@
-use crate::{CollectionClient, DataEntity, Persist, entities::TaskReward};
+use crate::{Cache, ClientError, CollectionClient, DataEntity, Persist, entities::TaskReward};
 use artifactsmmo_api_wrapper::ArtifactApi;
 use std::{
     collections::HashMap,
@@ -9,16 +9,18 @@ use std::{
 pub struct TasksRewardsClient {
     data: RwLock<HashMap<String, TaskReward>>,
     api: Arc<ArtifactApi>,
+    cache: Arc<Cache>,
 }
 
 impl TasksRewardsClient {
-    pub(crate) fn new(api: Arc<ArtifactApi>) -> Self {
+    pub(crate) fn new(api: Arc<ArtifactApi>, cache: Arc<Cache>) -> Result<Self, ClientError> {
         let rewards = Self {
             data: Default::default(),
             api,
+            cache,
         };
-        *rewards.data.write().unwrap() = rewards.load();
-        rewards
+        *rewards.data.write().unwrap() = rewards.load()?;
+        Ok(rewards)
     }
 
     pub fn max_quantity(&self) -> u32 {
@@ -30,20 +32,26 @@ impl TasksRewardsClient {
 }
 
 impl Persist<HashMap<String, TaskReward>> for TasksRewardsClient {
-    const PATH: &'static str = ".cache/tasks_rewards.json";
+    const KEY: &'static str = "tasks_rewards";
 
-    fn load_from_api(&self) -> HashMap<String, TaskReward> {
-        self.api
+    fn cache(&self) -> &Cache {
+        &self.cache
+    }
+
+    fn load_from_api(&self) -> Result<HashMap<String, TaskReward>, ClientError> {
+        Ok(self
+            .api
             .tasks
             .get_rewards()
-            .unwrap()
+            .map_err(ClientError::api)?
             .into_iter()
             .map(|tr| (tr.code.clone(), TaskReward::new(tr)))
-            .collect()
+            .collect())
     }
 
-    fn refresh(&self) {
-        *self.data.write().unwrap() = self.load_from_api();
+    fn refresh(&self) -> Result<(), ClientError> {
+        *self.data.write().unwrap() = self.fetch()?;
+        Ok(())
     }
 }
 
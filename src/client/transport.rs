@@ -0,0 +1,135 @@
+use crate::client::character::{ResponseSchema, action::Action, error::RequestError};
+use artifactsmmo_api_wrapper::ArtifactApi;
+use artifactsmmo_openapi::models::CharacterSchema;
+use std::{collections::VecDeque, fmt, sync::Mutex};
+
+/// Backend used by characters to send their action requests.
+/// `ArtifactApi` is the real reqwest backend, `MockTransport` serves canned responses from memory.
+pub trait Transport: Send + Sync + fmt::Debug {
+    fn request(&self, name: &str, action: &Action)
+    -> Result<Box<dyn ResponseSchema>, RequestError>;
+
+    /// Called with every character loaded by the client, so that backends
+    /// keeping their own state can start from the real character data.
+    fn register_character(&self, _character: &CharacterSchema) {}
+}
+
+impl Transport for ArtifactApi {
+    fn request(
+        &self,
+        name: &str,
+        action: &Action,
+    ) -> Result<Box<dyn ResponseSchema>, RequestError> {
+        action.request(name, self)
+    }
+}
+
+/// Transport of an offline client: every request fails with
+/// `RequestError::Offline` without touching the network.
+#[derive(Debug, Default)]
+pub struct OfflineTransport;
+
+impl Transport for OfflineTransport {
+    fn request(
+        &self,
+        _name: &str,
+        _action: &Action,
+    ) -> Result<Box<dyn ResponseSchema>, RequestError> {
+        Err(RequestError::Offline)
+    }
+}
+
+type Response = Result<Box<dyn ResponseSchema>, RequestError>;
+
+/// In-memory transport returning queued `*ResponseSchema` payloads in FIFO order.
+#[derive(Default)]
+pub struct MockTransport {
+    responses: Mutex<VecDeque<Response>>,
+    requests: Mutex<Vec<String>>,
+}
+
+impl MockTransport {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Queues a successful response.
+    pub fn push<T: ResponseSchema>(&self, response: T) {
+        self.responses
+            .lock()
+            .unwrap()
+            .push_back(Ok(Box::new(response)));
+    }
+
+    /// Queues an error response.
+    pub fn push_error(&self, error: RequestError) {
+        self.responses.lock().unwrap().push_back(Err(error));
+    }
+
+    /// Returns the requests received so far, formatted as `name: Action`.
+    pub fn requests(&self) -> Vec<String> {
+        self.requests.lock().unwrap().clone()
+    }
+
+    pub fn pending(&self) -> usize {
+        self.responses.lock().unwrap().len()
+    }
+}
+
+impl Transport for MockTransport {
+    fn request(
+        &self,
+        name: &str,
+        action: &Action,
+    ) -> Result<Box<dyn ResponseSchema>, RequestError> {
+        self.requests
+            .lock()
+            .unwrap()
+            .push(format!("{name}: {action}"));
+        self.responses
+            .lock()
+            .unwrap()
+            .pop_front()
+            .unwrap_or_else(|| {
+                Err(RequestError::Transport(format!(
+                    "no response queued for '{action}'"
+                )))
+            })
+    }
+}
+
+impl fmt::Debug for MockTransport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("MockTransport")
+            .field("pending", &self.pending())
+            .field("requests", &self.requests())
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use artifactsmmo_openapi::models::CharacterRestResponseSchema;
+
+    #[test]
+    fn mock_transport_serves_queued_responses() {
+        let transport = MockTransport::new();
+        transport.push(CharacterRestResponseSchema::default());
+        let res = transport.request("char", &Action::Rest);
+        assert!(res.is_ok_and(|r| r.downcast_ref::<CharacterRestResponseSchema>().is_some()));
+        assert!(matches!(
+            transport.request("char", &Action::Gather),
+            Err(RequestError::Transport(_))
+        ));
+        assert_eq!(transport.requests(), vec!["char: Rest", "char: Gather"]);
+    }
+
+    #[test]
+    fn offline_transport_rejects_every_request() {
+        assert!(matches!(
+            OfflineTransport.request("char", &Action::Rest),
+            Err(RequestError::Offline)
+        ));
+    }
+}
@@ -0,0 +1,145 @@
+use chrono::{DateTime, TimeDelta, Utc};
+use std::{
+    fmt,
+    sync::{Mutex, RwLock, mpsc::Sender},
+    thread,
+    time::Duration,
+};
+#[cfg(feature = "async")]
+use std::{future::Future, pin::Pin};
+
+#[cfg(feature = "async")]
+pub type SleepFuture<'a> = Pin<Box<dyn Future<Output = ()> + Send + 'a>>;
+
+/// Source of the current time used by the cooldown and event expiry logic.
+///
+/// `RealClock` follows the system time while `VirtualClock` only moves when
+/// told to, so that this logic can be tested deterministically and bot loops
+/// can run in accelerated time.
+pub trait Clock: Send + Sync + fmt::Debug {
+    fn now(&self) -> DateTime<Utc>;
+
+    /// Blocks until `duration` elapsed on this clock.
+    fn sleep(&self, duration: Duration);
+
+    /// Waits until `duration` elapsed on this clock without blocking the thread.
+    #[cfg(feature = "async")]
+    fn sleep_async(&self, duration: Duration) -> SleepFuture<'_>;
+
+    /// Registers `waker` to be sent a message each time the clock is moved by
+    /// hand, so that threads waiting for a time on it can check it again.
+    /// Clocks following the system time never send anything.
+    fn notify_on_change(&self, _waker: Sender<()>) {}
+}
+
+#[derive(Debug, Default, Clone, Copy)]
+pub struct RealClock;
+
+impl Clock for RealClock {
+    fn now(&self) -> DateTime<Utc> {
+        Utc::now()
+    }
+
+    fn sleep(&self, duration: Duration) {
+        thread::sleep(duration);
+    }
+
+    #[cfg(feature = "async")]
+    fn sleep_async(&self, duration: Duration) -> SleepFuture<'_> {
+        Box::pin(tokio::time::sleep(duration))
+    }
+}
+
+/// Clock whose time only moves when advanced. Sleeping on it returns
+/// immediately after fast-forwarding it by the slept duration.
+#[derive(Debug)]
+pub struct VirtualClock {
+    now: RwLock<DateTime<Utc>>,
+    wakers: Mutex<Vec<Sender<()>>>,
+}
+
+impl VirtualClock {
+    pub fn new(start: DateTime<Utc>) -> Self {
+        Self {
+            now: RwLock::new(start),
+            wakers: Default::default(),
+        }
+    }
+
+    /// Fast-forwards the clock by `duration`.
+    pub fn advance(&self, duration: Duration) {
+        *self.now.write().unwrap() += TimeDelta::from_std(duration).unwrap_or(TimeDelta::MAX);
+        self.wake();
+    }
+
+    pub fn set(&self, now: DateTime<Utc>) {
+        *self.now.write().unwrap() = now;
+        self.wake();
+    }
+
+    /// Notifies the registered wakers, forgetting the ones whose receiver
+    /// is gone.
+    fn wake(&self) {
+        self.wakers
+            .lock()
+            .unwrap()
+            .retain(|waker| waker.send(()).is_ok());
+    }
+}
+
+impl Default for VirtualClock {
+    /// Starts at the current system time.
+    fn default() -> Self {
+        Self::new(RealClock.now())
+    }
+}
+
+impl Clock for VirtualClock {
+    fn now(&self) -> DateTime<Utc> {
+        *self.now.read().unwrap()
+    }
+
+    fn sleep(&self, duration: Duration) {
+        self.advance(duration);
+    }
+
+    #[cfg(feature = "async")]
+    fn sleep_async(&self, duration: Duration) -> SleepFuture<'_> {
+        self.advance(duration);
+        Box::pin(std::future::ready(()))
+    }
+
+    fn notify_on_change(&self, waker: Sender<()>) {
+        self.wakers.lock().unwrap().push(waker);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn virtual_clock_fast_forwards_on_sleep() {
+        let start = DateTime::from_timestamp(0, 0).unwrap();
+        let clock = VirtualClock::new(start);
+        clock.sleep(Duration::from_secs(90));
+        assert_eq!(clock.now(), start + TimeDelta::seconds(90));
+        clock.advance(Duration::from_millis(500));
+        assert_eq!(clock.now(), start + TimeDelta::milliseconds(90_500));
+    }
+
+    #[test]
+    fn virtual_clock_wakes_waiters_when_moved() {
+        let clock = VirtualClock::default();
+        let (waker, woken) = std::sync::mpsc::channel();
+        clock.notify_on_change(waker);
+        assert!(woken.try_recv().is_err());
+        clock.advance(Duration::from_secs(1));
+        assert!(woken.try_recv().is_ok());
+        clock.set(clock.now());
+        assert!(woken.try_recv().is_ok());
+        drop(woken);
+        clock.advance(Duration::from_secs(1));
+        assert!(clock.wakers.lock().unwrap().is_empty());
+    }
+}
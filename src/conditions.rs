@@ -0,0 +1,256 @@
+use crate::{
+    GOLD, HasConditions, ItemContainer,
+    client::{
+        character::{CharacterClient, HasCharacterData},
+        items::LevelConditionCode,
+    },
+    gear::Slot,
+    skill::Skill,
+};
+use artifactsmmo_openapi::models::{ConditionOperator, ConditionSchema};
+use std::{
+    collections::{HashMap, HashSet},
+    fmt::{self, Display, Formatter},
+    str::FromStr,
+};
+use strum::IntoEnumIterator;
+
+/// Snapshot of everything a `ConditionSchema` can be evaluated against.
+///
+/// It can be taken from a `CharacterClient` and then edited freely to ask
+/// whether an item, map or transition would become usable in a hypothetical
+/// future state of the character.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct CharacterState {
+    pub skill_levels: HashMap<Skill, u32>,
+    pub gold: u32,
+    /// Quantities held in inventory, by item code.
+    pub inventory: HashMap<String, u32>,
+    /// Quantities equiped, by item code.
+    pub equipment: HashMap<String, u32>,
+    /// Codes of the completed achievements.
+    pub achievements: HashSet<String>,
+}
+
+impl CharacterState {
+    pub fn skill_level(&self, skill: Skill) -> u32 {
+        self.skill_levels.get(&skill).copied().unwrap_or(0)
+    }
+
+    pub fn with_skill_level(mut self, skill: Skill, level: u32) -> Self {
+        self.skill_levels.insert(skill, level);
+        self
+    }
+
+    pub fn with_gold(mut self, gold: u32) -> Self {
+        self.gold = gold;
+        self
+    }
+
+    pub fn with_item(mut self, item_code: &str, quantity: u32) -> Self {
+        *self.inventory.entry(item_code.to_owned()).or_default() += quantity;
+        self
+    }
+
+    pub fn with_equiped(mut self, item_code: &str, quantity: u32) -> Self {
+        *self.equipment.entry(item_code.to_owned()).or_default() += quantity;
+        self
+    }
+
+    pub fn with_achievement(mut self, code: &str) -> Self {
+        self.achievements.insert(code.to_owned());
+        self
+    }
+
+    pub fn meets(&self, entity: &impl HasConditions) -> bool {
+        entity
+            .conditions()
+            .iter()
+            .flatten()
+            .all(|c| self.evaluate(c).is_ok())
+    }
+
+    /// Returns the conditions of `entity` that are not met by this state.
+    pub fn unmet(&self, entity: &impl HasConditions) -> Vec<UnmetCondition> {
+        entity
+            .conditions()
+            .iter()
+            .flatten()
+            .filter_map(|c| self.evaluate(c).err())
+            .collect()
+    }
+
+    pub fn evaluate(&self, condition: &ConditionSchema) -> Result<(), UnmetCondition> {
+        let required = condition.value as u32;
+        let current = match condition.operator {
+            ConditionOperator::Cost => {
+                if condition.code == GOLD {
+                    self.gold
+                } else {
+                    self.quantity_in(&self.inventory, &condition.code)
+                }
+            }
+            ConditionOperator::HasItem => self.quantity_in(&self.equipment, &condition.code),
+            ConditionOperator::AchievementUnlocked => {
+                self.achievements.contains(&condition.code) as u32
+            }
+            ConditionOperator::Eq
+            | ConditionOperator::Ne
+            | ConditionOperator::Gt
+            | ConditionOperator::Lt => match LevelConditionCode::from_str(&condition.code) {
+                Ok(code) => self.skill_level(Skill::from(code)),
+                Err(_) => {
+                    return Err(UnmetCondition {
+                        condition: condition.clone(),
+                        current: 0,
+                        gap: None,
+                    });
+                }
+            },
+        };
+        let gap = match condition.operator {
+            ConditionOperator::Cost | ConditionOperator::HasItem => {
+                required.saturating_sub(current)
+            }
+            ConditionOperator::AchievementUnlocked => 1 - current,
+            ConditionOperator::Eq => current.abs_diff(required),
+            ConditionOperator::Ne => (current == required) as u32,
+            ConditionOperator::Gt => (required + 1).saturating_sub(current),
+            ConditionOperator::Lt => (current + 1).saturating_sub(required),
+        };
+        if gap == 0 {
+            return Ok(());
+        }
+        Err(UnmetCondition {
+            condition: condition.clone(),
+            current,
+            gap: Some(gap),
+        })
+    }
+
+    fn quantity_in(&self, items: &HashMap<String, u32>, item_code: &str) -> u32 {
+        items.get(item_code).copied().unwrap_or(0)
+    }
+}
+
+impl From<&CharacterClient> for CharacterState {
+    fn from(character: &CharacterClient) -> Self {
+        let mut inventory: HashMap<String, u32> = HashMap::new();
+        for slot in character.inventory().content().iter() {
+            if !slot.code.is_empty() {
+                *inventory.entry(slot.code.clone()).or_default() += slot.quantity as u32;
+            }
+        }
+        let mut equipment: HashMap<String, u32> = HashMap::new();
+        for slot in Slot::iter() {
+            let code = character.equiped_in(slot);
+            if !code.is_empty() {
+                *equipment.entry(code).or_default() += character.quantity_in_slot(slot);
+            }
+        }
+        Self {
+            skill_levels: Skill::iter()
+                .map(|s| (s, character.skill_level(s)))
+                .collect(),
+            gold: character.gold(),
+            inventory,
+            equipment,
+            achievements: character
+                .account()
+                .achievements()
+                .iter()
+                .filter(|a| a.completed_at.is_some())
+                .map(|a| a.code.clone())
+                .collect(),
+        }
+    }
+}
+
+/// A condition that is not met, with how far the evaluated state is from
+/// meeting it.
+#[derive(Debug, Clone, PartialEq)]
+pub struct UnmetCondition {
+    pub condition: ConditionSchema,
+    /// Current value of what the condition checks: a level, a quantity, an
+    /// amount of gold or 1 for an unlocked achievement.
+    pub current: u32,
+    /// Smallest change of `current` needed to meet the condition, `None` if
+    /// the condition code is not understood.
+    pub gap: Option<u32>,
+}
+
+impl Display for UnmetCondition {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} {} {} (current: {})",
+            self.condition.code, self.condition.operator, self.condition.value, self.current
+        )?;
+        if let Some(gap) = self.gap {
+            write!(f, ", off by {gap}")?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn condition(code: &str, operator: ConditionOperator, value: i32) -> ConditionSchema {
+        ConditionSchema {
+            code: code.to_owned(),
+            operator,
+            value,
+        }
+    }
+
+    #[test]
+    fn reports_how_far_conditions_are_from_being_met() {
+        let state = CharacterState::default()
+            .with_skill_level(Skill::Mining, 10)
+            .with_gold(100)
+            .with_item("copper_ore", 3);
+
+        let gap = |c: ConditionSchema| state.evaluate(&c).err().and_then(|u| u.gap);
+        assert_eq!(
+            gap(condition("mining_level", ConditionOperator::Gt, 14)),
+            Some(5)
+        );
+        assert_eq!(
+            gap(condition("mining_level", ConditionOperator::Gt, 9)),
+            None
+        );
+        assert_eq!(
+            gap(condition("mining_level", ConditionOperator::Lt, 8)),
+            Some(3)
+        );
+        assert_eq!(
+            gap(condition("mining_level", ConditionOperator::Eq, 12)),
+            Some(2)
+        );
+        assert_eq!(gap(condition(GOLD, ConditionOperator::Cost, 150)), Some(50));
+        assert_eq!(
+            gap(condition("copper_ore", ConditionOperator::Cost, 5)),
+            Some(2)
+        );
+        assert_eq!(
+            gap(condition(
+                "first_steps",
+                ConditionOperator::AchievementUnlocked,
+                1
+            )),
+            Some(1)
+        );
+        assert!(
+            state
+                .with_achievement("first_steps")
+                .evaluate(&condition(
+                    "first_steps",
+                    ConditionOperator::AchievementUnlocked,
+                    1
+                ))
+                .is_ok()
+        );
+    }
+}
@@ -1,6 +1,10 @@
 pub const MAX_LEVEL_DIFF: u32 = 10;
 
 pub const CRAFT_TIME: u32 = 5;
+pub const MOVE_CD_PER_TILE: u32 = 5;
+/// The API does not expose the cooldown of transitions, this is an estimate
+/// used to weight them against moves when routing.
+pub const TRANSITION_CD: u32 = 5;
 pub const MAX_LEVEL: u32 = 45;
 pub const TASK_CANCEL_PRICE: u32 = 1;
 pub const TASK_EXCHANGE_PRICE: u32 = 6;
@@ -0,0 +1,1191 @@
+use crate::{
+    Cache, CacheError, Code, DropRateSchemaExt, DropsItems, Gear, Level, Persist, Skill, Slot,
+    character::{
+        InventoryClient, ResponseSchema,
+        action::Action,
+        error::{
+            ALREADY_ON_MAP, ApiErrorResponseSchema, ApiErrorSchema, BANK_FULL,
+            BANK_GOLD_INSUFFICIENT, CHARACTER_GOLD_INSUFFICIENT, CHARACTER_NOT_FOUND,
+            CHARACTER_ON_COOLDOWN, CONDITIONS_NOT_MET, ENTITY_NOT_FOUND, ENTITY_NOT_FOUND_ON_MAP,
+            INVALID_SLOT_STATE, INVENTORY_FULL, ITEM_NOT_CONSUMABLE,
+            MISSING_ITEM_OR_INSUFFICIENT_QUANTITY, NO_PATH_AVAILABLE, RequestError,
+            SKILL_LEVEL_INSUFFICIENT,
+        },
+        projection::{
+            add_item, deposit_to_bank, quantity_in, remove_item, set_quantity_in, slot_of,
+            withdraw_from_bank,
+        },
+    },
+    check_lvl_diff,
+    client::{
+        items::ItemsClient, maps::MapsClient, monsters::MonstersClient, resources::ResourcesClient,
+    },
+    clock::{Clock, RealClock},
+    consts::{CRAFT_TIME, MAX_LEVEL, MOVE_CD_PER_TILE},
+    container::LimitedContainer,
+    entities::{Item, Map, Monster, Resource},
+    simulator::{FightParams, HasEffects, Participant, Simulator, gather_cd, time_to_rest},
+    transport::Transport,
+};
+use artifactsmmo_openapi::models::{
+    ActionType, BankGoldTransactionResponseSchema, BankItemTransactionResponseSchema, BankSchema,
+    CharacterFightResponseSchema, CharacterMovementResponseSchema, CharacterRestResponseSchema,
+    CharacterSchema, CooldownSchema, DeleteItemResponseSchema, DropRateSchema, DropSchema,
+    EquipmentResponseSchema, FightResult, MapContentType, MapLayer, MapSchema, SimpleItemSchema,
+    SkillResponseSchema, UseItemResponseSchema,
+};
+use chrono::TimeDelta;
+use itertools::Itertools;
+use rand::Rng;
+use std::{
+    cmp::{max, min},
+    collections::HashMap,
+    sync::{Arc, Mutex},
+};
+
+const MIN_REST_CD: u32 = 3;
+const BANK_CD: u32 = 3;
+const EQUIP_CD: u32 = 3;
+const USE_CD: u32 = 3;
+const DELETE_CD: u32 = 3;
+/// The server does not expose its XP formulas, actions grant `XP_PER_LEVEL` times
+/// the level of the monster, resource or item involved.
+const XP_PER_LEVEL: i32 = 10;
+
+/// Offline game server serving the character actions from the cached game data.
+///
+/// The emulator implements `Transport` and can be given to `Client::with_transport` to
+/// dry-run strategies: it keeps the characters and bank state in memory and applies the
+/// inventory, bank, XP, cooldown and fight changes of each action, fights being resolved
+/// with `Simulator::fight`. Actions without an emulated counterpart return
+/// `RequestError::Transport`.
+#[derive(Debug)]
+pub struct Emulator {
+    items: HashMap<String, Item>,
+    monsters: HashMap<String, Monster>,
+    resources: HashMap<String, Resource>,
+    maps: HashMap<(MapLayer, i32, i32), MapSchema>,
+    state: Mutex<EmulatorState>,
+    instant: bool,
+    clock: Arc<dyn Clock>,
+}
+
+impl Default for Emulator {
+    fn default() -> Self {
+        Self {
+            items: Default::default(),
+            monsters: Default::default(),
+            resources: Default::default(),
+            maps: Default::default(),
+            state: Default::default(),
+            instant: false,
+            clock: Arc::new(RealClock),
+        }
+    }
+}
+
+#[derive(Default, Debug)]
+struct EmulatorState {
+    characters: HashMap<String, CharacterSchema>,
+    bank: BankSchema,
+    bank_content: Vec<SimpleItemSchema>,
+}
+
+type Response = Result<Box<dyn ResponseSchema>, RequestError>;
+
+impl Emulator {
+    pub fn new(
+        items: Vec<Item>,
+        monsters: Vec<Monster>,
+        resources: Vec<Resource>,
+        maps: Vec<MapSchema>,
+    ) -> Self {
+        Self {
+            items: items
+                .into_iter()
+                .map(|i| (i.code().to_owned(), i))
+                .collect(),
+            monsters: monsters
+                .into_iter()
+                .map(|m| (m.code().to_owned(), m))
+                .collect(),
+            resources: resources
+                .into_iter()
+                .map(|r| (r.code().to_owned(), r))
+                .collect(),
+            maps: maps.into_iter().map(|m| ((m.layer, m.x, m.y), m)).collect(),
+            ..Default::default()
+        }
+    }
+
+    /// Loads the items, monsters, resources and maps persisted by the clients in `cache`.
+    pub fn from_cache(cache: &Cache) -> Result<Self, CacheError> {
+        let items: HashMap<String, Item> =
+            cache.read(<ItemsClient as Persist<HashMap<String, Item>>>::KEY)?;
+        let monsters: HashMap<String, Monster> =
+            cache.read(<MonstersClient as Persist<HashMap<String, Monster>>>::KEY)?;
+        let resources: HashMap<String, Resource> =
+            cache.read(<ResourcesClient as Persist<HashMap<String, Resource>>>::KEY)?;
+        let maps: Vec<MapSchema> = cache.read(<MapsClient as Persist<Vec<Map>>>::KEY)?;
+        Ok(Self::new(
+            items.into_values().collect(),
+            monsters.into_values().collect(),
+            resources.into_values().collect(),
+            maps,
+        ))
+    }
+
+    /// Cooldowns are still reported but expire immediately, so that clients do not wait.
+    pub fn instant(mut self) -> Self {
+        self.instant = true;
+        self
+    }
+
+    /// Computes the cooldowns with `clock`, which should be the one of the
+    /// client the emulator is given to.
+    pub fn with_clock(mut self, clock: Arc<dyn Clock>) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    pub fn with_character(self, character: CharacterSchema) -> Self {
+        self.register_character(&character);
+        self
+    }
+
+    pub fn with_bank(self, details: BankSchema, content: Vec<SimpleItemSchema>) -> Self {
+        {
+            let mut state = self.state.lock().unwrap();
+            state.bank = details;
+            state.bank_content = content;
+        }
+        self
+    }
+
+    pub fn character(&self, name: &str) -> Option<CharacterSchema> {
+        self.state.lock().unwrap().characters.get(name).cloned()
+    }
+
+    pub fn bank_details(&self) -> BankSchema {
+        self.state.lock().unwrap().bank.clone()
+    }
+
+    pub fn bank_content(&self) -> Vec<SimpleItemSchema> {
+        self.state.lock().unwrap().bank_content.clone()
+    }
+
+    fn map_of(&self, character: &CharacterSchema) -> Option<Map> {
+        self.maps
+            .get(&(character.layer, character.x, character.y))
+            .cloned()
+            .map(Map::new)
+    }
+
+    fn gear_of(&self, character: &CharacterSchema) -> Gear {
+        let item = |code: &String| self.items.get(code).cloned();
+        Gear {
+            weapon: item(&character.weapon_slot),
+            shield: item(&character.shield_slot),
+            helmet: item(&character.helmet_slot),
+            body_armor: item(&character.body_armor_slot),
+            leg_armor: item(&character.leg_armor_slot),
+            boots: item(&character.boots_slot),
+            ring1: item(&character.ring1_slot),
+            ring2: item(&character.ring2_slot),
+            amulet: item(&character.amulet_slot),
+            artifact1: item(&character.artifact1_slot),
+            artifact2: item(&character.artifact2_slot),
+            artifact3: item(&character.artifact3_slot),
+            utility1: item(&character.utility1_slot),
+            utility2: item(&character.utility2_slot),
+            rune: item(&character.rune_slot),
+            bag: item(&character.bag_slot),
+        }
+    }
+
+    fn apply_cooldown(
+        &self,
+        character: &mut CharacterSchema,
+        seconds: u32,
+        reason: ActionType,
+        cooldown: &mut CooldownSchema,
+    ) {
+        let now = self.clock.now();
+        let expiration = if self.instant {
+            now
+        } else {
+            now + TimeDelta::seconds(seconds as i64)
+        };
+        character.cooldown = seconds as _;
+        character.cooldown_expiration = Some(expiration.to_rfc3339());
+        cooldown.remaining_seconds = seconds as _;
+        cooldown.reason = reason;
+    }
+
+    fn r#move(&self, character: &mut CharacterSchema, x: i32, y: i32) -> Response {
+        if character.x == x && character.y == y {
+            return Err(response_error(ALREADY_ON_MAP, "character already on map"));
+        }
+        let Some(destination) = self.maps.get(&(character.layer, x, y)) else {
+            return Err(response_error(ENTITY_NOT_FOUND, "map not found"));
+        };
+        if Map::new(destination.clone()).is_blocked() {
+            return Err(response_error(NO_PATH_AVAILABLE, "no path available"));
+        }
+        let distance = character.x.abs_diff(x) + character.y.abs_diff(y);
+        character.x = x;
+        character.y = y;
+        let mut res = CharacterMovementResponseSchema::default();
+        self.apply_cooldown(
+            character,
+            distance * MOVE_CD_PER_TILE,
+            ActionType::Movement,
+            &mut res.data.cooldown,
+        );
+        res.data.destination = destination.clone().into();
+        res.data.character = character.clone().into();
+        Ok(Box::new(res))
+    }
+
+    fn fight(&self, character: &mut CharacterSchema) -> Response {
+        let Some(monster) = self
+            .map_of(character)
+            .and_then(|m| self.monsters.get(m.monster()?).cloned())
+        else {
+            return Err(response_error(ENTITY_NOT_FOUND_ON_MAP, "monster not found"));
+        };
+        if !inventory_of(character).has_room_for_drops_from(&monster) {
+            return Err(response_error(INVENTORY_FULL, "inventory is full"));
+        }
+        let participant = Participant::new(
+            character.name.clone(),
+            character.level as u32,
+            self.gear_of(character),
+            character.utility1_slot_quantity,
+            character.utility2_slot_quantity,
+            character.max_hp - character.hp,
+        );
+        let fight = Simulator::fight(participant, None, monster.clone(), FightParams::default());
+        let mut res = CharacterFightResponseSchema::default();
+        let mut drops = vec![];
+        let mut xp = 0;
+        let mut gold = 0;
+        if fight.is_winning() {
+            character.hp = max(fight.hp, 1);
+            drops = roll_drops(monster.drops());
+            drops
+                .iter()
+                .for_each(|d| add_item(character, &d.code, d.quantity as u32));
+            xp = xp_for(character.level as u32, monster.level());
+            gain_xp(character, Skill::Combat, xp);
+            gold = rand::rng()
+                .random_range(monster.min_gold()..=max(monster.min_gold(), monster.max_gold()));
+            character.gold += gold;
+        } else {
+            character.hp = 1;
+            character.layer = MapLayer::Overworld;
+            character.x = 0;
+            character.y = 0;
+        }
+        self.apply_cooldown(
+            character,
+            fight.cd,
+            ActionType::Fight,
+            &mut res.data.cooldown,
+        );
+        res.data.fight.result = fight.result;
+        res.data.fight.turns = fight.turns as _;
+        res.data.fight.characters.push(Default::default());
+        if let Some(result) = res.data.fight.characters.last_mut() {
+            result.character_name = character.name.clone();
+            result.xp = xp as _;
+            result.gold = gold as _;
+            result.drops = drops;
+        }
+        res.data.characters = vec![character.clone()];
+        Ok(Box::new(res))
+    }
+
+    fn rest(&self, character: &mut CharacterSchema) -> Response {
+        let missing_hp = max(character.max_hp - character.hp, 0) as u32;
+        character.hp = character.max_hp;
+        let mut res = CharacterRestResponseSchema::default();
+        self.apply_cooldown(
+            character,
+            max(time_to_rest(missing_hp), MIN_REST_CD),
+            ActionType::Rest,
+            &mut res.data.cooldown,
+        );
+        res.data.hp_restored = missing_hp as _;
+        res.data.character = character.clone().into();
+        Ok(Box::new(res))
+    }
+
+    fn gather(&self, character: &mut CharacterSchema) -> Response {
+        let Some(resource) = self
+            .map_of(character)
+            .and_then(|m| self.resources.get(m.resource()?).cloned())
+        else {
+            return Err(response_error(
+                ENTITY_NOT_FOUND_ON_MAP,
+                "resource not found",
+            ));
+        };
+        let skill = resource.skill();
+        if skill_level(character, skill) < resource.level() {
+            return Err(response_error(
+                SKILL_LEVEL_INSUFFICIENT,
+                "skill level insufficient",
+            ));
+        }
+        if !inventory_of(character).has_room_for_drops_from(&resource) {
+            return Err(response_error(INVENTORY_FULL, "inventory is full"));
+        }
+        let drops = roll_drops(resource.drops());
+        drops
+            .iter()
+            .for_each(|d| add_item(character, &d.code, d.quantity as u32));
+        let xp = xp_for(skill_level(character, skill), resource.level());
+        gain_xp(character, skill, xp);
+        let cooldown = gather_cd(
+            resource.level(),
+            self.gear_of(character).skill_cooldown_reduction(skill),
+        );
+        let mut res = SkillResponseSchema::default();
+        self.apply_cooldown(
+            character,
+            cooldown,
+            ActionType::Gathering,
+            &mut res.data.cooldown,
+        );
+        res.data.details.xp = xp as _;
+        res.data.details.items = drops;
+        res.data.character = character.clone().into();
+        Ok(Box::new(res))
+    }
+
+    fn craft(&self, character: &mut CharacterSchema, item_code: &str, quantity: u32) -> Response {
+        let Some(item) = self.items.get(item_code) else {
+            return Err(response_error(ENTITY_NOT_FOUND, "item not found"));
+        };
+        let Some(skill) = item.skill_to_craft() else {
+            return Err(response_error(ENTITY_NOT_FOUND, "item not craftable"));
+        };
+        if !self
+            .map_of(character)
+            .is_some_and(|m| m.content_code_is(skill.as_ref()))
+        {
+            return Err(response_error(
+                ENTITY_NOT_FOUND_ON_MAP,
+                "workshop not found",
+            ));
+        }
+        if skill_level(character, skill) < item.level() {
+            return Err(response_error(
+                SKILL_LEVEL_INSUFFICIENT,
+                "skill level insufficient",
+            ));
+        }
+        let mats = item.mats_for(quantity);
+        if !mats
+            .iter()
+            .all(|m| remove_item(character, &m.code, m.quantity))
+        {
+            return Err(response_error(
+                MISSING_ITEM_OR_INSUFFICIENT_QUANTITY,
+                "missing materials",
+            ));
+        }
+        let crafted = item.craft_schema().and_then(|s| s.quantity).unwrap_or(1) as u32 * quantity;
+        if !inventory_of(character).has_room_for(item_code, crafted) {
+            return Err(response_error(INVENTORY_FULL, "inventory is full"));
+        }
+        add_item(character, item_code, crafted);
+        let xp = xp_for(skill_level(character, skill), item.level()) * quantity as i32;
+        gain_xp(character, skill, xp);
+        let mut res = SkillResponseSchema::default();
+        self.apply_cooldown(
+            character,
+            CRAFT_TIME * quantity,
+            ActionType::Crafting,
+            &mut res.data.cooldown,
+        );
+        res.data.details.xp = xp as _;
+        res.data.details.items = vec![DropSchema {
+            code: item_code.to_owned(),
+            quantity: crafted as _,
+        }];
+        res.data.character = character.clone().into();
+        Ok(Box::new(res))
+    }
+
+    fn delete(&self, character: &mut CharacterSchema, item_code: &str, quantity: u32) -> Response {
+        if !remove_item(character, item_code, quantity) {
+            return Err(response_error(
+                MISSING_ITEM_OR_INSUFFICIENT_QUANTITY,
+                "missing item",
+            ));
+        }
+        let mut cooldown = CooldownSchema::default();
+        self.apply_cooldown(character, DELETE_CD, ActionType::DeleteItem, &mut cooldown);
+        let mut res = DeleteItemResponseSchema::default();
+        res.data.item = SimpleItemSchema {
+            code: item_code.to_owned(),
+            quantity,
+        }
+        .into();
+        res.data.character = character.clone().into();
+        Ok(Box::new(res))
+    }
+
+    fn deposit_item(
+        &self,
+        character: &mut CharacterSchema,
+        state: &mut EmulatorState,
+        items: &[SimpleItemSchema],
+    ) -> Response {
+        self.check_bank_on_map(character)?;
+        if !items
+            .iter()
+            .all(|i| remove_item(character, &i.code, i.quantity))
+        {
+            return Err(response_error(
+                MISSING_ITEM_OR_INSUFFICIENT_QUANTITY,
+                "missing item",
+            ));
+        }
+        let mut content = state.bank_content.clone();
+        deposit_to_bank(&mut content, items);
+        if content.len() > state.bank.slots as usize {
+            return Err(response_error(BANK_FULL, "bank is full"));
+        }
+        state.bank_content = content;
+        let mut res = BankItemTransactionResponseSchema::default();
+        self.apply_cooldown(
+            character,
+            BANK_CD,
+            ActionType::DepositItem,
+            &mut res.data.cooldown,
+        );
+        res.data.items = items.to_vec();
+        res.data.bank = state.bank_content.clone();
+        res.data.character = character.clone().into();
+        Ok(Box::new(res))
+    }
+
+    fn withdraw_item(
+        &self,
+        character: &mut CharacterSchema,
+        state: &mut EmulatorState,
+        items: &[SimpleItemSchema],
+    ) -> Response {
+        self.check_bank_on_map(character)?;
+        let mut content = state.bank_content.clone();
+        if !withdraw_from_bank(&mut content, items) {
+            return Err(response_error(
+                MISSING_ITEM_OR_INSUFFICIENT_QUANTITY,
+                "missing item in bank",
+            ));
+        }
+        if !inventory_of(character).has_room_for_multiple(items) {
+            return Err(response_error(INVENTORY_FULL, "inventory is full"));
+        }
+        items
+            .iter()
+            .for_each(|i| add_item(character, &i.code, i.quantity));
+        state.bank_content = content;
+        let mut res = BankItemTransactionResponseSchema::default();
+        self.apply_cooldown(
+            character,
+            BANK_CD,
+            ActionType::WithdrawItem,
+            &mut res.data.cooldown,
+        );
+        res.data.items = items.to_vec();
+        res.data.bank = state.bank_content.clone();
+        res.data.character = character.clone().into();
+        Ok(Box::new(res))
+    }
+
+    fn deposit_gold(
+        &self,
+        character: &mut CharacterSchema,
+        state: &mut EmulatorState,
+        quantity: u32,
+    ) -> Response {
+        self.check_bank_on_map(character)?;
+        if (character.gold as u32) < quantity {
+            return Err(response_error(
+                CHARACTER_GOLD_INSUFFICIENT,
+                "insufficient gold",
+            ));
+        }
+        character.gold -= quantity as i32;
+        state.bank.gold += quantity;
+        let mut res = BankGoldTransactionResponseSchema::default();
+        self.apply_cooldown(
+            character,
+            BANK_CD,
+            ActionType::DepositGold,
+            &mut res.data.cooldown,
+        );
+        res.data.bank.quantity = state.bank.gold;
+        res.data.character = character.clone().into();
+        Ok(Box::new(res))
+    }
+
+    fn withdraw_gold(
+        &self,
+        character: &mut CharacterSchema,
+        state: &mut EmulatorState,
+        quantity: u32,
+    ) -> Response {
+        self.check_bank_on_map(character)?;
+        if state.bank.gold < quantity {
+            return Err(response_error(
+                BANK_GOLD_INSUFFICIENT,
+                "insufficient gold in bank",
+            ));
+        }
+        state.bank.gold -= quantity;
+        character.gold += quantity as i32;
+        let mut res = BankGoldTransactionResponseSchema::default();
+        self.apply_cooldown(
+            character,
+            BANK_CD,
+            ActionType::WithdrawGold,
+            &mut res.data.cooldown,
+        );
+        res.data.bank.quantity = state.bank.gold;
+        res.data.character = character.clone().into();
+        Ok(Box::new(res))
+    }
+
+    fn equip(
+        &self,
+        character: &mut CharacterSchema,
+        item_code: &str,
+        slot: Slot,
+        quantity: u32,
+    ) -> Response {
+        let Some(item) = self.items.get(item_code) else {
+            return Err(response_error(ENTITY_NOT_FOUND, "item not found"));
+        };
+        if !item.is_equipable() || (character.level as u32) < item.level() {
+            return Err(response_error(CONDITIONS_NOT_MET, "conditions not met"));
+        }
+        let equiped = slot_of(character, slot).clone();
+        if !equiped.is_empty()
+            && (equiped != item_code
+                || quantity_in(character, slot) + quantity > slot.max_quantity())
+        {
+            return Err(response_error(INVALID_SLOT_STATE, "slot is not empty"));
+        }
+        if !remove_item(character, item_code, quantity) {
+            return Err(response_error(
+                MISSING_ITEM_OR_INSUFFICIENT_QUANTITY,
+                "missing item",
+            ));
+        }
+        *slot_of(character, slot) = item_code.to_owned();
+        set_quantity_in(character, slot, quantity_in(character, slot) + quantity);
+        character.max_hp += item.health();
+        character.inventory_max_items += item.inventory_space();
+        let mut res = EquipmentResponseSchema::default();
+        self.apply_cooldown(
+            character,
+            EQUIP_CD,
+            ActionType::Equip,
+            &mut res.data.cooldown,
+        );
+        res.data.slot = slot.into();
+        res.data.item.code = item_code.to_owned();
+        res.data.character = character.clone().into();
+        Ok(Box::new(res))
+    }
+
+    fn unequip(&self, character: &mut CharacterSchema, slot: Slot, quantity: u32) -> Response {
+        let equiped = slot_of(character, slot).clone();
+        let Some(item) = self.items.get(&equiped) else {
+            return Err(response_error(INVALID_SLOT_STATE, "slot is empty"));
+        };
+        let Some(remaining) = quantity_in(character, slot).checked_sub(quantity) else {
+            return Err(response_error(
+                INVALID_SLOT_STATE,
+                "quantity exceeds the equipped quantity",
+            ));
+        };
+        if remaining == 0 {
+            slot_of(character, slot).clear();
+            character.max_hp -= item.health();
+            character.hp = min(character.hp, character.max_hp);
+            character.inventory_max_items -= item.inventory_space();
+        }
+        set_quantity_in(character, slot, remaining);
+        if !inventory_of(character).has_room_for(&equiped, quantity) {
+            return Err(response_error(INVENTORY_FULL, "inventory is full"));
+        }
+        add_item(character, &equiped, quantity);
+        let mut res = EquipmentResponseSchema::default();
+        self.apply_cooldown(
+            character,
+            EQUIP_CD,
+            ActionType::Unequip,
+            &mut res.data.cooldown,
+        );
+        res.data.slot = slot.into();
+        res.data.item.code = equiped;
+        res.data.character = character.clone().into();
+        Ok(Box::new(res))
+    }
+
+    fn use_item(
+        &self,
+        character: &mut CharacterSchema,
+        item_code: &str,
+        quantity: u32,
+    ) -> Response {
+        let Some(item) = self.items.get(item_code) else {
+            return Err(response_error(ENTITY_NOT_FOUND, "item not found"));
+        };
+        if !item.is_consumable() {
+            return Err(response_error(ITEM_NOT_CONSUMABLE, "item not consumable"));
+        }
+        if !remove_item(character, item_code, quantity) {
+            return Err(response_error(
+                MISSING_ITEM_OR_INSUFFICIENT_QUANTITY,
+                "missing item",
+            ));
+        }
+        character.hp = min(
+            character.hp + item.heal() * quantity as i32,
+            character.max_hp,
+        );
+        let mut res = UseItemResponseSchema::default();
+        self.apply_cooldown(character, USE_CD, ActionType::Use, &mut res.data.cooldown);
+        res.data.item.code = item_code.to_owned();
+        res.data.character = character.clone().into();
+        Ok(Box::new(res))
+    }
+
+    fn check_bank_on_map(&self, character: &CharacterSchema) -> Result<(), RequestError> {
+        if self
+            .map_of(character)
+            .is_some_and(|m| m.content_type_is(MapContentType::Bank))
+        {
+            Ok(())
+        } else {
+            Err(response_error(ENTITY_NOT_FOUND_ON_MAP, "bank not found"))
+        }
+    }
+}
+
+impl Transport for Emulator {
+    fn request(&self, name: &str, action: &Action) -> Response {
+        let mut state = self.state.lock().unwrap();
+        let Some(mut character) = state.characters.get(name).cloned() else {
+            return Err(response_error(CHARACTER_NOT_FOUND, "character not found"));
+        };
+        if character
+            .cooldown_expiration
+            .as_ref()
+            .and_then(|exp| chrono::DateTime::parse_from_rfc3339(exp).ok())
+            .is_some_and(|exp| exp > self.clock.now())
+        {
+            return Err(response_error(
+                CHARACTER_ON_COOLDOWN,
+                "character in cooldown",
+            ));
+        }
+        let res = match action {
+            Action::Move { x, y } => self.r#move(&mut character, *x, *y),
+            Action::Fight { participants: None } => self.fight(&mut character),
+            Action::Rest => self.rest(&mut character),
+            Action::Gather => self.gather(&mut character),
+            Action::Craft {
+                item_code,
+                quantity,
+            } => self.craft(&mut character, item_code, *quantity),
+            Action::Delete {
+                item_code,
+                quantity,
+            } => self.delete(&mut character, item_code, *quantity),
+            Action::DepositItem { items } => self.deposit_item(&mut character, &mut state, items),
+            Action::WithdrawItem { items } => self.withdraw_item(&mut character, &mut state, items),
+            Action::DepositGold { quantity } => {
+                self.deposit_gold(&mut character, &mut state, *quantity)
+            }
+            Action::WithdrawGold { quantity } => {
+                self.withdraw_gold(&mut character, &mut state, *quantity)
+            }
+            Action::Equip {
+                item_code,
+                slot,
+                quantity,
+            } => self.equip(&mut character, item_code, *slot, *quantity),
+            Action::Unequip { slot, quantity } => self.unequip(&mut character, *slot, *quantity),
+            Action::UseItem {
+                item_code,
+                quantity,
+            } => self.use_item(&mut character, item_code, *quantity),
+            _ => Err(RequestError::Transport(format!(
+                "'{action}' is not supported by the emulator"
+            ))),
+        };
+        if res.is_ok() {
+            state.characters.insert(name.to_owned(), character);
+        }
+        res
+    }
+
+    fn register_character(&self, character: &CharacterSchema) {
+        self.state
+            .lock()
+            .unwrap()
+            .characters
+            .entry(character.name.clone())
+            .or_insert_with(|| character.clone());
+    }
+}
+
+fn response_error(code: isize, message: &str) -> RequestError {
+    RequestError::ResponseError(ApiErrorResponseSchema {
+        error: ApiErrorSchema {
+            code: code as u32,
+            message: message.to_owned(),
+        },
+    })
+}
+
+/// Rolls each drop with the chance given by `DropRateSchemaExt::rate`.
+fn roll_drops(drops: &[DropRateSchema]) -> Vec<DropSchema> {
+    let mut rng = rand::rng();
+    drops
+        .iter()
+        .filter_map(|d| {
+            rng.random_bool(d.rate().into()).then(|| DropSchema {
+                code: d.code.clone(),
+                quantity: rng.random_range(d.min_quantity..=max(d.min_quantity, d.max_quantity))
+                    as _,
+            })
+        })
+        .collect_vec()
+}
+
+fn xp_for(level: u32, entity_level: u32) -> i32 {
+    if check_lvl_diff(level, entity_level) {
+        entity_level as i32 * XP_PER_LEVEL
+    } else {
+        0
+    }
+}
+
+fn inventory_of(character: &CharacterSchema) -> InventoryClient {
+    InventoryClient::new(Arc::new(character.clone()))
+}
+
+fn skill_level(character: &CharacterSchema, skill: Skill) -> u32 {
+    (match skill {
+        Skill::Combat => character.level,
+        Skill::Mining => character.mining_level,
+        Skill::Woodcutting => character.woodcutting_level,
+        Skill::Fishing => character.fishing_level,
+        Skill::Weaponcrafting => character.weaponcrafting_level,
+        Skill::Gearcrafting => character.gearcrafting_level,
+        Skill::Jewelrycrafting => character.jewelrycrafting_level,
+        Skill::Cooking => character.cooking_level,
+        Skill::Alchemy => character.alchemy_level,
+    }) as u32
+}
+
+fn gain_xp(character: &mut CharacterSchema, skill: Skill, xp: i32) {
+    let c = character;
+    let (level, current, max_xp) = match skill {
+        Skill::Combat => (&mut c.level, &mut c.xp, c.max_xp),
+        Skill::Mining => (&mut c.mining_level, &mut c.mining_xp, c.mining_max_xp),
+        Skill::Woodcutting => (
+            &mut c.woodcutting_level,
+            &mut c.woodcutting_xp,
+            c.woodcutting_max_xp,
+        ),
+        Skill::Fishing => (&mut c.fishing_level, &mut c.fishing_xp, c.fishing_max_xp),
+        Skill::Weaponcrafting => (
+            &mut c.weaponcrafting_level,
+            &mut c.weaponcrafting_xp,
+            c.weaponcrafting_max_xp,
+        ),
+        Skill::Gearcrafting => (
+            &mut c.gearcrafting_level,
+            &mut c.gearcrafting_xp,
+            c.gearcrafting_max_xp,
+        ),
+        Skill::Jewelrycrafting => (
+            &mut c.jewelrycrafting_level,
+            &mut c.jewelrycrafting_xp,
+            c.jewelrycrafting_max_xp,
+        ),
+        Skill::Cooking => (&mut c.cooking_level, &mut c.cooking_xp, c.cooking_max_xp),
+        Skill::Alchemy => (&mut c.alchemy_level, &mut c.alchemy_xp, c.alchemy_max_xp),
+    };
+    *current += xp;
+    while max_xp > 0 && *current >= max_xp && (*level as u32) < MAX_LEVEL {
+        *current -= max_xp;
+        *level += 1;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::container::ItemContainer;
+    use artifactsmmo_openapi::models::{
+        CraftSchema, CraftSkill, GatheringSkill, InteractionSchema, InventorySlot, ItemSchema,
+        MapContentSchema, MonsterSchema, ResourceSchema, SimpleEffectSchema,
+    };
+
+    fn map(x: i32, r#type: MapContentType, code: &str) -> MapSchema {
+        MapSchema {
+            x,
+            interactions: InteractionSchema {
+                content: Some(
+                    MapContentSchema {
+                        r#type,
+                        code: code.to_owned(),
+                    }
+                    .into(),
+                ),
+                ..Default::default()
+            }
+            .into(),
+            ..Default::default()
+        }
+    }
+
+    fn item(code: &str, r#type: &str, effect: &str, value: i32) -> Item {
+        Item::new(ItemSchema {
+            code: code.to_owned(),
+            level: 1,
+            r#type: r#type.to_owned(),
+            effects: Some(vec![SimpleEffectSchema {
+                code: effect.to_owned(),
+                value,
+                ..Default::default()
+            }]),
+            ..Default::default()
+        })
+    }
+
+    /// Level 1 character at `x` with 20 empty inventory slots.
+    fn character(x: i32) -> CharacterSchema {
+        CharacterSchema {
+            name: "char".to_owned(),
+            x,
+            level: 1,
+            mining_level: 1,
+            weaponcrafting_level: 1,
+            hp: 120,
+            max_hp: 120,
+            inventory_max_items: 100,
+            inventory: Some(
+                (0..20)
+                    .map(|slot| InventorySlot {
+                        slot,
+                        ..Default::default()
+                    })
+                    .collect(),
+            ),
+            ..Default::default()
+        }
+    }
+
+    fn drop_of(code: &str, quantity: u32) -> DropRateSchema {
+        DropRateSchema {
+            code: code.to_owned(),
+            rate: 1,
+            min_quantity: quantity,
+            max_quantity: quantity,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn winning_fight_grants_drops_xp_and_gold() {
+        let chicken = Monster::new(MonsterSchema {
+            code: "chicken".to_owned(),
+            level: 1,
+            hp: 30,
+            attack_fire: 5,
+            min_gold: 3,
+            max_gold: 3,
+            drops: vec![drop_of("feather", 2)],
+            ..Default::default()
+        });
+        let emulator = Emulator::new(
+            vec![item("stick", "weapon", "attack_fire", 10)],
+            vec![chicken],
+            vec![],
+            vec![map(0, MapContentType::Monster, "chicken")],
+        )
+        .instant()
+        .with_character(CharacterSchema {
+            weapon_slot: "stick".to_owned(),
+            ..character(0)
+        });
+
+        // The character strikes first: 3 hits of 10 against 2 of 5.
+        assert!(
+            emulator
+                .request("char", &Action::Fight { participants: None })
+                .is_ok()
+        );
+        let character = emulator.character("char").unwrap();
+        assert_eq!(character.hp, 110);
+        assert_eq!(character.xp, XP_PER_LEVEL);
+        assert_eq!(character.gold, 3);
+        assert_eq!(character.cooldown, 12);
+        assert_eq!(inventory_of(&character).total_of("feather"), 2);
+    }
+
+    #[test]
+    fn gather_requires_the_skill_level() {
+        let resource = |code: &str, level| {
+            Resource::new(ResourceSchema {
+                code: code.to_owned(),
+                level,
+                skill: GatheringSkill::Mining,
+                drops: vec![drop_of("copper_ore", 1)],
+                ..Default::default()
+            })
+        };
+        let emulator = Emulator::new(
+            vec![],
+            vec![],
+            vec![resource("copper_rocks", 1), resource("iron_rocks", 10)],
+            vec![
+                map(0, MapContentType::Resource, "copper_rocks"),
+                map(1, MapContentType::Resource, "iron_rocks"),
+            ],
+        )
+        .instant()
+        .with_character(character(0));
+
+        assert!(emulator.request("char", &Action::Gather).is_ok());
+        let character = emulator.character("char").unwrap();
+        assert_eq!(inventory_of(&character).total_of("copper_ore"), 1);
+        assert_eq!(character.mining_xp, XP_PER_LEVEL);
+        assert_eq!(character.cooldown, gather_cd(1, 0) as i32);
+
+        assert!(
+            emulator
+                .request("char", &Action::Move { x: 1, y: 0 })
+                .is_ok()
+        );
+        assert!(matches!(
+            emulator.request("char", &Action::Gather),
+            Err(RequestError::ResponseError(e)) if e.error.code as isize == SKILL_LEVEL_INSUFFICIENT
+        ));
+    }
+
+    #[test]
+    fn craft_consumes_the_materials_at_the_workshop() {
+        let dagger = Item::new(ItemSchema {
+            code: "copper_dagger".to_owned(),
+            level: 1,
+            r#type: "weapon".to_owned(),
+            craft: Some(Box::new(CraftSchema {
+                skill: Some(CraftSkill::Weaponcrafting),
+                items: Some(vec![SimpleItemSchema {
+                    code: "copper_bar".to_owned(),
+                    quantity: 6,
+                }]),
+                quantity: Some(1),
+                ..Default::default()
+            })),
+            ..Default::default()
+        });
+        let mut crafter = character(0);
+        add_item(&mut crafter, "copper_bar", 6);
+        let emulator = Emulator::new(
+            vec![dagger],
+            vec![],
+            vec![],
+            vec![map(0, MapContentType::Workshop, "weaponcrafting")],
+        )
+        .instant()
+        .with_character(crafter);
+        let craft = Action::Craft {
+            item_code: "copper_dagger",
+            quantity: 1,
+        };
+
+        assert!(emulator.request("char", &craft).is_ok());
+        let character = emulator.character("char").unwrap();
+        assert_eq!(inventory_of(&character).total_of("copper_dagger"), 1);
+        assert_eq!(inventory_of(&character).total_of("copper_bar"), 0);
+        assert_eq!(character.weaponcrafting_xp, XP_PER_LEVEL);
+        assert_eq!(character.cooldown, CRAFT_TIME as i32);
+        assert!(matches!(
+            emulator.request("char", &craft),
+            Err(RequestError::ResponseError(e))
+                if e.error.code as isize == MISSING_ITEM_OR_INSUFFICIENT_QUANTITY
+        ));
+    }
+
+    #[test]
+    fn equip_applies_the_item_effects() {
+        let mut wearer = character(0);
+        add_item(&mut wearer, "copper_helmet", 1);
+        add_item(&mut wearer, "copper_boots", 1);
+        let emulator = Emulator::new(
+            vec![
+                item("copper_helmet", "helmet", "hp", 10),
+                item("copper_boots", "boots", "hp", 5),
+            ],
+            vec![],
+            vec![],
+            vec![],
+        )
+        .instant()
+        .with_character(wearer);
+        let equip = |item_code, slot| Action::Equip {
+            item_code,
+            slot,
+            quantity: 1,
+        };
+
+        assert!(
+            emulator
+                .request("char", &equip("copper_helmet", Slot::Helmet))
+                .is_ok()
+        );
+        let character = emulator.character("char").unwrap();
+        assert_eq!(character.helmet_slot, "copper_helmet");
+        assert_eq!(character.max_hp, 130);
+        assert_eq!(inventory_of(&character).total_of("copper_helmet"), 0);
+        assert!(matches!(
+            emulator.request("char", &equip("copper_boots", Slot::Helmet)),
+            Err(RequestError::ResponseError(e)) if e.error.code as isize == INVALID_SLOT_STATE
+        ));
+    }
+
+    #[test]
+    fn bank_gold_transactions_move_gold() {
+        let emulator = Emulator::new(
+            vec![],
+            vec![],
+            vec![],
+            vec![map(0, MapContentType::Bank, "bank")],
+        )
+        .instant()
+        .with_character(CharacterSchema {
+            gold: 100,
+            ..character(0)
+        });
+
+        assert!(
+            emulator
+                .request("char", &Action::DepositGold { quantity: 60 })
+                .is_ok()
+        );
+        assert_eq!(emulator.bank_details().gold, 60);
+        assert_eq!(emulator.character("char").unwrap().gold, 40);
+        assert!(matches!(
+            emulator.request("char", &Action::WithdrawGold { quantity: 61 }),
+            Err(RequestError::ResponseError(e)) if e.error.code as isize == BANK_GOLD_INSUFFICIENT
+        ));
+        assert_eq!(emulator.character("char").unwrap().gold, 40);
+    }
+
+    #[test]
+    fn deposit_item_moves_items_to_bank() {
+        let bank = MapSchema {
+            x: 4,
+            y: 1,
+            interactions: InteractionSchema {
+                content: Some(
+                    MapContentSchema {
+                        r#type: MapContentType::Bank,
+                        code: "bank".to_owned(),
+                    }
+                    .into(),
+                ),
+                ..Default::default()
+            }
+            .into(),
+            ..Default::default()
+        };
+        let emulator = Emulator::new(vec![], vec![], vec![], vec![bank])
+            .instant()
+            .with_bank(
+                BankSchema {
+                    slots: 20,
+                    ..Default::default()
+                },
+                vec![],
+            )
+            .with_character(CharacterSchema {
+                name: "char".to_owned(),
+                x: 4,
+                y: 1,
+                inventory_max_items: 100,
+                inventory: Some(vec![InventorySlot {
+                    slot: 0,
+                    code: "copper_ore".to_owned(),
+                    quantity: 5,
+                }]),
+                ..Default::default()
+            });
+        let items = [SimpleItemSchema {
+            code: "copper_ore".to_owned(),
+            quantity: 5,
+        }];
+        assert!(
+            emulator
+                .request("char", &Action::DepositItem { items: &items })
+                .is_ok()
+        );
+        assert_eq!(emulator.bank_content(), items.to_vec());
+        let character = emulator.character("char").unwrap();
+        assert_eq!(inventory_of(&character).total_of("copper_ore"), 0);
+        assert!(
+            emulator
+                .request("char", &Action::DepositItem { items: &items })
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn unequip_cannot_remove_more_than_equipped() {
+        let potion = Item::new(ItemSchema {
+            code: "potion".to_owned(),
+            ..Default::default()
+        });
+        let emulator = Emulator::new(vec![potion], vec![], vec![], vec![])
+            .instant()
+            .with_character(CharacterSchema {
+                name: "char".to_owned(),
+                inventory_max_items: 100,
+                inventory: Some(vec![]),
+                utility1_slot: "potion".to_owned(),
+                utility1_slot_quantity: 2,
+                ..Default::default()
+            });
+        let unequip = |quantity| Action::Unequip {
+            slot: Slot::Utility1,
+            quantity,
+        };
+
+        assert!(matches!(
+            emulator.request("char", &unequip(3)),
+            Err(RequestError::ResponseError(e)) if e.error.code as isize == INVALID_SLOT_STATE
+        ));
+        let character = emulator.character("char").unwrap();
+        assert_eq!(character.utility1_slot_quantity, 2);
+        assert_eq!(inventory_of(&character).total_of("potion"), 0);
+
+        assert!(emulator.request("char", &unequip(2)).is_ok());
+        let character = emulator.character("char").unwrap();
+        assert!(character.utility1_slot.is_empty());
+        assert_eq!(inventory_of(&character).total_of("potion"), 2);
+    }
+}
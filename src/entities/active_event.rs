@@ -1,6 +1,7 @@
 use std::{ops::Deref, sync::Arc};
 
 use artifactsmmo_openapi::models::{ActiveEventSchema, MapSchema};
+use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 
 #[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
@@ -11,10 +12,40 @@ impl ActiveEvent {
         Self(Arc::new(schema))
     }
 
+    pub fn code(&self) -> &str {
+        &self.0.code
+    }
+
+    /// Returns the code of the monster, resource or NPC brought by the event.
+    pub fn content_code(&self) -> Option<&str> {
+        self.0
+            .map
+            .interactions
+            .content
+            .as_deref()
+            .map(|c| c.code.as_str())
+    }
+
+    pub fn created_at_time(&self) -> Option<DateTime<Utc>> {
+        DateTime::parse_from_rfc3339(&self.0.created_at)
+            .ok()
+            .map(|e| e.to_utc())
+    }
+
     pub fn expiration(&self) -> &str {
         &self.0.expiration
     }
 
+    pub fn expiration_time(&self) -> Option<DateTime<Utc>> {
+        DateTime::parse_from_rfc3339(self.expiration())
+            .ok()
+            .map(|e| e.to_utc())
+    }
+
+    pub fn is_expired_at(&self, time: DateTime<Utc>) -> bool {
+        self.expiration_time().is_some_and(|e| e <= time)
+    }
+
     pub fn map(&self) -> &MapSchema {
         self.0.map.deref()
     }
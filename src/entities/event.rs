@@ -1,5 +1,5 @@
 use artifactsmmo_openapi::models::{ActiveEventSchema, EventContentSchema, EventSchema};
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, TimeDelta, Utc};
 use serde::{Deserialize, Serialize};
 use std::{ops::Deref, sync::Arc};
 
@@ -11,14 +11,31 @@ impl Event {
         Self(Arc::new(schema))
     }
 
+    pub fn code(&self) -> &str {
+        &self.0.code
+    }
+
     pub fn content(&self) -> &EventContentSchema {
         self.0.content.deref()
     }
+
+    /// Returns the chance of the event spawning each minute, as defined by the game.
+    pub fn spawn_rate(&self) -> f32 {
+        if self.0.rate <= 0 {
+            return 0.0;
+        }
+        1.0 / self.0.rate as f32
+    }
+
+    /// Returns how long the event stays active once spawned.
+    pub fn duration(&self) -> TimeDelta {
+        TimeDelta::minutes(self.0.duration as i64)
+    }
 }
 
 pub trait EventSchemaExt {
     fn content_code(&self) -> &str;
-    fn to_string(&self) -> String;
+    fn describe(&self) -> String;
 }
 
 impl EventSchemaExt for Event {
@@ -26,7 +43,7 @@ impl EventSchemaExt for Event {
         &self.0.content.code
     }
 
-    fn to_string(&self) -> String {
+    fn describe(&self) -> String {
         format!("{}: '{}'", self.0.name, self.content_code())
     }
 }
@@ -41,12 +58,10 @@ impl EventSchemaExt for ActiveEventSchema {
             .expect("event to have content")
     }
 
-    fn to_string(&self) -> String {
-        let remaining = if let Ok(expiration) = DateTime::parse_from_rfc3339(&self.expiration) {
-            (expiration.to_utc() - Utc::now()).num_seconds().to_string()
-        } else {
-            "?".to_string()
-        };
+    fn describe(&self) -> String {
+        let remaining = self
+            .remaining_at(Utc::now())
+            .map_or_else(|| "?".to_string(), |r| r.num_seconds().to_string());
         format!(
             "{} ({},{}): '{}', duration: {}, created at {}, expires at {}, remaining: {}s",
             self.name,
@@ -60,3 +75,38 @@ impl EventSchemaExt for ActiveEventSchema {
         )
     }
 }
+
+pub trait ActiveEventSchemaExt {
+    /// Returns the time left before the event expires at `now`, negative once
+    /// expired, or `None` if the expiration cannot be parsed.
+    fn remaining_at(&self, now: DateTime<Utc>) -> Option<TimeDelta>;
+}
+
+impl ActiveEventSchemaExt for ActiveEventSchema {
+    fn remaining_at(&self, now: DateTime<Utc>) -> Option<TimeDelta> {
+        DateTime::parse_from_rfc3339(&self.expiration)
+            .ok()
+            .map(|expiration| expiration.to_utc() - now)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn remaining_is_measured_at_the_given_time() {
+        let now = DateTime::from_timestamp(0, 0).unwrap();
+        let event = ActiveEventSchema {
+            expiration: (now + TimeDelta::seconds(90)).to_rfc3339(),
+            ..Default::default()
+        };
+
+        assert_eq!(event.remaining_at(now), Some(TimeDelta::seconds(90)));
+        assert_eq!(
+            event.remaining_at(now + TimeDelta::seconds(100)),
+            Some(TimeDelta::seconds(-10))
+        );
+        assert_eq!(ActiveEventSchema::default().remaining_at(now), None);
+    }
+}
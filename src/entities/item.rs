@@ -44,6 +44,13 @@ impl Item {
             .collect_vec()
     }
 
+    /// Returns the quantity of this item obtained by a single craft.
+    pub fn craft_yield(&self) -> u32 {
+        self.craft_schema()
+            .and_then(|s| s.quantity)
+            .map_or(1, |q| q.max(1) as u32)
+    }
+
     pub fn recycled_quantity(&self) -> u32 {
         let q = self.mats_quantity();
         q / 5 + if q.is_multiple_of(5) { 0 } else { 1 }
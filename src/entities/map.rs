@@ -1,7 +1,7 @@
 use crate::MapsClient;
 use artifactsmmo_openapi::models::{
-    AccessSchema, InteractionSchema, MapAccessType, MapContentSchema, MapContentType, MapSchema,
-    TaskType,
+    AccessSchema, InteractionSchema, MapAccessType, MapContentSchema, MapContentType, MapLayer,
+    MapSchema, TaskType, TransitionSchema,
 };
 use core::fmt;
 use serde::{Deserialize, Serialize};
@@ -23,6 +23,10 @@ impl Map {
         self.0.y
     }
 
+    pub fn layer(&self) -> MapLayer {
+        self.0.layer
+    }
+
     pub fn content(&self) -> Option<&MapContentSchema> {
         self.0.interactions.content.as_ref().map(|c| c.as_ref())
     }
@@ -47,6 +51,10 @@ impl Map {
         self.0.interactions.deref()
     }
 
+    pub fn transition(&self) -> Option<&TransitionSchema> {
+        self.0.interactions.transition.as_deref()
+    }
+
     pub fn is_blocked(&self) -> bool {
         self.0.access.r#type == MapAccessType::Blocked
     }
@@ -62,7 +70,7 @@ impl Map {
     }
 
     pub fn closest_among(&self, others: &[Map]) -> Option<Map> {
-        MapsClient::closest_from_amoung(self.0.x, self.0.y, others)
+        MapsClient::closest_from_amoung(self.0.layer, self.0.x, self.0.y, others)
     }
 
     pub fn is_tasksmaster(&self, task_type: Option<TaskType>) -> bool {
@@ -24,6 +24,14 @@ impl Monster {
     pub fn is_boss(&self) -> bool {
         self.0.r#type == MonsterType::Boss
     }
+
+    pub fn min_gold(&self) -> i32 {
+        self.0.min_gold
+    }
+
+    pub fn max_gold(&self) -> i32 {
+        self.0.max_gold
+    }
 }
 
 impl DropsItems for Monster {
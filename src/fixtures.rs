@@ -0,0 +1,75 @@
+//! Clients loaded from an offline in-memory cache, the way they load the data
+//! persisted by a previous run.
+
+use crate::{
+    Cache, Code, Persist,
+    client::{
+        events::EventsClient, items::ItemsClient, maps::MapsClient, monsters::MonstersClient,
+        server::ServerClient,
+    },
+    entities::{Event, Item, Map, Monster},
+    storage::MemoryStorage,
+};
+use artifactsmmo_openapi::models::MapSchema;
+use serde::{Deserialize, Serialize};
+use std::{collections::HashMap, sync::Arc};
+
+/// Returns an offline cache holding `data` under `key`.
+fn cached<D: Serialize>(key: &str, data: &D) -> Arc<Cache> {
+    let cache = Cache::with_storage(MemoryStorage::new(), None);
+    cache.write(key, data).unwrap();
+    cache.set_offline(true);
+    Arc::new(cache)
+}
+
+fn by_code<E: Code>(entities: Vec<E>) -> HashMap<String, E> {
+    entities
+        .into_iter()
+        .map(|e| (e.code().to_owned(), e))
+        .collect()
+}
+
+fn key<C: Persist<D>, D: for<'a> Deserialize<'a> + Serialize>() -> &'static str {
+    C::KEY
+}
+
+/// Events client without any event, which never refreshes the active events.
+fn events() -> Arc<EventsClient> {
+    events_on(Arc::default())
+}
+
+/// Same as `events`, measuring the time with the clock of `server`.
+pub(crate) fn events_on(server: Arc<ServerClient>) -> Arc<EventsClient> {
+    let cache = cached(
+        key::<EventsClient, HashMap<String, Event>>(),
+        &HashMap::<String, Event>::new(),
+    );
+    Arc::new(EventsClient::new(Arc::default(), cache, server).unwrap())
+}
+
+pub(crate) fn items(items: Vec<Item>) -> ItemsClient {
+    let cache = cached(key::<ItemsClient, HashMap<String, Item>>(), &by_code(items));
+    ItemsClient::new(
+        Arc::default(),
+        cache,
+        Arc::default(),
+        Arc::default(),
+        Arc::default(),
+        Arc::default(),
+    )
+    .unwrap()
+}
+
+pub(crate) fn monsters(monsters: Vec<Monster>) -> MonstersClient {
+    let cache = cached(
+        key::<MonstersClient, HashMap<String, Monster>>(),
+        &by_code(monsters),
+    );
+    MonstersClient::new(Arc::default(), cache, events()).unwrap()
+}
+
+pub(crate) fn maps(maps: Vec<MapSchema>) -> MapsClient {
+    let maps = maps.into_iter().map(Map::new).collect::<Vec<_>>();
+    let cache = cached(key::<MapsClient, Vec<Map>>(), &maps);
+    MapsClient::new(Arc::default(), cache, events()).unwrap()
+}
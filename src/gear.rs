@@ -46,28 +46,33 @@ impl Gear {
         rune: Option<Item>,
         bag: Option<Item>,
     ) -> Option<Gear> {
-        (!(utility1.is_some() && utility1 == utility2
-            || artifact1.is_some() && artifact1 == artifact2
-            || artifact2.is_some() && artifact2 == artifact3
-            || artifact1.is_some() && artifact1 == artifact3))
-            .then_some(Self {
-                weapon,
-                helmet,
-                shield,
-                body_armor,
-                leg_armor,
-                boots,
-                amulet,
-                ring1,
-                ring2,
-                utility1,
-                utility2,
-                artifact1,
-                artifact2,
-                artifact3,
-                rune,
-                bag,
-            })
+        let gear = Self {
+            weapon,
+            helmet,
+            shield,
+            body_armor,
+            leg_armor,
+            boots,
+            amulet,
+            ring1,
+            ring2,
+            utility1,
+            utility2,
+            artifact1,
+            artifact2,
+            artifact3,
+            rune,
+            bag,
+        };
+        gear.is_valid().then_some(gear)
+    }
+
+    /// Returns `false` if the same utility or artifact is equipped in more than one slot.
+    pub fn is_valid(&self) -> bool {
+        !(self.utility1.is_some() && self.utility1 == self.utility2
+            || self.artifact1.is_some() && self.artifact1 == self.artifact2
+            || self.artifact2.is_some() && self.artifact2 == self.artifact3
+            || self.artifact1.is_some() && self.artifact1 == self.artifact3)
     }
 
     pub fn item_in(&self, slot: Slot) -> Option<Item> {
@@ -91,6 +96,27 @@ impl Gear {
         }
     }
 
+    pub fn set(&mut self, slot: Slot, item: Option<Item>) {
+        match slot {
+            Slot::Weapon => self.weapon = item,
+            Slot::Shield => self.shield = item,
+            Slot::Helmet => self.helmet = item,
+            Slot::BodyArmor => self.body_armor = item,
+            Slot::LegArmor => self.leg_armor = item,
+            Slot::Boots => self.boots = item,
+            Slot::Ring1 => self.ring1 = item,
+            Slot::Ring2 => self.ring2 = item,
+            Slot::Amulet => self.amulet = item,
+            Slot::Artifact1 => self.artifact1 = item,
+            Slot::Artifact2 => self.artifact2 = item,
+            Slot::Artifact3 => self.artifact3 = item,
+            Slot::Utility1 => self.utility1 = item,
+            Slot::Utility2 => self.utility2 = item,
+            Slot::Rune => self.rune = item,
+            Slot::Bag => self.bag = item,
+        }
+    }
+
     pub fn align_to(&mut self, other: &Gear) {
         if self.ring1 == other.ring2 || self.ring2 == other.ring1 {
             swap(&mut self.ring1, &mut self.ring2);
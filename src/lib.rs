@@ -2,60 +2,75 @@ use artifactsmmo_openapi::models::{
     AccessSchema, CharacterFightSchema, ConditionSchema, DropRateSchema, DropSchema, InventorySlot,
     RewardsSchema, SimpleItemSchema, SkillDataSchema, SkillInfoSchema, TransitionSchema,
 };
-use fs_extra::file::{read_to_string, write_all};
 use itertools::Itertools;
-use log::error;
+use log::{debug, error};
 use serde::{Deserialize, Serialize};
-use std::{collections::HashMap, path::Path, sync::RwLockReadGuard};
+use std::{cmp::max, collections::HashMap, sync::RwLockReadGuard};
 
 pub use artifactsmmo_openapi::models;
 pub use sdk_derive::CollectionClient;
 
+pub use cache::{Cache, CacheError};
 pub use client::*;
+pub use clock::{Clock, RealClock, VirtualClock};
+pub use conditions::{CharacterState, UnmetCondition};
 pub use consts::*;
 pub use container::*;
 pub use gear::*;
 pub use skill::*;
 
+pub mod cache;
 pub mod client;
+pub mod clock;
+pub mod conditions;
 pub mod consts;
 pub mod container;
+pub mod emulator;
 pub mod entities;
+#[cfg(test)]
+mod fixtures;
 pub mod gear;
+pub mod optimizer;
 pub mod simulator;
 pub mod skill;
+pub mod storage;
 
 pub(crate) trait Persist<D: for<'a> Deserialize<'a> + Serialize> {
-    const PATH: &'static str;
-
-    fn load(&self) -> D {
-        if let Ok(data) = self.load_from_file::<D>() {
-            data
-        } else {
-            let data = self.load_from_api();
-            if let Err(e) = Self::persist(&data) {
-                error!("failed to persist data: {}", e);
+    const KEY: &'static str;
+
+    fn cache(&self) -> &Cache;
+
+    /// Loads the data from the cache, falling back to the API when the cache
+    /// is missing, invalid or stale and the cache is not offline.
+    fn load(&self) -> Result<D, ClientError> {
+        match self.cache().read(Self::KEY) {
+            Ok(data) => Ok(data),
+            Err(e) if self.cache().is_offline() => Err(ClientError::NotCached {
+                key: Self::KEY,
+                source: e,
+            }),
+            Err(e) => {
+                debug!("loading `{}` from API: {}", Self::KEY, e);
+                self.fetch()
             }
-            data
         }
     }
 
-    fn load_from_api(&self) -> D;
-
-    fn load_from_file<T: for<'a> Deserialize<'a>>(&self) -> Result<T, Box<dyn std::error::Error>> {
-        Ok(serde_json::from_str(&read_to_string(Path::new(
-            Self::PATH,
-        ))?)?)
+    /// Loads the data from the API and persists it.
+    fn fetch(&self) -> Result<D, ClientError> {
+        if self.cache().is_offline() {
+            return Err(ClientError::Offline);
+        }
+        let data = self.load_from_api()?;
+        if let Err(e) = self.cache().write(Self::KEY, &data) {
+            error!("failed to persist data: {}", e);
+        }
+        Ok(data)
     }
 
-    fn persist<T: Serialize>(data: T) -> Result<(), Box<dyn std::error::Error>> {
-        Ok(write_all(
-            Path::new(Self::PATH),
-            &serde_json::to_string_pretty(&data)?,
-        )?)
-    }
+    fn load_from_api(&self) -> Result<D, ClientError>;
 
-    fn refresh(&self);
+    fn refresh(&self) -> Result<(), ClientError>;
 }
 
 #[allow(private_bounds)]
@@ -259,8 +274,9 @@ impl DropRateSchemaExt for DropRateSchema {
         (self.min_quantity + self.max_quantity) as f32 / 2.0
     }
 
+    /// Returns the chance of the drop, the game defining `rate` as `1/rate`.
     fn rate(&self) -> f32 {
-        self.rate as f32 / 100.0
+        1.0 / max(self.rate, 1) as f32
     }
 
     fn effective_rate(&self) -> f32 {
@@ -0,0 +1,155 @@
+use crate::{
+    CharacterClient, CharacterState, Gear, Slot,
+    character::HasCharacterData,
+    entities::{Item, Monster},
+    optimizer::{descend, equipable_pool},
+    simulator::{FightParams, FightStats, Participant, Simulator},
+};
+use itertools::Itertools;
+use strum::IntoEnumIterator;
+
+const DEFAULT_FIGHTS: u32 = 100;
+
+/// Searches the `Gear` giving the best simulated outcome against a monster.
+///
+/// Loadouts are ranked by win rate, then by mean HP lost, then by mean fight
+/// cooldown. Every loadout is evaluated with the same seed so that
+/// comparisons are not skewed by sampling noise.
+#[derive(Debug, Clone)]
+pub struct GearOptimizer {
+    level: u32,
+    pool: Vec<Item>,
+    fights: u32,
+    seed: u64,
+    utility_quantity: u32,
+}
+
+impl GearOptimizer {
+    /// Creates an optimizer for a character of the given `level`, choosing
+    /// among the items of `pool` that can be equipped at that level.
+    pub fn new(level: u32, pool: impl IntoIterator<Item = Item>) -> Self {
+        Self {
+            level,
+            pool: equipable_pool(level, pool),
+            fights: DEFAULT_FIGHTS,
+            seed: 0,
+            utility_quantity: Slot::Utility1.max_quantity(),
+        }
+    }
+
+    /// Creates an optimizer for `character`, keeping only the items of
+    /// `pool` whose conditions it meets.
+    pub fn for_character(
+        character: &CharacterClient,
+        pool: impl IntoIterator<Item = Item>,
+    ) -> Self {
+        let state = CharacterState::from(character);
+        Self::new(
+            character.level(),
+            pool.into_iter().filter(|i| state.meets(i)).collect_vec(),
+        )
+    }
+
+    /// Sets the number of fights simulated for each evaluated loadout.
+    pub fn fights(mut self, fights: u32) -> Self {
+        self.fights = fights.max(1);
+        self
+    }
+
+    pub fn seed(mut self, seed: u64) -> Self {
+        self.seed = seed;
+        self
+    }
+
+    /// Sets the quantity of each utility assumed to be equipped.
+    pub fn utility_quantity(mut self, quantity: u32) -> Self {
+        self.utility_quantity = quantity;
+        self
+    }
+
+    pub fn pool(&self) -> &[Item] {
+        &self.pool
+    }
+
+    /// Returns the best loadout against `monster` along with its statistics.
+    pub fn optimize(&self, monster: &Monster) -> (Gear, FightStats) {
+        let slots = Slot::iter().filter(|s| !s.is_bag()).collect_vec();
+        descend(
+            Gear::default(),
+            &slots,
+            &self.pool,
+            |gear| self.evaluate(gear, monster),
+            is_better,
+        )
+    }
+
+    pub fn evaluate(&self, gear: &Gear, monster: &Monster) -> FightStats {
+        let utility1_quantity = gear.utility1.as_ref().map_or(0, |_| self.utility_quantity);
+        let utility2_quantity = gear.utility2.as_ref().map_or(0, |_| self.utility_quantity);
+        Simulator::fight_many(
+            self.fights,
+            Participant::new(
+                "optimizer".to_string(),
+                self.level,
+                gear.clone(),
+                utility1_quantity,
+                utility2_quantity,
+                0,
+            ),
+            None,
+            monster.clone(),
+            FightParams::default().seed(self.seed),
+        )
+    }
+}
+
+fn is_better(stats: &FightStats, other: &FightStats) -> bool {
+    stats
+        .win_rate()
+        .total_cmp(&other.win_rate())
+        .then_with(|| other.hp_lost.mean().total_cmp(&stats.hp_lost.mean()))
+        .then_with(|| other.cd.mean().total_cmp(&stats.cd.mean()))
+        .is_gt()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Code;
+    use artifactsmmo_openapi::models::{ItemSchema, MonsterSchema, SimpleEffectSchema};
+
+    fn weapon(code: &str, level: u32, attack: i32) -> Item {
+        Item::new(ItemSchema {
+            code: code.to_string(),
+            level: level as _,
+            r#type: "weapon".to_string(),
+            effects: Some(vec![SimpleEffectSchema {
+                code: "attack_fire".to_string(),
+                value: attack,
+                ..Default::default()
+            }]),
+            ..Default::default()
+        })
+    }
+
+    #[test]
+    fn picks_strongest_usable_weapon() {
+        let monster = Monster::new(MonsterSchema {
+            hp: 200,
+            attack_earth: 10,
+            ..Default::default()
+        });
+        let optimizer = GearOptimizer::new(
+            10,
+            [
+                weapon("stick", 1, 5),
+                weapon("sword", 10, 40),
+                weapon("axe", 30, 200),
+            ],
+        )
+        .fights(10);
+        let (gear, stats) = optimizer.optimize(&monster);
+        assert_eq!(gear.weapon.as_ref().map(|w| w.code()), Some("sword"));
+        assert!(stats.is_winning_at(0.95));
+    }
+}
@@ -0,0 +1,177 @@
+use crate::{
+    CharacterClient, CharacterState, DropsItems, Gear, Level, Skill, Slot,
+    character::HasCharacterData,
+    entities::{Item, Resource},
+    optimizer::{descend, equipable_pool},
+    simulator::{HasEffects, gather_cd},
+};
+use itertools::Itertools;
+use strum::IntoEnumIterator;
+
+/// Drop rate increase assumed for each point of prospecting, 10 points adding
+/// 1%. The API does not expose the formula, see
+/// `GatheringOptimizer::prospecting_multiplier` to override it.
+pub const DEFAULT_PROSPECTING_MULTIPLIER: f32 = 0.001;
+const SECONDS_PER_HOUR: f32 = 3600.0;
+
+/// Searches the `Gear` maximizing the expected drops per hour on a resource.
+///
+/// Loadouts are ranked by drops per hour, then by gathering cooldown.
+#[derive(Debug, Clone)]
+pub struct GatheringOptimizer {
+    skill_level: u32,
+    pool: Vec<Item>,
+    prospecting_multiplier: f32,
+}
+
+/// Expected outcome of gathering a resource with a given `Gear`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct GatheringStats {
+    pub cooldown: u32,
+    pub drops_per_hour: f32,
+}
+
+impl GatheringOptimizer {
+    /// Creates an optimizer for a character of the given `level` and
+    /// `skill_level`, choosing among the items of `pool` that can be equipped
+    /// at that level.
+    pub fn new(level: u32, skill_level: u32, pool: impl IntoIterator<Item = Item>) -> Self {
+        Self {
+            skill_level,
+            pool: equipable_pool(level, pool),
+            prospecting_multiplier: DEFAULT_PROSPECTING_MULTIPLIER,
+        }
+    }
+
+    /// Sets the drop rate increase granted by each point of prospecting.
+    pub fn prospecting_multiplier(mut self, multiplier: f32) -> Self {
+        self.prospecting_multiplier = multiplier;
+        self
+    }
+
+    /// Creates an optimizer for `character` gathering with `skill`, keeping
+    /// only the items of `pool` whose conditions it meets.
+    pub fn for_character(
+        character: &CharacterClient,
+        skill: Skill,
+        pool: impl IntoIterator<Item = Item>,
+    ) -> Self {
+        let state = CharacterState::from(character);
+        Self::new(
+            character.level(),
+            character.skill_level(skill),
+            pool.into_iter().filter(|i| state.meets(i)).collect_vec(),
+        )
+    }
+
+    pub fn pool(&self) -> &[Item] {
+        &self.pool
+    }
+
+    /// Returns the best loadout for gathering `resource` along with its
+    /// statistics, or `None` if the skill level is too low to gather it.
+    pub fn optimize(&self, resource: &Resource) -> Option<(Gear, GatheringStats)> {
+        if resource.level() > self.skill_level {
+            return None;
+        }
+        let slots = Slot::iter().filter(|s| !s.is_utility()).collect_vec();
+        Some(descend(
+            Gear::default(),
+            &slots,
+            &self.pool,
+            |gear| self.evaluate(gear, resource),
+            is_better,
+        ))
+    }
+
+    pub fn evaluate(&self, gear: &Gear, resource: &Resource) -> GatheringStats {
+        let cooldown = gather_cd(
+            resource.level(),
+            gear.skill_cooldown_reduction(resource.skill()),
+        );
+        let multiplier = 1.0 + gear.prospecting() as f32 * self.prospecting_multiplier;
+        let drops = resource
+            .drops()
+            .iter()
+            .map(|d| resource.effective_drop_rate_of(&d.code))
+            .sum::<f32>()
+            * multiplier;
+        GatheringStats {
+            cooldown,
+            drops_per_hour: drops * SECONDS_PER_HOUR / cooldown.max(1) as f32,
+        }
+    }
+}
+
+fn is_better(stats: &GatheringStats, other: &GatheringStats) -> bool {
+    stats
+        .drops_per_hour
+        .total_cmp(&other.drops_per_hour)
+        .then_with(|| other.cooldown.cmp(&stats.cooldown))
+        .is_gt()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Code;
+    use artifactsmmo_openapi::models::{
+        DropRateSchema, GatheringSkill, ItemSchema, ResourceSchema, SimpleEffectSchema,
+    };
+
+    fn item(code: &str, r#type: &str, effect: &str, value: i32) -> Item {
+        Item::new(ItemSchema {
+            code: code.to_string(),
+            level: 1,
+            r#type: r#type.to_string(),
+            effects: Some(vec![SimpleEffectSchema {
+                code: effect.to_string(),
+                value,
+                ..Default::default()
+            }]),
+            ..Default::default()
+        })
+    }
+
+    #[test]
+    fn picks_fastest_tool_and_prospecting() {
+        let resource = Resource::new(ResourceSchema {
+            code: "copper_rocks".to_string(),
+            level: 1,
+            skill: GatheringSkill::Mining,
+            drops: vec![DropRateSchema {
+                code: "copper_ore".to_string(),
+                rate: 1,
+                min_quantity: 1,
+                max_quantity: 1,
+                ..Default::default()
+            }],
+            ..Default::default()
+        });
+        let optimizer = GatheringOptimizer::new(
+            1,
+            1,
+            [
+                item("copper_pickaxe", "weapon", "mining", -10),
+                item("iron_pickaxe", "weapon", "mining", -30),
+                item("fishing_net", "weapon", "fishing", -30),
+                item("lucky_ring", "ring", "prospecting", 50),
+            ],
+        );
+        let (gear, stats) = optimizer.optimize(&resource).unwrap();
+        assert_eq!(gear.weapon.as_ref().map(|w| w.code()), Some("iron_pickaxe"));
+        assert_eq!(gear.ring1.as_ref().map(|r| r.code()), Some("lucky_ring"));
+        assert_eq!(stats.cooldown, gather_cd(1, -30));
+
+        let gear = Gear {
+            ring1: Some(item("lucky_ring", "ring", "prospecting", 50)),
+            ..Default::default()
+        };
+        let base = optimizer.evaluate(&Gear::default(), &resource);
+        let boosted = optimizer
+            .clone()
+            .prospecting_multiplier(0.01)
+            .evaluate(&gear, &resource);
+        assert!((boosted.drops_per_hour - base.drops_per_hour * 1.5).abs() < 1e-3);
+    }
+}
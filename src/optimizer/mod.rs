@@ -0,0 +1,69 @@
+use crate::{Code, Gear, Level, Slot, entities::Item, items::Type};
+use itertools::Itertools;
+
+pub use fight::GearOptimizer;
+pub use gathering::{DEFAULT_PROSPECTING_MULTIPLIER, GatheringOptimizer, GatheringStats};
+
+pub mod fight;
+pub mod gathering;
+
+/// Number of full passes over the slots before giving up on convergence.
+const MAX_PASSES: usize = 3;
+
+/// Searches for the best `Gear` by coordinate descent: each slot in `slots` is
+/// in turn set to every matching item of `pool` (or left empty) while the other
+/// slots stay fixed, keeping the change when `is_better` prefers its score.
+/// Stops when a full pass brings no improvement.
+pub(crate) fn descend<S>(
+    start: Gear,
+    slots: &[Slot],
+    pool: &[Item],
+    score: impl Fn(&Gear) -> S,
+    is_better: impl Fn(&S, &S) -> bool,
+) -> (Gear, S) {
+    let mut best = start;
+    let mut best_score = score(&best);
+    for _ in 0..MAX_PASSES {
+        let mut improved = false;
+        for &slot in slots {
+            for candidate in candidates_for(slot, pool) {
+                if best.item_in(slot) == candidate {
+                    continue;
+                }
+                let mut gear = best.clone();
+                gear.set(slot, candidate);
+                if !gear.is_valid() {
+                    continue;
+                }
+                let gear_score = score(&gear);
+                if is_better(&gear_score, &best_score) {
+                    best = gear;
+                    best_score = gear_score;
+                    improved = true;
+                }
+            }
+        }
+        if !improved {
+            break;
+        }
+    }
+    (best, best_score)
+}
+
+fn candidates_for(slot: Slot, pool: &[Item]) -> Vec<Option<Item>> {
+    let r#type = Type::from(slot);
+    pool.iter()
+        .filter(|i| i.type_is(r#type))
+        .cloned()
+        .map(Some)
+        .chain([None])
+        .collect_vec()
+}
+
+/// Keeps the equipable items of `pool` usable at `level`, without duplicates.
+pub(crate) fn equipable_pool(level: u32, pool: impl IntoIterator<Item = Item>) -> Vec<Item> {
+    pool.into_iter()
+        .filter(|i| i.is_equipable() && i.level() <= level)
+        .unique_by(|i| i.code().to_owned())
+        .collect_vec()
+}
@@ -8,10 +8,16 @@ use crate::{
 };
 use artifactsmmo_openapi::models::SimpleEffectSchema;
 use dyn_clone::DynClone;
+use rand::RngCore;
 use std::{cell::RefCell, rc::Rc};
 
 pub(super) trait SimulationEntity: HasEffects + DynClone {
-    fn turn_against(&mut self, target: &mut dyn SimulationEntity, turn: u32) {
+    fn turn_against(
+        &mut self,
+        target: &mut dyn SimulationEntity,
+        turn: u32,
+        rng: &mut dyn RngCore,
+    ) {
         if turn == self.reconstitution() as u32 {
             self.set_health(self.max_hp());
         }
@@ -37,7 +43,7 @@ pub(super) trait SimulationEntity: HasEffects + DynClone {
             self.apply_burn(target);
             self.apply_poison(target);
         }
-        for hit in self.hits_against(target, self.averaged()).iter() {
+        for hit in self.hits_against(target, self.averaged(), rng).iter() {
             target.dec_health(hit.dmg);
             if hit.is_crit {
                 self.inc_health(hit.dmg * self.lifesteal() / 100);
@@ -4,6 +4,7 @@ use crate::{
 };
 use artifactsmmo_openapi::models::SimpleEffectSchema;
 use itertools::Itertools;
+use rand::{Rng, RngCore};
 use strum::IntoEnumIterator;
 
 const HP: &str = "hp";
@@ -115,11 +116,16 @@ pub trait HasEffects {
             .unwrap_or(0)
     }
 
-    fn hits_against(&self, target: &dyn HasEffects, averaged: bool) -> Vec<Hit> {
+    fn hits_against(
+        &self,
+        target: &dyn HasEffects,
+        averaged: bool,
+        rng: &mut dyn RngCore,
+    ) -> Vec<Hit> {
         let is_crit = if averaged {
             false
         } else {
-            rand::random_range(1..=100) <= self.critical_strike()
+            rng.random_range(1..=100) <= self.critical_strike()
         };
         DamageType::iter()
             .filter_map(|t| {
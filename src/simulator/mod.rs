@@ -6,7 +6,7 @@ use crate::{
 };
 use artifactsmmo_openapi::models::FightResult;
 use itertools::Itertools;
-use rand::seq::IndexedRandom;
+use rand::{Rng, RngCore, SeedableRng, rngs::StdRng, seq::IndexedRandom};
 use std::cmp::max;
 
 pub use damage_type::DamageType;
@@ -37,12 +37,28 @@ const BURN_MULTIPLIER: f32 = 0.90;
 pub struct Simulator {}
 
 impl Simulator {
+    /// Runs one fight. The randomness is seeded from `params` when a seed was
+    /// given, making the result reproducible.
     pub fn fight(
         initiator: Participant,
         participants: Option<Vec<Participant>>,
         monster: Monster,
         params: FightParams,
     ) -> Fight {
+        Self::fight_with_rng(initiator, participants, monster, params, &mut params.rng())
+    }
+
+    /// Runs one fight drawing every random outcome (critical strikes, turn
+    /// order ties, monster targeting) from `rng`.
+    pub fn fight_with_rng(
+        initiator: Participant,
+        participants: Option<Vec<Participant>>,
+        monster: Monster,
+        params: FightParams,
+        rng: &mut dyn RngCore,
+    ) -> Fight {
+        let utility1_quantity = initiator.utility1_quantity;
+        let utility2_quantity = initiator.utility2_quantity;
         let char = SimulationCharacter::new(
             initiator.name.clone(),
             initiator.level,
@@ -84,17 +100,17 @@ impl Simulator {
             if remaining_fighters.is_empty() {
                 remaining_fighters = fighters.clone();
             }
-            let Some(mut fighter) = get_next_fighter(&mut remaining_fighters) else {
+            let Some(mut fighter) = get_next_fighter(&mut remaining_fighters, rng) else {
                 break;
             };
             remaining_fighters.retain(|f| f.name() != fighter.name());
             if fighter.is_monster() {
-                let Some(mut target) = pick_monster_target(&chars) else {
+                let Some(mut target) = pick_monster_target(&chars, rng) else {
                     break;
                 };
-                monster.turn_against(&mut target, turn);
+                monster.turn_against(&mut target, turn, rng);
             } else {
-                fighter.turn_against(&mut monster, turn);
+                fighter.turn_against(&mut monster, turn, rng);
             }
             turn += 1;
         }
@@ -111,12 +127,39 @@ impl Simulator {
                 FightResult::Win
             },
             cd: fight_cd(char.haste(), turn),
+            utility1_consumed: utility1_quantity.saturating_sub(char.utility1_quantity()),
+            utility2_consumed: utility2_quantity.saturating_sub(char.utility2_quantity()),
         }
     }
+
+    /// Runs `n` independent fights and aggregates their outcomes, giving a
+    /// view of how likely the initiator is to win instead of a single sample.
+    pub fn fight_many(
+        n: u32,
+        initiator: Participant,
+        participants: Option<Vec<Participant>>,
+        monster: Monster,
+        params: FightParams,
+    ) -> FightStats {
+        let mut rng = params.rng();
+        let fights = (0..n)
+            .map(|_| {
+                Self::fight_with_rng(
+                    initiator.clone(),
+                    participants.clone(),
+                    monster.clone(),
+                    params,
+                    &mut rng,
+                )
+            })
+            .collect_vec();
+        FightStats::new(&fights)
+    }
 }
 
 fn get_next_fighter(
     fighters: &mut Vec<Box<dyn SimulationEntity>>,
+    rng: &mut dyn RngCore,
 ) -> Option<Box<dyn SimulationEntity>> {
     fighters
         .iter()
@@ -124,13 +167,16 @@ fn get_next_fighter(
         .max_set_by_key(|f| f.initiative())
         .into_iter()
         .max_set_by_key(|f| f.current_health())
-        .choose(&mut rand::rng())
+        .choose(rng)
         .map(|&c| c.clone())
 }
 
-fn pick_monster_target(chars: &[SimulationCharacter]) -> Option<SimulationCharacter> {
+fn pick_monster_target(
+    chars: &[SimulationCharacter],
+    rng: &mut dyn RngCore,
+) -> Option<SimulationCharacter> {
     let chars_alive = chars.iter().filter(|c| c.current_health() > 0);
-    let targets = if rand::random_range(1..=100) <= 90 {
+    let targets = if rng.random_range(1..=100) <= 90 {
         chars_alive.max_set_by_key(|c| c.threat())
     } else {
         chars_alive.collect_vec()
@@ -138,10 +184,11 @@ fn pick_monster_target(chars: &[SimulationCharacter]) -> Option<SimulationCharac
     targets
         .iter()
         .min_set_by_key(|c| c.current_health())
-        .choose(&mut rand::rng())
+        .choose(rng)
         .map(|&&c| c.clone())
 }
 
+#[derive(Clone)]
 pub struct Participant {
     name: String,
     level: u32,
@@ -184,10 +231,11 @@ impl From<&CharacterClient> for Participant {
     }
 }
 
-#[derive(Default)]
+#[derive(Default, Clone, Copy)]
 pub struct FightParams {
     averaged: bool,
     ignore_death: bool,
+    seed: Option<u64>,
 }
 
 impl FightParams {
@@ -200,9 +248,22 @@ impl FightParams {
         self.ignore_death = true;
         self
     }
+
+    /// Seeds the fight randomness so that identical seeds give identical fights.
+    pub fn seed(mut self, seed: u64) -> Self {
+        self.seed = Some(seed);
+        self
+    }
+
+    fn rng(&self) -> StdRng {
+        match self.seed {
+            Some(seed) => StdRng::seed_from_u64(seed),
+            None => StdRng::from_rng(&mut rand::rng()),
+        }
+    }
 }
 
-#[derive(Debug)]
+#[derive(Debug, PartialEq)]
 pub struct Fight {
     pub turns: u32,
     pub hp: i32,
@@ -210,6 +271,8 @@ pub struct Fight {
     pub hp_lost: i32,
     pub result: FightResult,
     pub cd: u32,
+    pub utility1_consumed: u32,
+    pub utility2_consumed: u32,
 }
 
 impl Fight {
@@ -222,6 +285,88 @@ impl Fight {
     }
 }
 
+/// Aggregated outcome of several simulated fights against the same monster.
+#[derive(Debug, Clone)]
+pub struct FightStats {
+    pub fights: u32,
+    pub wins: u32,
+    pub hp_lost: Distribution,
+    pub turns: Distribution,
+    pub cd: Distribution,
+    pub utility1_consumed: Distribution,
+    pub utility2_consumed: Distribution,
+}
+
+impl FightStats {
+    pub fn new(fights: &[Fight]) -> Self {
+        Self {
+            fights: fights.len() as u32,
+            wins: fights.iter().filter(|f| f.is_winning()).count() as u32,
+            hp_lost: Distribution::new(fights.iter().map(|f| f.hp_lost as f32)),
+            turns: Distribution::new(fights.iter().map(|f| f.turns as f32)),
+            cd: Distribution::new(fights.iter().map(|f| f.cd as f32)),
+            utility1_consumed: Distribution::new(fights.iter().map(|f| f.utility1_consumed as f32)),
+            utility2_consumed: Distribution::new(fights.iter().map(|f| f.utility2_consumed as f32)),
+        }
+    }
+
+    /// Ratio of won fights, between 0.0 and 1.0.
+    pub fn win_rate(&self) -> f32 {
+        if self.fights == 0 {
+            return 0.0;
+        }
+        self.wins as f32 / self.fights as f32
+    }
+
+    /// Returns `true` if at least `rate` of the simulated fights were won.
+    pub fn is_winning_at(&self, rate: f32) -> bool {
+        self.fights > 0 && self.win_rate() >= rate
+    }
+}
+
+/// Sorted samples of a fight metric.
+#[derive(Debug, Clone, Default)]
+pub struct Distribution {
+    samples: Vec<f32>,
+}
+
+impl Distribution {
+    pub fn new(samples: impl IntoIterator<Item = f32>) -> Self {
+        let mut samples = samples.into_iter().collect_vec();
+        samples.sort_by(f32::total_cmp);
+        Self { samples }
+    }
+
+    pub fn samples(&self) -> &[f32] {
+        &self.samples
+    }
+
+    pub fn mean(&self) -> f32 {
+        if self.samples.is_empty() {
+            return 0.0;
+        }
+        self.samples.iter().sum::<f32>() / self.samples.len() as f32
+    }
+
+    pub fn min(&self) -> f32 {
+        self.samples.first().copied().unwrap_or_default()
+    }
+
+    pub fn max(&self) -> f32 {
+        self.samples.last().copied().unwrap_or_default()
+    }
+
+    /// Returns the smallest sample greater than or equal to `p` percent of
+    /// the samples (nearest-rank method).
+    pub fn percentile(&self, p: f32) -> f32 {
+        if self.samples.is_empty() {
+            return 0.0;
+        }
+        let rank = (p.clamp(0.0, 100.0) / 100.0 * self.samples.len() as f32).ceil() as usize;
+        self.samples[rank.saturating_sub(1).min(self.samples.len() - 1)]
+    }
+}
+
 /// Compute the average damage an attack will do against the given `target_resistance`.
 pub fn average_dmg(
     attack_dmg: i32,
@@ -287,7 +432,16 @@ pub fn gather_cd(resource_level: u32, cooldown_reduction: i32) -> u32 {
 
 #[cfg(test)]
 mod tests {
-    use crate::simulator::gather_cd;
+    use crate::{
+        Gear,
+        entities::{Item, Monster},
+        simulator::{
+            Distribution, Fight, FightParams, FightStats, Participant, Simulator, gather_cd,
+        },
+    };
+    use artifactsmmo_openapi::models::{
+        FightResult, ItemSchema, MonsterSchema, SimpleEffectSchema,
+    };
 
     //TODO: rewrite tests
     // use crate::{ITEMS, MONSTERS};
@@ -354,4 +508,139 @@ mod tests {
     fn check_gather_cd() {
         assert_eq!(gather_cd(1, -10), 27)
     }
+
+    #[test]
+    fn same_seed_gives_same_fight() {
+        let monster = Monster::new(MonsterSchema {
+            hp: 300,
+            attack_fire: 12,
+            critical_strike: 50,
+            initiative: 100,
+            ..Default::default()
+        });
+        let participant = Participant::new("tester".to_string(), 1, Gear::default(), 0, 0, 0);
+        let fight = |seed| {
+            Simulator::fight(
+                participant.clone(),
+                None,
+                monster.clone(),
+                FightParams::default().seed(seed),
+            )
+        };
+        assert_eq!(fight(42), fight(42));
+        assert_eq!(
+            Simulator::fight_many(
+                10,
+                participant.clone(),
+                None,
+                monster.clone(),
+                FightParams::default().seed(7)
+            )
+            .hp_lost
+            .samples(),
+            Simulator::fight_many(
+                10,
+                participant,
+                None,
+                monster,
+                FightParams::default().seed(7)
+            )
+            .hp_lost
+            .samples(),
+        );
+    }
+
+    #[test]
+    fn distribution_percentiles() {
+        let distribution = Distribution::new((1..=20).rev().map(|v| v as f32));
+        assert_eq!(distribution.min(), 1.0);
+        assert_eq!(distribution.max(), 20.0);
+        assert_eq!(distribution.mean(), 10.5);
+        assert_eq!(distribution.percentile(50.0), 10.0);
+        assert_eq!(distribution.percentile(95.0), 19.0);
+        assert_eq!(distribution.percentile(0.0), 1.0);
+    }
+
+    #[test]
+    fn fight_many_aggregates_a_known_matchup() {
+        let stick = Item::new(ItemSchema {
+            code: "stick".to_string(),
+            r#type: "weapon".to_string(),
+            effects: Some(vec![SimpleEffectSchema {
+                code: "attack_fire".to_string(),
+                value: 10,
+                ..Default::default()
+            }]),
+            ..Default::default()
+        });
+        let participant = Participant::new(
+            "tester".to_string(),
+            1,
+            Gear {
+                weapon: Some(stick),
+                ..Default::default()
+            },
+            0,
+            0,
+            0,
+        );
+        let monster = |hp| {
+            Monster::new(MonsterSchema {
+                hp,
+                attack_fire: 5,
+                ..Default::default()
+            })
+        };
+
+        // Without critical strikes the fights are identical: the tester
+        // strikes first and lands 3 hits of 10 while taking 2 of 5.
+        let stats = Simulator::fight_many(
+            20,
+            participant.clone(),
+            None,
+            monster(30),
+            FightParams::default(),
+        );
+        assert_eq!(stats.fights, 20);
+        assert_eq!(stats.win_rate(), 1.0);
+        assert_eq!(stats.hp_lost.mean(), 10.0);
+        assert_eq!(stats.turns.mean(), 6.0);
+        assert_eq!(stats.cd.min(), 12.0);
+        assert_eq!(stats.cd.max(), 12.0);
+
+        // The tester runs out of its 120 HP long before the monster does.
+        let stats =
+            Simulator::fight_many(20, participant, None, monster(1000), FightParams::default());
+        assert_eq!(stats.win_rate(), 0.0);
+        assert!(!stats.is_winning_at(0.05));
+        assert_eq!(stats.hp_lost.mean(), 120.0);
+    }
+
+    #[test]
+    fn fight_stats_aggregate_the_fights() {
+        let fight = |result, hp_lost, cd| Fight {
+            turns: 10,
+            hp: 100 - hp_lost,
+            monster_hp: 0,
+            hp_lost,
+            result,
+            cd,
+            utility1_consumed: 0,
+            utility2_consumed: 0,
+        };
+        let stats = FightStats::new(&[
+            fight(FightResult::Win, 10, 20),
+            fight(FightResult::Win, 20, 20),
+            fight(FightResult::Win, 30, 20),
+            fight(FightResult::Loss, 100, 40),
+        ]);
+        assert_eq!(stats.wins, 3);
+        assert_eq!(stats.win_rate(), 0.75);
+        assert!(stats.is_winning_at(0.75));
+        assert!(!stats.is_winning_at(0.8));
+        assert_eq!(stats.hp_lost.mean(), 40.0);
+        assert_eq!(stats.cd.mean(), 25.0);
+        assert_eq!(stats.cd.percentile(75.0), 20.0);
+        assert_eq!(FightStats::new(&[]).win_rate(), 0.0);
+    }
 }
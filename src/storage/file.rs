@@ -0,0 +1,56 @@
+use crate::storage::{Encoding, Storage, StorageError};
+use std::{fs, io::ErrorKind, path::PathBuf};
+
+/// Stores each entry in its own file under a root directory.
+#[derive(Debug, Clone)]
+pub struct FileStorage {
+    root: PathBuf,
+    encoding: Encoding,
+}
+
+impl FileStorage {
+    /// Stores entries as pretty-printed `.json` files.
+    pub fn json(root: impl Into<PathBuf>) -> Self {
+        Self {
+            root: root.into(),
+            encoding: Encoding::Json,
+        }
+    }
+
+    /// Stores entries as MessagePack `.bin` files, faster to load than JSON.
+    #[cfg(feature = "binary")]
+    pub fn binary(root: impl Into<PathBuf>) -> Self {
+        Self {
+            root: root.into(),
+            encoding: Encoding::Binary,
+        }
+    }
+
+    pub fn path(&self, key: &str) -> PathBuf {
+        let extension = match self.encoding {
+            Encoding::Json => "json",
+            #[cfg(feature = "binary")]
+            Encoding::Binary => "bin",
+        };
+        self.root.join(key).with_extension(extension)
+    }
+}
+
+impl Storage for FileStorage {
+    fn encoding(&self) -> Encoding {
+        self.encoding
+    }
+
+    fn load(&self, key: &str) -> Result<Vec<u8>, StorageError> {
+        fs::read(self.path(key)).map_err(|e| match e.kind() {
+            ErrorKind::NotFound => StorageError::NotFound(key.to_owned()),
+            _ => StorageError::Io(e),
+        })
+    }
+
+    fn store(&self, key: &str, data: &[u8]) -> Result<(), StorageError> {
+        let path = self.path(key);
+        fs::create_dir_all(path.parent().unwrap_or(&self.root))?;
+        Ok(fs::write(path, data)?)
+    }
+}
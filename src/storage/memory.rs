@@ -0,0 +1,33 @@
+use crate::storage::{Storage, StorageError};
+use std::{collections::HashMap, sync::RwLock};
+
+/// Keeps entries in memory only, mostly useful for tests.
+#[derive(Debug, Default)]
+pub struct MemoryStorage {
+    entries: RwLock<HashMap<String, Vec<u8>>>,
+}
+
+impl MemoryStorage {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl Storage for MemoryStorage {
+    fn load(&self, key: &str) -> Result<Vec<u8>, StorageError> {
+        self.entries
+            .read()
+            .unwrap()
+            .get(key)
+            .cloned()
+            .ok_or_else(|| StorageError::NotFound(key.to_owned()))
+    }
+
+    fn store(&self, key: &str, data: &[u8]) -> Result<(), StorageError> {
+        self.entries
+            .write()
+            .unwrap()
+            .insert(key.to_owned(), data.to_vec());
+        Ok(())
+    }
+}
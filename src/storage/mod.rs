@@ -0,0 +1,78 @@
+use std::{fmt, sync::Arc};
+use thiserror::Error;
+
+pub use file::FileStorage;
+pub use memory::MemoryStorage;
+#[cfg(feature = "sqlite")]
+pub use sqlite::SqliteStorage;
+
+pub mod file;
+pub mod memory;
+#[cfg(feature = "sqlite")]
+pub mod sqlite;
+
+/// Backend storing the serialized static game data of a `Cache`.
+///
+/// Entries are identified by a key (e.g. `items`) and stored as raw bytes in
+/// the `Encoding` returned by the backend.
+pub trait Storage: Send + Sync + fmt::Debug {
+    fn encoding(&self) -> Encoding {
+        Encoding::Json
+    }
+
+    fn load(&self, key: &str) -> Result<Vec<u8>, StorageError>;
+
+    fn store(&self, key: &str, data: &[u8]) -> Result<(), StorageError>;
+}
+
+/// Storage of `inner` whose keys are prefixed with a namespace, so that
+/// several caches can share a backend without their entries colliding.
+#[derive(Debug)]
+pub(crate) struct Namespaced {
+    inner: Arc<dyn Storage>,
+    namespace: String,
+}
+
+impl Namespaced {
+    pub(crate) fn new(inner: Arc<dyn Storage>, namespace: String) -> Self {
+        Self { inner, namespace }
+    }
+
+    fn key(&self, key: &str) -> String {
+        format!("{}/{key}", self.namespace)
+    }
+}
+
+impl Storage for Namespaced {
+    fn encoding(&self) -> Encoding {
+        self.inner.encoding()
+    }
+
+    fn load(&self, key: &str) -> Result<Vec<u8>, StorageError> {
+        self.inner.load(&self.key(key))
+    }
+
+    fn store(&self, key: &str, data: &[u8]) -> Result<(), StorageError> {
+        self.inner.store(&self.key(key), data)
+    }
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum Encoding {
+    #[default]
+    Json,
+    /// Compact MessagePack encoding, enabled by the `binary` feature.
+    #[cfg(feature = "binary")]
+    Binary,
+}
+
+#[derive(Debug, Error)]
+pub enum StorageError {
+    #[error("`{0}` not found in storage")]
+    NotFound(String),
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+    #[cfg(feature = "sqlite")]
+    #[error(transparent)]
+    Sqlite(#[from] rusqlite::Error),
+}
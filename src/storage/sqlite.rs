@@ -0,0 +1,63 @@
+use crate::storage::{Encoding, Storage, StorageError};
+use rusqlite::{Connection, OptionalExtension, params};
+use std::{path::Path, sync::Mutex};
+
+/// Stores entries in a single SQLite database, which can be shared by several
+/// tools. Enabled by the `sqlite` feature.
+#[derive(Debug)]
+pub struct SqliteStorage {
+    connection: Mutex<Connection>,
+    encoding: Encoding,
+}
+
+impl SqliteStorage {
+    pub fn open(path: impl AsRef<Path>) -> Result<Self, StorageError> {
+        Self::init(Connection::open(path)?)
+    }
+
+    pub fn open_in_memory() -> Result<Self, StorageError> {
+        Self::init(Connection::open_in_memory()?)
+    }
+
+    /// Sets the encoding used for the entries written from now on.
+    pub fn with_encoding(mut self, encoding: Encoding) -> Self {
+        self.encoding = encoding;
+        self
+    }
+
+    fn init(connection: Connection) -> Result<Self, StorageError> {
+        connection.execute(
+            "CREATE TABLE IF NOT EXISTS cache (key TEXT PRIMARY KEY, data BLOB NOT NULL)",
+            [],
+        )?;
+        Ok(Self {
+            connection: Mutex::new(connection),
+            encoding: Encoding::default(),
+        })
+    }
+}
+
+impl Storage for SqliteStorage {
+    fn encoding(&self) -> Encoding {
+        self.encoding
+    }
+
+    fn load(&self, key: &str) -> Result<Vec<u8>, StorageError> {
+        self.connection
+            .lock()
+            .unwrap()
+            .query_row("SELECT data FROM cache WHERE key = ?1", [key], |row| {
+                row.get(0)
+            })
+            .optional()?
+            .ok_or_else(|| StorageError::NotFound(key.to_owned()))
+    }
+
+    fn store(&self, key: &str, data: &[u8]) -> Result<(), StorageError> {
+        self.connection.lock().unwrap().execute(
+            "INSERT OR REPLACE INTO cache (key, data) VALUES (?1, ?2)",
+            params![key, data],
+        )?;
+        Ok(())
+    }
+}